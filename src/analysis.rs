@@ -0,0 +1,331 @@
+use std::sync::{Arc, Mutex};
+
+use rayon::prelude::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    card::CardBeforeBidding,
+    deck::Deck,
+    game_record::{Choice, Decision, GameRecord, HandRecord},
+    hand::HandBeforeBidding,
+    hand_state::HandState,
+    hands_iterator::{CardConstraint, CardLocation, Constraints},
+    players::replay::{Branch, ReplayPlayer},
+    position::Position,
+    rules::Rules,
+    suit::Suit,
+};
+
+const ANALYSIS_SAMPLES: u64 = 200;
+
+const POSITIONS: [Position; 4] = [
+    Position::North,
+    Position::East,
+    Position::South,
+    Position::West,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct DecisionCost {
+    pub(crate) hand: usize,
+    pub(crate) player: Position,
+    pub(crate) choice: Choice,
+    pub(crate) best: Choice,
+    pub(crate) cost: f64,
+}
+
+pub(crate) fn analyze(record: &GameRecord) -> Result<Vec<DecisionCost>, String> {
+    let rules = record.rules()?;
+    let mut costs = Vec::new();
+    for (index, hand) in record.hands.iter().enumerate() {
+        costs.extend(
+            analyze_hand(rules, hand)
+                .map_err(|error| format!("Hand {}: {}", index + 1, error))?
+                .into_iter()
+                .map(|(player, choice, best, cost)| DecisionCost {
+                    hand: index + 1,
+                    player,
+                    choice,
+                    best,
+                    cost,
+                }),
+        );
+    }
+    costs.sort_by(|a, b| b.cost.total_cmp(&a.cost).then(a.hand.cmp(&b.hand)));
+    Ok(costs)
+}
+
+struct Deal<'a> {
+    rules: Rules,
+    dealer: Position,
+    up_card: CardBeforeBidding,
+    hands: [HandBeforeBidding; 4],
+    decisions: &'a [Decision],
+}
+
+impl Deal<'_> {
+    fn rollout(
+        &self,
+        hands: [HandBeforeBidding; 4],
+        at: usize,
+        option: usize,
+        player: &Position,
+    ) -> (i8, Branch) {
+        let branch = Arc::new(Mutex::new(Branch::create(
+            self.decisions.to_vec(),
+            at,
+            option,
+        )));
+        let mut players =
+            POSITIONS.map(|position| ReplayPlayer::create(position, self.rules, branch.clone()));
+        let mut hand_state = HandState::create(self.dealer, self.up_card, hands);
+        let (winner, score) = loop {
+            if let Some(result) = hand_state.step(&mut players) {
+                break result;
+            }
+        };
+        drop(players);
+        let branch = Arc::try_unwrap(branch).unwrap().into_inner().unwrap();
+        if winner.team_index() == player.team_index() {
+            (score as i8, branch)
+        } else {
+            (-(score as i8), branch)
+        }
+    }
+
+    fn constraints(&self, player: &Position, branch: &Branch) -> Constraints {
+        let location = |position: &Position| match position.seen_from_south(player) {
+            Position::West => CardLocation::West,
+            Position::North => CardLocation::North,
+            _ => CardLocation::East,
+        };
+        let discarded = self
+            .decisions
+            .iter()
+            .any(|decision| matches!(decision.choice, Choice::Discard { .. }));
+        Constraints {
+            pinned: branch
+                .played
+                .iter()
+                .filter(|(position, card)| position != player && *card != self.up_card)
+                .map(|(position, card)| CardConstraint {
+                    card: *card,
+                    location: location(position),
+                })
+                .collect(),
+            excluded: branch
+                .voids
+                .iter()
+                .filter(|(position, _, _)| {
+                    position != player && (*position != self.dealer || !discarded)
+                })
+                .flat_map(|(position, led, trump)| {
+                    Deck::create_all_cards(&self.rules)
+                        .into_iter()
+                        .filter(|card| follows_suit(card, led, trump))
+                        .map(|card| CardConstraint {
+                            card,
+                            location: location(position),
+                        })
+                })
+                .collect(),
+        }
+    }
+
+    fn score_options(
+        &self,
+        at: usize,
+        player: &Position,
+        options: usize,
+        branch: &Branch,
+    ) -> Option<Vec<f64>> {
+        let (count, totals) = HandState::sampled_deals(
+            &self.rules,
+            &[self.up_card],
+            self.hands[player.index()].clone(),
+            &self.constraints(player, branch),
+            ANALYSIS_SAMPLES,
+            at as u64,
+        )
+        .filter_map(|sampled| {
+            let hands =
+                POSITIONS.map(|position| sampled[position.seen_from_south(player).index()].clone());
+            let (score, branch) = self.rollout(hands.clone(), at, 0, player);
+            if branch.diverged {
+                return None;
+            }
+            let mut scores = vec![score as i32];
+            scores.extend(
+                (1..options).map(|option| self.rollout(hands.clone(), at, option, player).0 as i32),
+            );
+            Some((1, scores))
+        })
+        .reduce(
+            || (0, vec![0; options]),
+            |(count, totals), (other_count, other_totals)| {
+                (
+                    count + other_count,
+                    totals
+                        .iter()
+                        .zip(other_totals)
+                        .map(|(total, other)| total + other)
+                        .collect(),
+                )
+            },
+        );
+        if count == 0 {
+            return None;
+        }
+        Some(
+            totals
+                .into_iter()
+                .map(|total| total as f64 / count as f64)
+                .collect(),
+        )
+    }
+}
+
+fn follows_suit(card: &CardBeforeBidding, led: &Suit, trump: &Suit) -> bool {
+    if led == trump {
+        card.is_trump(trump)
+    } else {
+        card.suit == *led && !card.is_trump(trump)
+    }
+}
+
+fn analyze_hand(
+    rules: Rules,
+    hand: &HandRecord,
+) -> Result<Vec<(Position, Choice, Choice, f64)>, String> {
+    let deal = Deal {
+        rules,
+        dealer: hand.dealer()?,
+        up_card: hand.up_card()?,
+        hands: hand.dealt_hands()?,
+        decisions: &hand.decisions,
+    };
+    let players = hand
+        .decisions
+        .iter()
+        .map(Decision::position)
+        .collect::<Result<Vec<Position>, String>>()?;
+    players
+        .into_par_iter()
+        .enumerate()
+        .map(|(at, player)| {
+            let choice = &deal.decisions[at].choice;
+            let (_, branch) = deal.rollout(deal.hands.clone(), at, 0, &player);
+            let chosen = branch
+                .options
+                .iter()
+                .position(|option| option == choice)
+                .ok_or_else(|| {
+                    format!(
+                        "Decision {} by {:?} to {} wasn't one of the options",
+                        at + 1,
+                        player,
+                        choice
+                    )
+                })?;
+            let scores = deal
+                .score_options(at, &player, branch.options.len(), &branch)
+                .ok_or_else(|| {
+                    format!(
+                        "No sampled deal matches the play before decision {}",
+                        at + 1
+                    )
+                })?;
+            let best = (0..scores.len())
+                .max_by(|a, b| {
+                    scores[*a]
+                        .total_cmp(&scores[*b])
+                        .then((*a == chosen).cmp(&(*b == chosen)))
+                })
+                .unwrap_or(chosen);
+            Ok((
+                player,
+                choice.clone(),
+                branch.options[best].clone(),
+                scores[best] - scores[chosen],
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand_record(decisions: Vec<Decision>) -> HandRecord {
+        HandRecord {
+            dealer: "S".to_string(),
+            up_card: "9H".to_string(),
+            hands: [
+                ["9C", "10C", "QC", "9S", "10S"],
+                ["KC", "AC", "QS", "KS", "10D"],
+                ["JH", "JD", "AH", "KH", "QH"],
+                ["JC", "JS", "AS", "9D", "QD"],
+            ]
+            .map(|hand| hand.iter().map(|card| card.to_string()).collect()),
+            decisions,
+        }
+    }
+
+    fn decision(player: &str, choice: Choice) -> Decision {
+        Decision {
+            player: player.to_string(),
+            choice,
+        }
+    }
+
+    #[test]
+    fn passing_a_lay_down_hand_is_costly() {
+        let record = GameRecord {
+            joker: false,
+            deck_size: 24,
+            hands: vec![hand_record(vec![
+                decision("W", Choice::OrderUp { yes: false }),
+                decision("N", Choice::OrderUp { yes: false }),
+                decision("E", Choice::OrderUp { yes: false }),
+                decision("S", Choice::OrderUp { yes: false }),
+            ])],
+        };
+        let costs = analyze(&record).unwrap();
+        let dealer_pass = costs
+            .iter()
+            .find(|cost| {
+                cost.player == Position::South && cost.choice == Choice::OrderUp { yes: false }
+            })
+            .unwrap();
+        assert_eq!(1, dealer_pass.hand);
+        assert_eq!(Choice::OrderUp { yes: true }, dealer_pass.best);
+        assert!(dealer_pass.cost > 0.0);
+        assert_eq!(dealer_pass, &costs[0]);
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        let mut hand = hand_record(Vec::new());
+        hand.up_card = "1X".to_string();
+        let record = GameRecord {
+            joker: false,
+            deck_size: 24,
+            hands: vec![hand],
+        };
+        assert!(analyze(&record).unwrap_err().starts_with("Hand 1: "));
+    }
+
+    #[test]
+    fn rejects_decisions_that_were_not_options() {
+        let record = GameRecord {
+            joker: false,
+            deck_size: 24,
+            hands: vec![hand_record(vec![
+                decision("W", Choice::OrderUp { yes: false }),
+                decision("N", Choice::CallAlone { yes: true }),
+            ])],
+        };
+        assert!(analyze(&record)
+            .unwrap_err()
+            .starts_with("Hand 1: Decision 2 by North"));
+    }
+}
@@ -0,0 +1,458 @@
+// A small HTTP/1.1 server for tools that want to query the simulator. Every
+// endpoint takes and returns JSON, and positions, suits and cards are named as
+// in the bot protocol (`S`, `H`, `JD`, ...):
+//
+// POST /evaluate-bids: `hand`, `trump_candidate` and `dealer` (South holds the
+// hand), plus optional `samples`, `seed`, `strategy`, `ignore_other_bids`,
+// `joker` and `deck_size`. Returns the expected value of each possible bid over
+// the same sampled deals.
+//
+// POST /play-card: `position`, `hand`, `trump`, `caller`, optional `alone`,
+// `defender` and `played` (a list of `{"player": "W", "card": "AH"}`), plus
+// optional `strategy`, `joker` and `deck_size`. Returns the strategy's card and
+// the legal cards.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+    thread,
+};
+
+use enum_iterator::IntoEnumIterator;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    hand::HandBeforeBidding,
+    hands_iterator::Constraints,
+    players::registry::Strategy,
+    position::Position,
+    protocol,
+    rules::{DeckSize, Rules},
+    simulation::{self, Bid, HandResult, Scenario},
+    suit::Suit,
+    trick_state::{PlayedCard, TrickState},
+};
+
+pub(crate) const DEFAULT_PORT: u16 = 8080;
+const DEFAULT_SAMPLES: u64 = 10_000;
+const MAX_SAMPLES: u64 = 1_000_000;
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+
+#[derive(Deserialize)]
+struct Settings {
+    #[serde(default)]
+    joker: bool,
+    #[serde(default = "default_deck_size")]
+    deck_size: u8,
+    #[serde(default = "default_strategy")]
+    strategy: String,
+}
+
+#[derive(Deserialize)]
+struct EvaluateBidsRequest {
+    hand: Vec<String>,
+    trump_candidate: String,
+    dealer: String,
+    #[serde(default = "default_samples")]
+    samples: u64,
+    seed: Option<u64>,
+    #[serde(default)]
+    ignore_other_bids: bool,
+    #[serde(flatten)]
+    settings: Settings,
+}
+
+#[derive(Serialize)]
+struct EvaluateBidsResponse {
+    samples: u64,
+    seed: u64,
+    bids: Vec<BidEvaluation>,
+}
+
+#[derive(Serialize)]
+struct BidEvaluation {
+    bid: &'static str,
+    trump: &'static str,
+    alone: bool,
+    bid_count: u64,
+    expected_value: Option<f64>,
+    outcomes: BTreeMap<i8, u64>,
+}
+
+#[derive(Deserialize)]
+struct PlayCardRequest {
+    position: String,
+    hand: Vec<String>,
+    trump: String,
+    caller: String,
+    #[serde(default)]
+    alone: bool,
+    defender: Option<String>,
+    #[serde(default)]
+    played: Vec<PlayedCardRequest>,
+    #[serde(flatten)]
+    settings: Settings,
+}
+
+#[derive(Deserialize)]
+struct PlayedCardRequest {
+    player: String,
+    card: String,
+}
+
+#[derive(Serialize)]
+struct PlayCardResponse {
+    card: String,
+    legal_cards: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+impl Settings {
+    fn rules(&self) -> Result<Rules, String> {
+        Ok(Rules {
+            joker: self.joker,
+            deck_size: DeckSize::from_str(&self.deck_size.to_string())?,
+        })
+    }
+
+    fn strategy(&self) -> Result<Strategy, String> {
+        match Strategy::from_str(&self.strategy)? {
+            Strategy::AdvancedWithConfig(_)
+            | Strategy::SeededRandom {
+                config: Some(_), ..
+            }
+            | Strategy::Human
+            | Strategy::Process(_)
+            | Strategy::Table(_) => Err(format!(
+                "The {} strategy isn't available here",
+                self.strategy
+            )),
+            strategy => Ok(strategy),
+        }
+    }
+}
+
+pub(crate) fn serve(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let _connection = thread::spawn(move || {
+            if let Err(error) = handle_connection(stream) {
+                log::warn!("Couldn't handle a request: {}", error);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    let _ = reader.read_line(&mut request_line)?;
+    let mut words = request_line.split_whitespace();
+    let method = words.next().unwrap_or_default().to_string();
+    let path = words.next().unwrap_or_default().to_string();
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "Invalid Content-Length")
+                })?;
+            }
+        }
+    }
+    let (status, body) = if content_length > MAX_BODY_LENGTH {
+        (413, error_body("Request body is too large"))
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        handle_request(&method, &path, &String::from_utf8_lossy(&body))
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+pub(crate) fn handle_request(method: &str, path: &str, body: &str) -> (u16, String) {
+    let result = match (method, path) {
+        ("POST", "/evaluate-bids") => parse_body(body).and_then(evaluate_bids).map(to_json),
+        ("POST", "/play-card") => parse_body(body).and_then(play_card).map(to_json),
+        (_, "/evaluate-bids" | "/play-card") => {
+            return (405, error_body(&format!("{} isn't allowed", method)))
+        }
+        _ => return (404, error_body(&format!("{} wasn't found", path))),
+    };
+    match result {
+        Ok(body) => (200, body),
+        Err(error) => (400, error_body(&error)),
+    }
+}
+
+fn evaluate_bids(request: EvaluateBidsRequest) -> Result<EvaluateBidsResponse, String> {
+    if request.samples == 0 || request.samples > MAX_SAMPLES {
+        return Err(format!("samples must be between 1 and {}", MAX_SAMPLES));
+    }
+    let strategy = request.settings.strategy()?;
+    let trump_candidate = protocol::parse_card(&request.trump_candidate)?;
+    let mut scenario = Scenario {
+        rules: request.settings.rules()?,
+        dealer: Position::from_str(&request.dealer)?,
+        trump_candidate,
+        hand: HandBeforeBidding {
+            cards: parse_cards(&request.hand)?,
+        },
+        bid: Bid::OrderUp,
+        go_alone: false,
+        ignore_other_bids: request.ignore_other_bids,
+        strategies: [
+            strategy.clone(),
+            strategy.clone(),
+            strategy.clone(),
+            strategy,
+        ],
+        constraints: Constraints::default(),
+        passes: Vec::new(),
+        discard: None,
+    };
+    scenario.validate()?;
+    let seed = request.seed.unwrap_or_else(|| thread_rng().gen());
+    let bids = std::iter::once(Bid::OrderUp).chain(
+        Suit::into_enum_iter()
+            .filter(|suit| *suit != trump_candidate.suit)
+            .map(Bid::Call),
+    );
+    let mut evaluations = Vec::new();
+    for bid in bids {
+        for go_alone in [false, true] {
+            scenario.bid = bid;
+            scenario.go_alone = go_alone;
+            let results = simulation::simulate_sampled(&scenario, request.samples, seed);
+            evaluations.push(BidEvaluation {
+                bid: match bid {
+                    Bid::OrderUp => "order_up",
+                    Bid::Call(_) => "call",
+                },
+                trump: protocol::suit_name(&match bid {
+                    Bid::OrderUp => trump_candidate.suit,
+                    Bid::Call(trump) => trump,
+                }),
+                alone: go_alone,
+                bid_count: results.bid_count(),
+                expected_value: results.expected_value(),
+                outcomes: results
+                    .result_counts
+                    .iter()
+                    .filter_map(|(result, count)| match result {
+                        HandResult::InconsistentBidding | HandResult::DifferentBidResult => None,
+                        HandResult::ExpectedBidResult { score } => Some((*score, *count)),
+                    })
+                    .collect(),
+            });
+        }
+    }
+    Ok(EvaluateBidsResponse {
+        samples: request.samples,
+        seed,
+        bids: evaluations,
+    })
+}
+
+fn play_card(request: PlayCardRequest) -> Result<PlayCardResponse, String> {
+    let rules = request.settings.rules()?;
+    let position = Position::from_str(&request.position)?;
+    let trump = protocol::parse_suit(&request.trump)?;
+    let caller = Position::from_str(&request.caller)?;
+    let bid_result = match (&request.defender, request.alone) {
+        (Some(defender), _) => BidResultCalled::DefendedAlone {
+            trump,
+            caller,
+            defender: Position::from_str(defender)?,
+        },
+        (None, true) => BidResultCalled::CalledAlone { trump, caller },
+        (None, false) => BidResultCalled::Called { trump, caller },
+    };
+    let hand = HandBeforeBidding::update_bowers(
+        HandBeforeBidding {
+            cards: parse_cards(&request.hand)?,
+        },
+        &trump,
+    );
+    if hand.cards.is_empty() {
+        return Err("The hand is empty".to_string());
+    }
+    let mut cards_played = Vec::new();
+    for played_card in &request.played {
+        cards_played.push(PlayedCard {
+            player: Position::from_str(&played_card.player)?,
+            card: Card::update_bowers(vec![protocol::parse_card(&played_card.card)?], &trump)[0],
+        });
+    }
+    let card = request
+        .settings
+        .strategy()?
+        .create_player(position, rules)
+        .play_card(&hand, &bid_result, &cards_played);
+    Ok(PlayCardResponse {
+        card: protocol::played_card_name(&TrickState::legal_card(&hand, card, &cards_played)),
+        legal_cards: TrickState::legal_cards(&hand, &cards_played)
+            .iter()
+            .map(protocol::played_card_name)
+            .collect(),
+    })
+}
+
+fn parse_cards(names: &[String]) -> Result<Vec<CardBeforeBidding>, String> {
+    names
+        .iter()
+        .map(|name| protocol::parse_card(name))
+        .collect()
+}
+
+fn parse_body<'a, T: Deserialize<'a>>(body: &'a str) -> Result<T, String> {
+    serde_json::from_str(body).map_err(|error| format!("Invalid request: {}", error))
+}
+
+fn to_json(value: impl Serialize) -> String {
+    serde_json::to_string(&value).unwrap()
+}
+
+fn error_body(error: &str) -> String {
+    to_json(ErrorResponse {
+        error: error.to_string(),
+    })
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "",
+    }
+}
+
+fn default_deck_size() -> u8 {
+    24
+}
+
+fn default_strategy() -> String {
+    "advanced".to_string()
+}
+
+fn default_samples() -> u64 {
+    DEFAULT_SAMPLES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn json(body: &str) -> Value {
+        serde_json::from_str(body).unwrap()
+    }
+
+    #[test]
+    fn evaluate_bids() {
+        let (status, body) = handle_request(
+            "POST",
+            "/evaluate-bids",
+            r#"{"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H", "dealer": "N", "samples": 100, "seed": 3, "ignore_other_bids": true}"#,
+        );
+        assert_eq!(200, status, "{}", body);
+        let body = json(&body);
+        let bids = body["bids"].as_array().unwrap();
+        assert_eq!(8, bids.len());
+        assert_eq!("order_up", bids[0]["bid"]);
+        assert_eq!("H", bids[0]["trump"]);
+        assert_eq!(100, bids[0]["bid_count"]);
+        assert!(bids[0]["expected_value"].as_f64().unwrap() > 1.0);
+        assert!(bids
+            .iter()
+            .all(|bid| bid["trump"] != "H" || bid["bid"] == "order_up"));
+    }
+
+    #[test]
+    fn play_card_follows_suit() {
+        let (status, body) = handle_request(
+            "POST",
+            "/play-card",
+            r#"{"position": "S", "hand": ["JD", "AS", "9C"], "trump": "H", "caller": "E", "played": [{"player": "E", "card": "10H"}]}"#,
+        );
+        assert_eq!(200, status, "{}", body);
+        let body = json(&body);
+        assert_eq!("JD", body["card"]);
+        assert_eq!(json(r#"["JD"]"#), body["legal_cards"]);
+    }
+
+    #[test]
+    fn invalid_requests() {
+        assert_eq!(404, handle_request("GET", "/nowhere", "").0);
+        assert_eq!(405, handle_request("GET", "/play-card", "").0);
+        assert_eq!(400, handle_request("POST", "/play-card", "{").0);
+        let (status, body) = handle_request(
+            "POST",
+            "/evaluate-bids",
+            r#"{"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H", "dealer": "N", "strategy": "human"}"#,
+        );
+        assert_eq!(400, status);
+        assert_eq!(
+            "The human strategy isn't available here",
+            json(&body)["error"]
+        );
+        let (status, body) = handle_request(
+            "POST",
+            "/evaluate-bids",
+            r#"{"hand": ["JH", "JD", "AH", "KH", "9H"], "trump_candidate": "9H", "dealer": "N"}"#,
+        );
+        assert_eq!(400, status);
+        assert_eq!(
+            format!(
+                "{} appears more than once",
+                CardBeforeBidding::from_str("9H").unwrap()
+            ),
+            json(&body)["error"]
+        );
+    }
+
+    #[test]
+    fn serves_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let _server = thread::spawn(move || serve(listener));
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let body = r#"{"position": "N", "hand": ["AS"], "trump": "C", "caller": "N"}"#;
+        write!(
+            stream,
+            "POST /play-card HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.ends_with(r#"{"card":"AS","legal_cards":["AS"]}"#));
+    }
+}
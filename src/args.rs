@@ -1,6 +1,22 @@
 use clap::{command, ArgGroup, Args, Parser, Subcommand};
+use std::{net::IpAddr, path::PathBuf};
 
-use crate::{card::CardBeforeBidding, position::Position, suit::Suit};
+use crate::{
+    api,
+    bid_result::BidResultCalled,
+    card::CardBeforeBidding,
+    hand::HandBeforeBidding,
+    hands_iterator::{CardConstraint, Constraints},
+    lead::LeadScenario,
+    match_play::FirstDealer,
+    players::registry::Strategy,
+    position::Position,
+    report::OutputFormat,
+    rules::{DeckSize, Rules},
+    server::DEFAULT_PORT,
+    simulation::{Bid, Pass, Scenario},
+    suit::Suit,
+};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -12,8 +28,174 @@ pub(crate) struct EuchreArgs {
 
 #[derive(Subcommand)]
 pub(crate) enum Commands {
-    PlayGame,
+    Analyze(AnalyzeArgs),
+    Duplicate(DuplicateArgs),
+    EvaluateDiscards(EvaluateDiscardsArgs),
+    EvaluateLead(EvaluateLeadArgs),
+    GenerateBidTable(GenerateBidTableArgs),
+    PlayGame(PlayGameArgs),
+    PlayTwoHanded(PlayTwoHandedArgs),
+    PlayBidEuchre,
+    PlayMatch(PlayMatchArgs),
+    Ratings(RatingsArgs),
+    Serve(ServeArgs),
+    Server(ServerArgs),
+    SimulateBatch(SimulateBatchArgs),
     SimulateHand(SimulateHandArgs),
+    ShowBidTable(ShowBidTableArgs),
+    Tournament(TournamentArgs),
+    Tui(PlayGameArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct AnalyzeArgs {
+    #[arg(value_name = "path")]
+    pub(crate) game_file: PathBuf,
+
+    #[arg(long, default_value_t = 10, value_name = "count")]
+    pub(crate) top: usize,
+}
+
+#[derive(Args)]
+pub(crate) struct PlayGameArgs {
+    #[command(flatten)]
+    pub(crate) seats: SeatsArgs,
+
+    #[arg(long, value_name = "path")]
+    pub(crate) record: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct PlayTwoHandedArgs {
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct DuplicateArgs {
+    #[arg(long, num_args = 2, default_values = ["advanced", "basic"], value_name = "strategy")]
+    pub(crate) players: Vec<Strategy>,
+
+    #[arg(long, default_value_t = 1000, value_name = "count")]
+    pub(crate) boards: u32,
+
+    #[arg(long, action)]
+    pub(crate) show_boards: bool,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct PlayMatchArgs {
+    #[arg(
+        long,
+        default_value_t = 1000,
+        value_name = "count",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub(crate) games: u32,
+
+    #[arg(long, default_value = "rotate", value_name = "random|rotate")]
+    pub(crate) first_dealer: FirstDealer,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct TournamentArgs {
+    #[arg(long, num_args = 2.., default_values = ["basic", "advanced"], value_name = "strategy")]
+    pub(crate) players: Vec<Strategy>,
+
+    #[arg(long, default_value_t = 500, value_name = "count")]
+    pub(crate) boards: u32,
+
+    #[arg(long, value_name = "path")]
+    pub(crate) ratings_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct RatingsArgs {
+    #[arg(long, default_value = "ratings.tsv", value_name = "path")]
+    pub(crate) ratings_file: PathBuf,
+}
+
+#[derive(Args)]
+pub(crate) struct ServeArgs {
+    #[arg(long, default_value_t = api::DEFAULT_PORT, value_name = "port")]
+    pub(crate) port: u16,
+}
+
+#[derive(Args)]
+pub(crate) struct ServerArgs {
+    #[arg(long, default_value = "127.0.0.1", value_name = "address")]
+    pub(crate) bind: IpAddr,
+
+    #[arg(long, default_value_t = DEFAULT_PORT, value_name = "port")]
+    pub(crate) port: u16,
+
+    #[arg(long, num_args = 1..=4, default_values = ["S"], value_name = "N|E|S|W")]
+    pub(crate) remote_seats: Vec<Position>,
+
+    #[arg(long, default_value_t = 1, value_name = "count")]
+    pub(crate) games: u32,
+
+    #[arg(long, default_value_t = 300, value_name = "seconds")]
+    pub(crate) timeout: u64,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct SeatsArgs {
+    #[arg(long, default_value = "advanced", value_name = "strategy")]
+    pub(crate) north: Strategy,
+
+    #[arg(long, default_value = "advanced", value_name = "strategy")]
+    pub(crate) east: Strategy,
+
+    #[arg(long, default_value = "advanced", value_name = "strategy")]
+    pub(crate) south: Strategy,
+
+    #[arg(long, default_value = "advanced", value_name = "strategy")]
+    pub(crate) west: Strategy,
+}
+
+impl SeatsArgs {
+    pub(crate) fn strategies(&self) -> [Strategy; 4] {
+        [
+            self.north.clone(),
+            self.east.clone(),
+            self.south.clone(),
+            self.west.clone(),
+        ]
+    }
+}
+
+#[derive(Args)]
+pub(crate) struct RulesArgs {
+    #[arg(long, action)]
+    pub(crate) joker: bool,
+
+    #[arg(long, default_value = "24", value_name = "24|28|32")]
+    pub(crate) deck_size: DeckSize,
+}
+
+impl From<&RulesArgs> for Rules {
+    fn from(value: &RulesArgs) -> Self {
+        Rules {
+            joker: value.joker,
+            deck_size: value.deck_size,
+        }
+    }
 }
 
 #[derive(Args)]
@@ -43,4 +225,226 @@ pub(crate) struct SimulateHandArgs {
 
     #[arg(long, required = true, num_args = 5, value_name = "card")]
     pub(crate) hand: Vec<CardBeforeBidding>,
+
+    #[arg(long, num_args = 1.., value_name = "card:W|N|E|K")]
+    pub(crate) pin: Vec<CardConstraint>,
+
+    #[arg(long, num_args = 1.., value_name = "card:W|N|E|K")]
+    pub(crate) exclude: Vec<CardConstraint>,
+
+    #[arg(long, num_args = 1.., value_name = "position:1|2")]
+    pub(crate) passed: Vec<Pass>,
+
+    #[arg(long, default_value = "text", value_name = "text|json|csv")]
+    pub(crate) format: OutputFormat,
+
+    #[arg(long, value_name = "path", conflicts_with("no_cache"))]
+    pub(crate) cache_dir: Option<PathBuf>,
+
+    #[arg(long, action)]
+    pub(crate) no_cache: bool,
+
+    #[command(flatten)]
+    pub(crate) seats: SeatsArgs,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct SimulateBatchArgs {
+    #[arg(value_name = "path")]
+    pub(crate) scenario_file: PathBuf,
+
+    #[arg(long, value_name = "count")]
+    pub(crate) samples: Option<u64>,
+
+    #[arg(long, value_name = "seed")]
+    pub(crate) seed: Option<u64>,
+
+    #[arg(long, default_value = "text", value_name = "text|json|csv")]
+    pub(crate) format: OutputFormat,
+
+    #[arg(long, value_name = "path")]
+    pub(crate) output: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub(crate) seats: SeatsArgs,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct EvaluateDiscardsArgs {
+    #[arg(long, required = true, value_name = "trump candidate")]
+    pub(crate) trump_candidate: CardBeforeBidding,
+
+    #[arg(long, action)]
+    pub(crate) go_alone: bool,
+
+    #[arg(long, action)]
+    pub(crate) ignore_other_bids: bool,
+
+    #[arg(long, required = true, num_args = 5, value_name = "card")]
+    pub(crate) hand: Vec<CardBeforeBidding>,
+
+    #[arg(long, value_name = "count")]
+    pub(crate) samples: Option<u64>,
+
+    #[command(flatten)]
+    pub(crate) seats: SeatsArgs,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+impl From<&EvaluateDiscardsArgs> for Scenario {
+    fn from(value: &EvaluateDiscardsArgs) -> Self {
+        Scenario {
+            rules: Rules::from(&value.rules),
+            dealer: Position::South,
+            trump_candidate: value.trump_candidate,
+            hand: HandBeforeBidding {
+                cards: value.hand.clone(),
+            },
+            bid: Bid::OrderUp,
+            go_alone: value.go_alone,
+            ignore_other_bids: value.ignore_other_bids,
+            strategies: value.seats.strategies(),
+            constraints: Constraints::default(),
+            passes: Vec::new(),
+            discard: None,
+        }
+    }
+}
+
+#[derive(Args)]
+pub(crate) struct EvaluateLeadArgs {
+    #[arg(long, required = true, value_name = "trump suit")]
+    pub(crate) trump: Suit,
+
+    #[arg(long, required = true, value_name = "position")]
+    pub(crate) caller: Position,
+
+    #[arg(long, action)]
+    pub(crate) alone: bool,
+
+    #[arg(long, requires("alone"), value_name = "position")]
+    pub(crate) defender: Option<Position>,
+
+    #[arg(long, required = true, value_name = "trump candidate")]
+    pub(crate) trump_candidate: CardBeforeBidding,
+
+    #[arg(long, action)]
+    pub(crate) ordered_up: bool,
+
+    #[arg(long, required = true, value_name = "position")]
+    pub(crate) leader: Position,
+
+    #[arg(long, required = true, num_args = 5, value_name = "card")]
+    pub(crate) hand: Vec<CardBeforeBidding>,
+
+    #[arg(long, value_name = "count")]
+    pub(crate) samples: Option<u64>,
+
+    #[command(flatten)]
+    pub(crate) seats: SeatsArgs,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct GenerateBidTableArgs {
+    #[arg(long, required = true, value_name = "path")]
+    pub(crate) output: PathBuf,
+
+    #[arg(long, default_value_t = 200, value_name = "count")]
+    pub(crate) samples: u32,
+
+    #[arg(long, value_name = "seed")]
+    pub(crate) seed: Option<u64>,
+
+    #[command(flatten)]
+    pub(crate) seats: SeatsArgs,
+
+    #[command(flatten)]
+    pub(crate) rules: RulesArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct ShowBidTableArgs {
+    #[arg(value_name = "path")]
+    pub(crate) table: PathBuf,
+
+    #[arg(long, requires_all = ["dealer", "hand"], value_name = "trump candidate")]
+    pub(crate) trump_candidate: Option<CardBeforeBidding>,
+
+    #[arg(long, requires = "trump_candidate", value_name = "position")]
+    pub(crate) dealer: Option<Position>,
+
+    #[arg(long, requires = "trump_candidate", num_args = 5, value_name = "card")]
+    pub(crate) hand: Vec<CardBeforeBidding>,
+}
+
+impl TryFrom<&EvaluateLeadArgs> for LeadScenario {
+    type Error = String;
+
+    fn try_from(value: &EvaluateLeadArgs) -> Result<Self, Self::Error> {
+        let (trump, caller) = (value.trump, value.caller);
+        let bid_result = match (value.alone, value.defender) {
+            (false, _) => BidResultCalled::Called { trump, caller },
+            (true, None) => BidResultCalled::CalledAlone { trump, caller },
+            (true, Some(defender)) if defender.team_index() != caller.team_index() => {
+                BidResultCalled::DefendedAlone {
+                    trump,
+                    caller,
+                    defender,
+                }
+            }
+            (true, Some(defender)) => {
+                return Err(format!("{:?} can't defend against their partner", defender))
+            }
+        };
+        Ok(LeadScenario {
+            rules: Rules::from(&value.rules),
+            bid_result,
+            trump_candidate: value.trump_candidate,
+            ordered_up: value.ordered_up,
+            leader: value.leader,
+            hand: HandBeforeBidding {
+                cards: value.hand.clone(),
+            },
+            strategies: value.seats.strategies(),
+        })
+    }
+}
+
+impl From<&SimulateHandArgs> for Scenario {
+    fn from(value: &SimulateHandArgs) -> Self {
+        let bid = match (value.order_up, value.call_suit) {
+            (true, _) => Bid::OrderUp,
+            (false, Some(trump)) => Bid::Call(trump),
+            (false, None) => panic!("You must either order the trump candidate up (--order-up) or call a trump suit (--call-suit {{C|D|H|S}}"),
+        };
+        Scenario {
+            rules: Rules::from(&value.rules),
+            dealer: value.dealer,
+            trump_candidate: value.trump_candidate,
+            hand: HandBeforeBidding {
+                cards: value.hand.clone(),
+            },
+            bid,
+            go_alone: value.go_alone,
+            ignore_other_bids: value.ignore_other_bids,
+            strategies: value.seats.strategies(),
+            constraints: Constraints {
+                pinned: value.pin.clone(),
+                excluded: value.exclude.clone(),
+            },
+            passes: value.passed.clone(),
+            discard: None,
+        }
+    }
 }
@@ -0,0 +1,320 @@
+use enum_iterator::IntoEnumIterator;
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::Card,
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    position::Position,
+    suit::Suit,
+    trick_state::{PlayedCard, TrickState},
+};
+
+pub(crate) const MINIMUM_BID: u8 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strain {
+    Trump(Suit),
+    High,
+    Low,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionBid {
+    Tricks { tricks: u8, strain: Strain },
+    ShootTheMoon { strain: Strain },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contract {
+    pub bidder: Position,
+    pub bid: AuctionBid,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum AuctionResult {
+    Won { contract: Contract },
+    NoOneBid,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct AuctionState {
+    pub(crate) dealer: Position,
+    pub(crate) phase: AuctionPhase,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum AuctionPhase {
+    Bidding {
+        bidder: Position,
+        high_bid: Option<Contract>,
+    },
+    Done {
+        auction_result: AuctionResult,
+    },
+}
+
+impl Strain {
+    pub fn all() -> impl Iterator<Item = Strain> {
+        Suit::into_enum_iter()
+            .map(Strain::Trump)
+            .chain([Strain::High, Strain::Low])
+    }
+
+    pub fn trump(&self) -> Option<Suit> {
+        match self {
+            Self::Trump(trump) => Some(*trump),
+            Self::High | Self::Low => None,
+        }
+    }
+
+    pub(crate) fn update_bowers(&self, hand: HandBeforeBidding) -> Hand {
+        match self {
+            Self::Trump(trump) => HandBeforeBidding::update_bowers(hand, trump),
+            Self::High | Self::Low => Hand {
+                cards: hand
+                    .cards
+                    .into_iter()
+                    .map(|card| Card {
+                        suit: card.suit,
+                        rank: card.rank.into(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    pub(crate) fn get_winning_position(&self, cards_played: &[PlayedCard]) -> Position {
+        match self {
+            Self::Trump(trump) => TrickState::get_winning_position(trump, cards_played),
+            Self::High | Self::Low => {
+                let led_suit = cards_played[0].card.suit;
+                let following_suit = cards_played
+                    .iter()
+                    .filter(|played_card| played_card.card.suit == led_suit);
+                let winner = if *self == Self::High {
+                    following_suit.max_by_key(|played_card| played_card.card.rank)
+                } else {
+                    following_suit.min_by_key(|played_card| played_card.card.rank)
+                };
+                winner.unwrap().player
+            }
+        }
+    }
+}
+
+impl AuctionBid {
+    pub fn strain(&self) -> Strain {
+        match self {
+            Self::Tricks { strain, .. } | Self::ShootTheMoon { strain } => *strain,
+        }
+    }
+
+    pub fn tricks(&self) -> u8 {
+        match self {
+            Self::Tricks { tricks, .. } => *tricks,
+            Self::ShootTheMoon { .. } => 6,
+        }
+    }
+
+    fn level(&self) -> u8 {
+        match self {
+            Self::Tricks { tricks, .. } => *tricks,
+            Self::ShootTheMoon { .. } => 7,
+        }
+    }
+
+    pub fn is_valid_over(&self, high_bid: Option<&Contract>) -> bool {
+        match self {
+            Self::Tricks { tricks, .. } if !(MINIMUM_BID..=6).contains(tricks) => false,
+            _ => match high_bid {
+                Some(contract) => self.level() > contract.bid.level(),
+                None => true,
+            },
+        }
+    }
+}
+
+impl Contract {
+    pub fn is_alone(&self) -> bool {
+        matches!(self.bid, AuctionBid::ShootTheMoon { .. })
+    }
+
+    pub fn bid_result(&self) -> Option<BidResultCalled> {
+        let trump = self.bid.strain().trump()?;
+        Some(if self.is_alone() {
+            BidResultCalled::CalledAlone {
+                trump,
+                caller: self.bidder,
+            }
+        } else {
+            BidResultCalled::Called {
+                trump,
+                caller: self.bidder,
+            }
+        })
+    }
+
+    pub fn next_position_playing(&self, position: &Position) -> Position {
+        let next_position = position.next_position_bidding();
+        if self.is_alone() && next_position == self.bidder.partner() {
+            next_position.next_position_bidding()
+        } else {
+            next_position
+        }
+    }
+}
+
+impl AuctionState {
+    pub(crate) fn create(dealer: Position) -> AuctionState {
+        AuctionState {
+            dealer,
+            phase: AuctionPhase::Bidding {
+                bidder: dealer.next_position_bidding(),
+                high_bid: None,
+            },
+        }
+    }
+
+    pub(crate) fn step(
+        &mut self,
+        players: &mut [impl Player; 4],
+        hands: &[HandBeforeBidding; 4],
+    ) -> Option<AuctionResult> {
+        match &mut self.phase {
+            AuctionPhase::Bidding { bidder, high_bid } => {
+                let bidder_index = bidder.index();
+                match players[bidder_index].bid_auction(
+                    &hands[bidder_index],
+                    &self.dealer,
+                    high_bid.as_ref(),
+                ) {
+                    Some(bid) if bid.is_valid_over(high_bid.as_ref()) => {
+                        log::info!("{:?} bid {:?}", bidder, bid);
+                        *high_bid = Some(Contract {
+                            bidder: *bidder,
+                            bid,
+                        });
+                    }
+                    _ => log::info!("{:?} passed", bidder),
+                }
+                if *bidder == self.dealer {
+                    self.phase = AuctionPhase::Done {
+                        auction_result: match high_bid.take() {
+                            Some(contract) => AuctionResult::Won { contract },
+                            None => AuctionResult::NoOneBid,
+                        },
+                    };
+                } else {
+                    *bidder = bidder.next_position_bidding();
+                }
+                None
+            }
+            AuctionPhase::Done { auction_result } => Some(auction_result.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rank_with_bowers::RankWithBowers, suit::Suit};
+    use test_case::test_case;
+
+    #[derive(Clone)]
+    struct FixedBidder {
+        bid: Option<AuctionBid>,
+    }
+
+    impl Player for FixedBidder {
+        fn bid_auction(
+            &mut self,
+            _hand: &HandBeforeBidding,
+            _dealer: &Position,
+            _high_bid: Option<&Contract>,
+        ) -> Option<AuctionBid> {
+            self.bid
+        }
+    }
+
+    fn tricks(tricks: u8) -> Option<AuctionBid> {
+        Some(AuctionBid::Tricks {
+            tricks,
+            strain: Strain::Trump(Suit::Hearts),
+        })
+    }
+
+    fn run_auction(bids: [Option<AuctionBid>; 4], dealer: Position) -> AuctionResult {
+        let mut players = bids.map(|bid| FixedBidder { bid });
+        let hands = [
+            HandBeforeBidding { cards: Vec::new() },
+            HandBeforeBidding { cards: Vec::new() },
+            HandBeforeBidding { cards: Vec::new() },
+            HandBeforeBidding { cards: Vec::new() },
+        ];
+        let mut auction_state = AuctionState::create(dealer);
+        loop {
+            if let Some(auction_result) = auction_state.step(&mut players, &hands) {
+                return auction_result;
+            }
+        }
+    }
+
+    #[test]
+    fn everyone_passes() {
+        assert_eq!(
+            AuctionResult::NoOneBid,
+            run_auction([None, None, None, None], Position::North)
+        );
+    }
+
+    #[test_case([tricks(3), tricks(4), None, None], Position::West => Some((Position::East, 4)) ; "higher bid wins")]
+    #[test_case([tricks(4), tricks(4), None, None], Position::West => Some((Position::North, 4)) ; "equal bid is a pass")]
+    #[test_case([tricks(2), None, None, None], Position::West => None ; "bid below minimum is a pass")]
+    #[test_case([tricks(7), None, None, None], Position::West => None ; "bid above six is a pass")]
+    #[test_case([tricks(6), None, Some(AuctionBid::ShootTheMoon { strain: Strain::Low }), None], Position::West => Some((Position::South, 6)) ; "shooting beats six")]
+    #[test_case([None, tricks(5), tricks(3), None], Position::North => Some((Position::East, 5)) ; "lower bid after higher is a pass")]
+    fn auction(bids: [Option<AuctionBid>; 4], dealer: Position) -> Option<(Position, u8)> {
+        match run_auction(bids, dealer) {
+            AuctionResult::Won { contract } => Some((contract.bidder, contract.bid.tricks())),
+            AuctionResult::NoOneBid => None,
+        }
+    }
+
+    fn played(player: Position, suit: Suit, rank: RankWithBowers) -> PlayedCard {
+        PlayedCard {
+            player,
+            card: Card { suit, rank },
+        }
+    }
+
+    #[test_case(Strain::High => Position::South)]
+    #[test_case(Strain::Low => Position::West)]
+    #[test_case(Strain::Trump(Suit::Clubs) => Position::East)]
+    #[test_case(Strain::Trump(Suit::Hearts) => Position::South)]
+    fn get_winning_position(strain: Strain) -> Position {
+        strain.get_winning_position(&[
+            played(Position::North, Suit::Hearts, RankWithBowers::King),
+            played(Position::East, Suit::Clubs, RankWithBowers::Nine),
+            played(Position::South, Suit::Hearts, RankWithBowers::Ace),
+            played(Position::West, Suit::Hearts, RankWithBowers::Ten),
+        ])
+    }
+
+    #[test_case(Position::North, false => Position::East)]
+    #[test_case(Position::North, true => Position::East)]
+    #[test_case(Position::West, false => Position::North)]
+    #[test_case(Position::West, true => Position::East)]
+    fn next_position_playing(position: Position, shoot: bool) -> Position {
+        let strain = Strain::High;
+        Contract {
+            bidder: Position::South,
+            bid: if shoot {
+                AuctionBid::ShootTheMoon { strain }
+            } else {
+                AuctionBid::Tricks { tricks: 3, strain }
+            },
+        }
+        .next_position_playing(&position)
+    }
+}
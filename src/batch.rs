@@ -0,0 +1,203 @@
+// A batch file is a JSON list of scenarios for simulate-batch, such as
+// `[{"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H",
+// "dealer": "S", "bid": "order_up", "alone": false}]`. A `call` bid also needs
+// a `trump` suit. `ignore_other_bids`, `pinned`, `excluded` and `passed` are
+// optional and take the same values as the simulate-hand options.
+
+use std::{fs, io, path::Path, str::FromStr};
+
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use serde::Deserialize;
+
+use crate::{
+    hand::HandBeforeBidding,
+    hands_iterator::Constraints,
+    players::registry::Strategy,
+    position::Position,
+    protocol,
+    rules::Rules,
+    simulation::{self, Bid, Scenario, SimulationResults},
+};
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct BatchScenario {
+    hand: Vec<String>,
+    trump_candidate: String,
+    dealer: String,
+    bid: String,
+    trump: Option<String>,
+    #[serde(default)]
+    alone: bool,
+    #[serde(default)]
+    ignore_other_bids: bool,
+    #[serde(default)]
+    pinned: Vec<String>,
+    #[serde(default)]
+    excluded: Vec<String>,
+    #[serde(default)]
+    passed: Vec<String>,
+}
+
+impl BatchScenario {
+    pub(crate) fn load(path: &Path) -> io::Result<Vec<BatchScenario>> {
+        serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub(crate) fn scenario(
+        &self,
+        rules: Rules,
+        strategies: [Strategy; 4],
+    ) -> Result<Scenario, String> {
+        let bid = match (self.bid.as_str(), &self.trump) {
+            ("order_up", _) => Bid::OrderUp,
+            ("call", Some(trump)) => Bid::Call(protocol::parse_suit(trump)?),
+            ("call", None) => return Err("A call needs a trump suit".to_string()),
+            (bid, _) => return Err(format!("Invalid bid: {}", bid)),
+        };
+        let scenario = Scenario {
+            rules,
+            dealer: Position::from_str(&self.dealer)?,
+            trump_candidate: protocol::parse_card(&self.trump_candidate)?,
+            hand: HandBeforeBidding {
+                cards: self
+                    .hand
+                    .iter()
+                    .map(|name| protocol::parse_card(name))
+                    .collect::<Result<_, String>>()?,
+            },
+            bid,
+            go_alone: self.alone,
+            ignore_other_bids: self.ignore_other_bids,
+            strategies,
+            constraints: Constraints {
+                pinned: parse_all(&self.pinned)?,
+                excluded: parse_all(&self.excluded)?,
+            },
+            passes: parse_all(&self.passed)?,
+            discard: None,
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+}
+
+pub(crate) fn load_scenarios(
+    path: &Path,
+    rules: Rules,
+    strategies: [Strategy; 4],
+) -> Result<Vec<Scenario>, String> {
+    BatchScenario::load(path)
+        .map_err(|error| format!("Couldn't read {}: {}", path.display(), error))?
+        .iter()
+        .enumerate()
+        .map(|(index, scenario)| {
+            scenario
+                .scenario(rules, strategies.clone())
+                .map_err(|error| format!("Scenario {}: {}", index + 1, error))
+        })
+        .collect()
+}
+
+pub(crate) fn simulate_all(
+    scenarios: &[Scenario],
+    samples: Option<u64>,
+    seed: u64,
+) -> Vec<SimulationResults> {
+    scenarios
+        .par_iter()
+        .map(|scenario| match samples {
+            Some(samples) => simulation::simulate_sampled(scenario, samples, seed),
+            None => simulation::simulate(scenario),
+        })
+        .collect()
+}
+
+fn parse_all<T: FromStr<Err = String>>(names: &[String]) -> Result<Vec<T>, String> {
+    names.iter().map(|name| T::from_str(name)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hands_iterator::CardConstraint, simulation::Pass, suit::Suit};
+    use test_case::test_case;
+
+    fn strategies() -> [Strategy; 4] {
+        [
+            Strategy::Advanced,
+            Strategy::Advanced,
+            Strategy::Advanced,
+            Strategy::Advanced,
+        ]
+    }
+
+    fn batch(json: &str) -> Vec<BatchScenario> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn parses_scenarios() {
+        let scenarios = batch(
+            r#"[
+                {"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H", "dealer": "S", "bid": "order_up"},
+                {"hand": ["JC", "JS", "AC", "KC", "AS"], "trump_candidate": "9H", "dealer": "W", "bid": "call", "trump": "C", "alone": true, "pinned": ["QC:N"], "passed": ["N:1"]}
+            ]"#,
+        );
+        let first = scenarios[0]
+            .scenario(Rules::default(), strategies())
+            .unwrap();
+        assert_eq!(Bid::OrderUp, first.bid);
+        assert!(!first.go_alone);
+        let second = scenarios[1]
+            .scenario(Rules::default(), strategies())
+            .unwrap();
+        assert_eq!(Bid::Call(Suit::Clubs), second.bid);
+        assert!(second.go_alone);
+        assert_eq!(
+            vec![CardConstraint::from_str("QC:N").unwrap()],
+            second.constraints.pinned
+        );
+        assert_eq!(vec![Pass::from_str("N:1").unwrap()], second.passes);
+    }
+
+    #[test_case(r#"{"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H", "dealer": "S", "bid": "call"}"# => Err("A call needs a trump suit".to_string()))]
+    #[test_case(r#"{"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H", "dealer": "S", "bid": "pass"}"# => Err("Invalid bid: pass".to_string()))]
+    #[test_case(r#"{"hand": ["JH", "JD", "AH", "KH"], "trump_candidate": "9H", "dealer": "S", "bid": "order_up"}"# => Err("The hand must have 5 cards, not 4".to_string()))]
+    fn rejects_invalid_scenarios(json: &str) -> Result<Bid, String> {
+        serde_json::from_str::<BatchScenario>(json)
+            .unwrap()
+            .scenario(Rules::default(), strategies())
+            .map(|scenario| scenario.bid)
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let error = serde_json::from_str::<BatchScenario>(
+            r#"{"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H", "dealer": "S", "bid": "order_up", "go_alone": true}"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().starts_with("unknown field `go_alone`"));
+    }
+
+    #[test]
+    fn simulates_every_scenario() {
+        let scenarios: Vec<Scenario> = batch(
+            r#"[
+                {"hand": ["JH", "JD", "AH", "KH", "AS"], "trump_candidate": "9H", "dealer": "S", "bid": "order_up"},
+                {"hand": ["10C", "9S", "10S", "QD", "KD"], "trump_candidate": "9H", "dealer": "S", "bid": "order_up", "alone": true}
+            ]"#,
+        )
+        .iter()
+        .map(|scenario| scenario.scenario(Rules::default(), strategies()).unwrap())
+        .collect();
+        let results = simulate_all(&scenarios, Some(100), 4);
+        assert_eq!(2, results.len());
+        assert_eq!(
+            results[0],
+            simulation::simulate_sampled(&scenarios[0], 100, 4)
+        );
+        assert!(results[0].expected_value().unwrap() > results[1].expected_value().unwrap());
+    }
+}
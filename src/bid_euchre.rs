@@ -0,0 +1,270 @@
+use crate::{
+    auction_state::{AuctionBid, AuctionResult, AuctionState, Contract},
+    deck::Deck,
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    position::Position,
+    rules::Rules,
+    trick_state::{PlayedCard, TrickState},
+};
+
+pub(crate) const WINNING_SCORE: i16 = 32;
+pub(crate) const SHOOT_THE_MOON_SCORE: i16 = 12;
+
+pub(crate) struct BidEuchreGameState<T: Player> {
+    pub(crate) players: [T; 4],
+    pub(crate) phase: BidEuchreGamePhase,
+    pub(crate) north_south_score: i16,
+    pub(crate) east_west_score: i16,
+}
+
+pub(crate) enum BidEuchreGamePhase {
+    Playing { hand_state: BidEuchreHandState },
+    Done,
+}
+
+#[derive(Debug)]
+pub(crate) struct BidEuchreHandState {
+    pub(crate) dealer: Position,
+    pub(crate) phase: BidEuchreHandPhase,
+}
+
+#[derive(Debug)]
+pub(crate) enum BidEuchreHandPhase {
+    Auction {
+        auction_state: AuctionState,
+        hands: [HandBeforeBidding; 4],
+    },
+    Playing {
+        contract: Contract,
+        hands: [Hand; 4],
+        leader: Position,
+        tricks_taken: [u8; 4],
+    },
+    Scoring {
+        contract: Option<Contract>,
+        tricks_taken: [u8; 4],
+    },
+}
+
+impl<T: Player> BidEuchreGameState<T> {
+    pub(crate) fn create(players: [T; 4]) -> BidEuchreGameState<T> {
+        BidEuchreGameState {
+            players,
+            phase: BidEuchreGamePhase::Playing {
+                hand_state: BidEuchreHandState::deal(Position::South),
+            },
+            north_south_score: 0,
+            east_west_score: 0,
+        }
+    }
+
+    pub(crate) fn step(&mut self) -> Option<String> {
+        match &mut self.phase {
+            BidEuchreGamePhase::Playing { ref mut hand_state } => {
+                if let Some((north_south_score, east_west_score)) =
+                    hand_state.step(&mut self.players)
+                {
+                    self.finish_hand(north_south_score, east_west_score)
+                }
+                None
+            }
+            BidEuchreGamePhase::Done => Some(if self.east_west_score > self.north_south_score {
+                format!(
+                    "East/West wins! Final score: {0}-{1}",
+                    self.east_west_score, self.north_south_score,
+                )
+            } else {
+                format!(
+                    "North/South wins! Final score: {0}-{1}",
+                    self.north_south_score, self.east_west_score,
+                )
+            }),
+        }
+    }
+
+    fn finish_hand(&mut self, north_south_score: i16, east_west_score: i16) {
+        self.north_south_score += north_south_score;
+        self.east_west_score += east_west_score;
+        log::info!(
+            "North/South scored {} and East/West scored {}: {}-{}",
+            north_south_score,
+            east_west_score,
+            self.north_south_score,
+            self.east_west_score
+        );
+        if (self.north_south_score >= WINNING_SCORE || self.east_west_score >= WINNING_SCORE)
+            && self.north_south_score != self.east_west_score
+        {
+            self.phase = BidEuchreGamePhase::Done;
+        }
+        match &self.phase {
+            BidEuchreGamePhase::Playing { hand_state } => {
+                self.phase = BidEuchreGamePhase::Playing {
+                    hand_state: BidEuchreHandState::deal(hand_state.dealer.next_position_bidding()),
+                }
+            }
+            BidEuchreGamePhase::Done => (),
+        }
+    }
+}
+
+impl BidEuchreHandState {
+    pub(crate) fn create(dealer: Position, hands: [HandBeforeBidding; 4]) -> BidEuchreHandState {
+        BidEuchreHandState {
+            dealer,
+            phase: BidEuchreHandPhase::Auction {
+                auction_state: AuctionState::create(dealer),
+                hands,
+            },
+        }
+    }
+
+    fn deal(dealer: Position) -> BidEuchreHandState {
+        BidEuchreHandState::create(
+            dealer,
+            Deck::create_shuffled_deck(&Rules::default()).deal_bid_euchre(),
+        )
+    }
+
+    pub(crate) fn step(&mut self, players: &mut [impl Player; 4]) -> Option<(i16, i16)> {
+        match &mut self.phase {
+            BidEuchreHandPhase::Auction {
+                auction_state,
+                hands,
+            } => {
+                match auction_state.step(players, hands) {
+                    Some(AuctionResult::Won { contract }) => {
+                        let strain = contract.bid.strain();
+                        self.phase = BidEuchreHandPhase::Playing {
+                            leader: contract.bidder,
+                            hands: hands.clone().map(|hand| strain.update_bowers(hand)),
+                            contract,
+                            tricks_taken: [0; 4],
+                        }
+                    }
+                    Some(AuctionResult::NoOneBid) => {
+                        log::info!("{}", "No one bid");
+                        self.phase = BidEuchreHandPhase::Scoring {
+                            contract: None,
+                            tricks_taken: [0; 4],
+                        }
+                    }
+                    None => (),
+                }
+                None
+            }
+            BidEuchreHandPhase::Playing {
+                contract,
+                hands,
+                leader,
+                tricks_taken,
+            } => {
+                let mut cards_played: Vec<PlayedCard> = Vec::with_capacity(4);
+                let mut player = *leader;
+                loop {
+                    let hand = &mut hands[player.index()];
+                    let card = players[player.index()].play_card_in_contract(
+                        hand,
+                        contract,
+                        &cards_played,
+                    );
+                    let card = TrickState::legal_card(hand, card, &cards_played);
+                    hand.cards.retain(|c| c != &card);
+                    cards_played.push(PlayedCard { player, card });
+                    player = contract.next_position_playing(&player);
+                    if player == *leader {
+                        break;
+                    }
+                }
+                if let Some(bid_result) = contract.bid_result() {
+                    for player in players.iter_mut() {
+                        player.trick_end(&bid_result, &cards_played);
+                    }
+                }
+                let trick_winner = contract.bid.strain().get_winning_position(&cards_played);
+                tricks_taken[trick_winner.index()] += 1;
+                if hands[trick_winner.index()].cards.is_empty() {
+                    self.phase = BidEuchreHandPhase::Scoring {
+                        contract: Some(contract.clone()),
+                        tricks_taken: *tricks_taken,
+                    };
+                } else {
+                    *leader = trick_winner;
+                }
+                None
+            }
+            BidEuchreHandPhase::Scoring {
+                contract: Some(contract),
+                tricks_taken,
+            } => Some(BidEuchreHandState::get_score(contract, tricks_taken)),
+            BidEuchreHandPhase::Scoring { contract: None, .. } => Some((0, 0)),
+        }
+    }
+
+    fn get_score(contract: &Contract, tricks_taken: &[u8; 4]) -> (i16, i16) {
+        let bidder = contract.bidder;
+        let bidder_tricks =
+            (tricks_taken[bidder.index()] + tricks_taken[bidder.partner().index()]) as i16;
+        let defender = bidder.next_position_bidding();
+        let defender_tricks =
+            (tricks_taken[defender.index()] + tricks_taken[defender.partner().index()]) as i16;
+        let bidder_score = match contract.bid {
+            AuctionBid::ShootTheMoon { .. } if bidder_tricks == 6 => SHOOT_THE_MOON_SCORE,
+            AuctionBid::ShootTheMoon { .. } => -SHOOT_THE_MOON_SCORE,
+            AuctionBid::Tricks { tricks, .. } if bidder_tricks >= tricks as i16 => bidder_tricks,
+            AuctionBid::Tricks { tricks, .. } => -(tricks as i16),
+        };
+        match bidder {
+            Position::North | Position::South => (bidder_score, defender_tricks),
+            Position::East | Position::West => (defender_tricks, bidder_score),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{auction_state::Strain, players::advanced::AdvancedPlayer, suit::Suit};
+    use test_case::test_case;
+
+    #[test_case(Position::South, AuctionBid::Tricks { tricks: 3, strain: Strain::High }, [1, 1, 2, 2] => (3, 3) ; "made exactly")]
+    #[test_case(Position::South, AuctionBid::Tricks { tricks: 3, strain: Strain::High }, [3, 0, 2, 1] => (5, 1) ; "made with extra")]
+    #[test_case(Position::South, AuctionBid::Tricks { tricks: 4, strain: Strain::Low }, [1, 2, 2, 1] => (-4, 3) ; "set")]
+    #[test_case(Position::East, AuctionBid::Tricks { tricks: 4, strain: Strain::Trump(Suit::Hearts) }, [0, 4, 1, 1] => (1, 5) ; "east west made")]
+    #[test_case(Position::South, AuctionBid::ShootTheMoon { strain: Strain::Trump(Suit::Hearts) }, [0, 0, 6, 0] => (12, 0) ; "shot the moon")]
+    #[test_case(Position::South, AuctionBid::ShootTheMoon { strain: Strain::High }, [0, 1, 5, 0] => (-12, 1) ; "failed to shoot the moon")]
+    fn get_score(bidder: Position, bid: AuctionBid, tricks_taken: [u8; 4]) -> (i16, i16) {
+        BidEuchreHandState::get_score(&Contract { bidder, bid }, &tricks_taken)
+    }
+
+    #[test]
+    fn deal_bid_euchre() {
+        let mut deck = Deck::create_shuffled_deck(&Rules::default());
+        let hands = deck.deal_bid_euchre();
+        for hand in hands {
+            assert_eq!(6, hand.cards.len());
+        }
+        assert!(deck.cards.is_empty());
+    }
+
+    #[test]
+    fn plays_full_game() {
+        let mut game_state = BidEuchreGameState::create([
+            AdvancedPlayer::create(Position::North),
+            AdvancedPlayer::create(Position::East),
+            AdvancedPlayer::create(Position::South),
+            AdvancedPlayer::create(Position::West),
+        ]);
+        let result = loop {
+            if let Some(result) = game_state.step() {
+                break result;
+            }
+        };
+        assert!(
+            game_state.north_south_score >= WINNING_SCORE
+                || game_state.east_west_score >= WINNING_SCORE
+        );
+        assert!(result.contains("wins!"));
+    }
+}
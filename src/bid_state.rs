@@ -1,6 +1,6 @@
 use crate::{
     bid_result::BidResultAll, card::CardBeforeBidding, hand::HandBeforeBidding, player::Player,
-    position::Position,
+    position::Position, rank::Rank,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -9,8 +9,11 @@ pub(crate) struct BidState {
     pub(crate) phase: BidPhase,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum BidPhase {
+    DealerNamesJokerSuit {
+        trump_candidate: CardBeforeBidding,
+    },
     FirstRoundFirstPlayer {
         trump_candidate: CardBeforeBidding,
     },
@@ -57,7 +60,11 @@ impl BidState {
     pub(crate) fn create(dealer: Position, trump_candidate: CardBeforeBidding) -> BidState {
         BidState {
             dealer,
-            phase: BidPhase::FirstRoundFirstPlayer { trump_candidate },
+            phase: if trump_candidate.rank == Rank::Joker {
+                BidPhase::DealerNamesJokerSuit { trump_candidate }
+            } else {
+                BidPhase::FirstRoundFirstPlayer { trump_candidate }
+            },
         }
     }
 
@@ -67,6 +74,18 @@ impl BidState {
         hands: &mut [HandBeforeBidding; 4],
     ) -> Option<BidResultAll> {
         match &mut self.phase {
+            BidPhase::DealerNamesJokerSuit { trump_candidate } => {
+                let dealer_index = self.dealer.index();
+                let suit =
+                    players[dealer_index].choose_joker_suit(&hands[dealer_index], &self.dealer);
+                self.phase = BidPhase::FirstRoundFirstPlayer {
+                    trump_candidate: CardBeforeBidding {
+                        suit,
+                        rank: trump_candidate.rank,
+                    },
+                };
+                None
+            }
             BidPhase::FirstRoundFirstPlayer {
                 ref trump_candidate,
             } => {
@@ -202,12 +221,9 @@ impl BidState {
                 self.phase = match BidState::call(&self.dealer, bidder, players, hands, turned_down)
                 {
                     Some(bid_result) => BidPhase::Done { bid_result },
-                    _ => {
-                        log::info!("{}", "No one called a trump suit");
-                        BidPhase::Done {
-                            bid_result: BidResultAll::NoOneCalled,
-                        }
-                    }
+                    _ => BidPhase::Done {
+                        bid_result: BidResultAll::NoOneCalled,
+                    },
                 };
                 None
             }
@@ -226,7 +242,6 @@ impl BidState {
         if !players[bidder_index].should_order_up(&hands[bidder_index], dealer, trump_candidate) {
             return None;
         }
-        log::info!("{:?} ordered up {}", bidder, trump_candidate);
         if !players[bidder_index].should_order_up_alone(
             &hands[bidder_index],
             dealer,
@@ -271,7 +286,7 @@ impl BidState {
         }
     }
 
-    fn discard(
+    pub(crate) fn discard(
         dealer: &mut impl Player,
         hand: &mut HandBeforeBidding,
         card_ordered: CardBeforeBidding,
@@ -293,7 +308,6 @@ impl BidState {
     ) -> Option<BidResultAll> {
         match players[bidder.index()].call_trump(&hands[bidder.index()], dealer, turned_down) {
             Some(trump) if trump != turned_down.suit => {
-                log::info!("{:?} called {}", bidder, trump);
                 if !players[bidder.index()].should_call_alone(
                     &hands[bidder.index()],
                     dealer,
@@ -343,6 +357,54 @@ impl BidState {
     }
 }
 
+impl BidPhase {
+    pub(crate) fn announcement(&self, next: &BidPhase, dealer: &Position) -> Option<String> {
+        match (self, next) {
+            (
+                BidPhase::DealerNamesJokerSuit { .. },
+                BidPhase::FirstRoundFirstPlayer { trump_candidate },
+            ) => Some(format!(
+                "{:?} named {} for the joker",
+                dealer, trump_candidate.suit
+            )),
+            (
+                BidPhase::FirstRoundFirstPlayer { .. }
+                | BidPhase::FirstRoundSecondPlayer { .. }
+                | BidPhase::FirstRoundThirdPlayer { .. }
+                | BidPhase::FirstRoundFourthPlayer { .. },
+                BidPhase::OrderedUp {
+                    caller,
+                    card_ordered,
+                }
+                | BidPhase::OrderedUpAlone {
+                    caller,
+                    card_ordered,
+                }
+                | BidPhase::OrderedUpDefendedAlone {
+                    caller,
+                    card_ordered,
+                    ..
+                },
+            ) => Some(format!("{:?} ordered up {}", caller, card_ordered)),
+            (
+                BidPhase::SecondRoundFirstPlayer { .. }
+                | BidPhase::SecondRoundSecondPlayer { .. }
+                | BidPhase::SecondRoundThirdPlayer { .. }
+                | BidPhase::SecondRoundFourthPlayer { .. },
+                BidPhase::Done { bid_result },
+            ) => Some(match bid_result {
+                BidResultAll::Called { trump, caller }
+                | BidResultAll::CalledAlone { trump, caller }
+                | BidResultAll::DefendedAlone { trump, caller, .. } => {
+                    format!("{:?} called {}", caller, trump)
+                }
+                BidResultAll::NoOneCalled => "No one called a trump suit".to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,6 +443,56 @@ mod tests {
         )
     }
 
+    #[test]
+    fn announces_bids() {
+        let dealer = Position::North;
+        let trump_candidate = CardBeforeBidding {
+            suit: Suit::Hearts,
+            rank: Rank::Ace,
+        };
+        let turned_down = trump_candidate;
+        let first_round = BidPhase::FirstRoundSecondPlayer { trump_candidate };
+        let second_round = BidPhase::SecondRoundFourthPlayer { turned_down };
+        assert_eq!(
+            first_round.announcement(
+                &BidPhase::OrderedUp {
+                    caller: Position::North,
+                    card_ordered: trump_candidate,
+                },
+                &dealer
+            ),
+            Some(format!("North ordered up {}", trump_candidate))
+        );
+        assert_eq!(
+            first_round.announcement(
+                &BidPhase::FirstRoundThirdPlayer { trump_candidate },
+                &dealer
+            ),
+            None
+        );
+        assert_eq!(
+            second_round.announcement(
+                &BidPhase::Done {
+                    bid_result: BidResultAll::Called {
+                        trump: Suit::Spades,
+                        caller: Position::North,
+                    },
+                },
+                &dealer
+            ),
+            Some(format!("North called {}", Suit::Spades))
+        );
+        assert_eq!(
+            second_round.announcement(
+                &BidPhase::Done {
+                    bid_result: BidResultAll::NoOneCalled,
+                },
+                &dealer
+            ),
+            Some("No one called a trump suit".to_string())
+        );
+    }
+
     #[test]
     fn ordered_up_only_allows_valid_discards() {
         let dealer = Position::North;
@@ -454,6 +566,48 @@ mod tests {
         )
     }
 
+    #[test]
+    fn joker_turned_up_dealer_names_suit() {
+        let dealer = Position::North;
+        let trump_candidate = CardBeforeBidding::JOKER;
+        let trump = Suit::Spades;
+        let card_ordered = CardBeforeBidding {
+            suit: trump,
+            rank: Rank::Joker,
+        };
+        let mut players = make_players();
+        let caller = Position::South;
+        players[caller.index()] = PreprogrammedBidder::orders_up();
+        let mut hands = make_hands();
+        let expected_return_value = BidResultAll::Called { trump, caller };
+        let bid_result = expected_return_value.clone();
+        let expected_results = [
+            BidPhase::DealerNamesJokerSuit { trump_candidate },
+            BidPhase::FirstRoundFirstPlayer {
+                trump_candidate: card_ordered,
+            },
+            BidPhase::FirstRoundSecondPlayer {
+                trump_candidate: card_ordered,
+            },
+            BidPhase::OrderedUp {
+                caller,
+                card_ordered,
+            },
+            BidPhase::Done { bid_result },
+        ];
+        check_sequence(
+            dealer,
+            trump_candidate,
+            &mut players,
+            &mut hands,
+            &expected_results,
+            expected_return_value,
+        );
+        assert!(hands[dealer.index()]
+            .cards
+            .contains(&CardBeforeBidding::JOKER));
+    }
+
     #[test]
     fn ordered_up_alone() {
         let dealer = Position::North;
@@ -0,0 +1,620 @@
+// A bid table holds simulated expected values for every bid South could make
+// with every canonical hand. Suits are named relative to the up card: its own
+// suit, the other suit of the same color and the two cross suits. The cross
+// suits are interchangeable, so a hand and its copy with them swapped share an
+// entry. Entries are also keyed by the up card's rank and by South's seat,
+// counted from the dealer's left. The values assume South gets to make the bid,
+// as simulate-hand does with --ignore-other-bids.
+//
+// The file starts with the magic bytes "EUCHRBT", a format version byte, the
+// joker and deck size rules, then the sample count and entry count as
+// little-endian u32s. The entries follow, sorted by key. Each entry is the key
+// as a little-endian u64 followed by eight little-endian i16 expected values in
+// hundredths of a point. The values are ordering up, ordering up alone, then
+// calling and calling alone in the next suit and in each cross suit.
+
+use std::{collections::BTreeSet, fs, io, path::Path, str::FromStr, sync::Arc};
+
+use enum_iterator::IntoEnumIterator;
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{
+    card::CardBeforeBidding,
+    deck::Deck,
+    hand::HandBeforeBidding,
+    hands_iterator::Constraints,
+    players::registry::Strategy,
+    position::Position,
+    protocol::{card_name, suit_name},
+    rank::Rank,
+    rules::{DeckSize, Rules},
+    shared_files::SharedFiles,
+    simulation::{self, Bid, Scenario},
+    suit::Suit,
+};
+
+const MAGIC: &[u8; 7] = b"EUCHRBT";
+const FORMAT_VERSION: u8 = 1;
+const HEADER_SIZE: usize = 18;
+const ENTRY_SIZE: usize = 24;
+const BID_COUNT: usize = 8;
+const SEAT_NAMES: [&str; 4] = [
+    "left_of_dealer",
+    "across_from_dealer",
+    "right_of_dealer",
+    "dealer",
+];
+
+// The up card's suit, the next suit and the two cross suits, in that order.
+type Roles = [Suit; 4];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct TableKey(u64);
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BidTable {
+    rules: Rules,
+    samples: u32,
+    entries: Vec<(TableKey, [i16; BID_COUNT])>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct BidValues {
+    pub(crate) order_up: f64,
+    pub(crate) order_up_alone: f64,
+    pub(crate) calls: [CallValues; 3],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CallValues {
+    pub(crate) trump: Suit,
+    pub(crate) call: f64,
+    pub(crate) call_alone: f64,
+}
+
+impl TableKey {
+    pub(crate) fn create(
+        hand: &HandBeforeBidding,
+        trump_candidate: &CardBeforeBidding,
+        dealer: Position,
+        position: Position,
+    ) -> Option<(TableKey, Roles)> {
+        if trump_candidate.rank == Rank::Joker || hand.cards.len() != 5 {
+            return None;
+        }
+        role_orders(trump_candidate.suit)
+            .into_iter()
+            .map(|roles| {
+                let mut codes: Vec<u64> = hand
+                    .cards
+                    .iter()
+                    .map(|card| card_code(card, &roles))
+                    .collect();
+                codes.sort();
+                let cards = codes.into_iter().fold(0, |key, code| key << 6 | code);
+                let key =
+                    seat(dealer, position) << 34 | (trump_candidate.rank as u64) << 30 | cards;
+                (TableKey(key), roles)
+            })
+            .min_by_key(|(key, _)| *key)
+    }
+
+    fn seat(&self) -> usize {
+        (self.0 >> 34) as usize
+    }
+
+    fn trump_candidate(&self) -> CardBeforeBidding {
+        CardBeforeBidding {
+            suit: representative_roles()[0],
+            rank: rank_from_value(self.0 >> 30 & 0xF),
+        }
+    }
+
+    fn hand(&self) -> HandBeforeBidding {
+        let roles = representative_roles();
+        HandBeforeBidding {
+            cards: (0..5)
+                .rev()
+                .map(|index| {
+                    let code = self.0 >> (index * 6) & 0x3F;
+                    match rank_from_value(code & 0xF) {
+                        Rank::Joker => CardBeforeBidding::JOKER,
+                        rank => CardBeforeBidding {
+                            suit: roles[(code >> 4) as usize],
+                            rank,
+                        },
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn dealer(&self) -> Position {
+        [
+            Position::North,
+            Position::East,
+            Position::South,
+            Position::West,
+        ]
+        .into_iter()
+        .find(|&dealer| seat(dealer, Position::South) as usize == self.seat())
+        .unwrap()
+    }
+
+    fn scenarios(&self, rules: Rules, strategies: &[Strategy; 4]) -> [Scenario; BID_COUNT] {
+        let roles = representative_roles();
+        let bids = [
+            Bid::OrderUp,
+            Bid::Call(roles[1]),
+            Bid::Call(roles[2]),
+            Bid::Call(roles[3]),
+        ];
+        [0, 1, 2, 3, 4, 5, 6, 7].map(|index| Scenario {
+            rules,
+            dealer: self.dealer(),
+            trump_candidate: self.trump_candidate(),
+            hand: self.hand(),
+            bid: bids[index / 2],
+            go_alone: index % 2 == 1,
+            ignore_other_bids: true,
+            strategies: strategies.clone(),
+            constraints: Constraints::default(),
+            passes: Vec::new(),
+            discard: None,
+        })
+    }
+}
+
+impl BidTable {
+    pub(crate) fn create(
+        rules: Rules,
+        samples: u32,
+        mut entries: Vec<(TableKey, [i16; BID_COUNT])>,
+    ) -> BidTable {
+        entries.sort_by_key(|(key, _)| *key);
+        BidTable {
+            rules,
+            samples,
+            entries,
+        }
+    }
+
+    pub(crate) fn generate(
+        rules: Rules,
+        strategies: &[Strategy; 4],
+        keys: &[TableKey],
+        samples: u32,
+        seed: u64,
+    ) -> BidTable {
+        let entries = keys
+            .par_iter()
+            .map(|key| {
+                let values = key.scenarios(rules, strategies).map(|scenario| {
+                    let results = simulation::simulate_sampled(&scenario, samples as u64, seed);
+                    results
+                        .expected_value()
+                        .map_or(0, |expected_value| (expected_value * 100.0).round() as i16)
+                });
+                (*key, values)
+            })
+            .collect();
+        BidTable::create(rules, samples, entries)
+    }
+
+    pub(crate) fn load(path: &Path) -> io::Result<BidTable> {
+        BidTable::from_bytes(&fs::read(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub(crate) fn load_shared(path: &str) -> io::Result<Arc<BidTable>> {
+        static TABLES: SharedFiles<Arc<BidTable>> = SharedFiles::new();
+        TABLES.load(path, |path| BidTable::load(path).map(Arc::new))
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub(crate) fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    pub(crate) fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn lookup(
+        &self,
+        hand: &HandBeforeBidding,
+        trump_candidate: &CardBeforeBidding,
+        dealer: Position,
+        position: Position,
+    ) -> Option<BidValues> {
+        let (key, roles) = TableKey::create(hand, trump_candidate, dealer, position)?;
+        let index = self
+            .entries
+            .binary_search_by_key(&key, |(key, _)| *key)
+            .ok()?;
+        let values = self.entries[index].1.map(|value| value as f64 / 100.0);
+        Some(BidValues {
+            order_up: values[0],
+            order_up_alone: values[1],
+            calls: [1, 2, 3].map(|role| CallValues {
+                trump: roles[role],
+                call: values[role * 2],
+                call_alone: values[role * 2 + 1],
+            }),
+        })
+    }
+
+    pub(crate) fn chart(&self) -> Vec<String> {
+        let roles = representative_roles();
+        self.entries
+            .iter()
+            .map(|(key, values)| {
+                let value = |index: usize| format!("{:.2}", values[index] as f64 / 100.0);
+                let mut fields = vec![
+                    format!("seat={}", SEAT_NAMES[key.seat()]),
+                    format!("trump_candidate={}", card_name(&key.trump_candidate())),
+                    format!(
+                        "hand={}",
+                        key.hand()
+                            .cards
+                            .iter()
+                            .map(card_name)
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    ),
+                    format!("order_up={}", value(0)),
+                    format!("order_up_alone={}", value(1)),
+                ];
+                for (role, trump) in roles.iter().enumerate().skip(1) {
+                    let suit = suit_name(trump);
+                    fields.push(format!("call_{}={}", suit, value(role * 2)));
+                    fields.push(format!("call_{}_alone={}", suit, value(role * 2 + 1)));
+                }
+                fields.join(" ")
+            })
+            .collect()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.entries.len() * ENTRY_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.push(self.rules.joker as u8);
+        bytes.push(self.rules.deck_size.card_count());
+        bytes.extend_from_slice(&self.samples.to_le_bytes());
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for (key, values) in &self.entries {
+            bytes.extend_from_slice(&key.0.to_le_bytes());
+            for value in values {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<BidTable, String> {
+        if bytes.len() < HEADER_SIZE || &bytes[..7] != MAGIC {
+            return Err("This isn't a bid table".to_string());
+        }
+        if bytes[7] != FORMAT_VERSION {
+            return Err(format!("Unsupported bid table version: {}", bytes[7]));
+        }
+        let rules = Rules {
+            joker: bytes[8] != 0,
+            deck_size: DeckSize::from_str(&bytes[9].to_string())?,
+        };
+        let samples = u32::from_le_bytes(bytes[10..14].try_into().unwrap());
+        let count = u32::from_le_bytes(bytes[14..18].try_into().unwrap()) as usize;
+        if bytes.len() != HEADER_SIZE + count * ENTRY_SIZE {
+            return Err(format!(
+                "The bid table should have {} entries but is {} bytes long",
+                count,
+                bytes.len()
+            ));
+        }
+        let entries = bytes[HEADER_SIZE..]
+            .chunks_exact(ENTRY_SIZE)
+            .map(|entry| {
+                let key = TableKey(u64::from_le_bytes(entry[..8].try_into().unwrap()));
+                let mut values = [0; BID_COUNT];
+                for (index, value) in values.iter_mut().enumerate() {
+                    let start = 8 + index * 2;
+                    *value = i16::from_le_bytes(entry[start..start + 2].try_into().unwrap());
+                }
+                (key, values)
+            })
+            .collect();
+        Ok(BidTable::create(rules, samples, entries))
+    }
+}
+
+pub(crate) fn all_keys(rules: &Rules) -> Vec<TableKey> {
+    let trump_suit = representative_roles()[0];
+    let all_cards = Deck::create_all_cards(rules);
+    let mut keys = BTreeSet::new();
+    for trump_candidate in all_cards
+        .iter()
+        .filter(|card| card.suit == trump_suit && card.rank != Rank::Joker)
+    {
+        let others: Vec<CardBeforeBidding> = all_cards
+            .iter()
+            .filter(|card| *card != trump_candidate)
+            .copied()
+            .collect();
+        for cards in combinations(&others, 5) {
+            let hand = HandBeforeBidding { cards };
+            for dealer in [
+                Position::North,
+                Position::East,
+                Position::South,
+                Position::West,
+            ] {
+                let (key, _) =
+                    TableKey::create(&hand, trump_candidate, dealer, Position::South).unwrap();
+                let _inserted = keys.insert(key);
+            }
+        }
+    }
+    keys.into_iter().collect()
+}
+
+fn combinations(cards: &[CardBeforeBidding], count: usize) -> Vec<Vec<CardBeforeBidding>> {
+    if count == 0 {
+        return vec![Vec::new()];
+    }
+    (0..cards.len())
+        .flat_map(|index| {
+            combinations(&cards[index + 1..], count - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, cards[index]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+fn seat(dealer: Position, position: Position) -> u64 {
+    let mut seat = 0;
+    let mut bidder = dealer.next_position_bidding();
+    while bidder != position {
+        seat += 1;
+        bidder = bidder.next_position_bidding();
+    }
+    seat
+}
+
+fn role_orders(trump_candidate: Suit) -> [Roles; 2] {
+    let next = trump_candidate.other_suit_of_same_color();
+    let cross: Vec<Suit> = Suit::into_enum_iter()
+        .filter(|&suit| suit != trump_candidate && suit != next)
+        .collect();
+    [
+        [trump_candidate, next, cross[0], cross[1]],
+        [trump_candidate, next, cross[1], cross[0]],
+    ]
+}
+
+fn representative_roles() -> Roles {
+    role_orders(Suit::Hearts)[0]
+}
+
+fn card_code(card: &CardBeforeBidding, roles: &Roles) -> u64 {
+    match card.rank {
+        Rank::Joker => Rank::Joker as u64,
+        rank => {
+            let role = roles.iter().position(|&suit| suit == card.suit).unwrap();
+            (role as u64) << 4 | rank as u64
+        }
+    }
+}
+
+fn rank_from_value(value: u64) -> Rank {
+    Rank::into_enum_iter()
+        .find(|&rank| rank as u64 == value)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(names: [&str; 5]) -> HandBeforeBidding {
+        HandBeforeBidding {
+            cards: names
+                .iter()
+                .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                .collect(),
+        }
+    }
+
+    fn card(name: &str) -> CardBeforeBidding {
+        CardBeforeBidding::from_str(name).unwrap()
+    }
+
+    fn strategies() -> [Strategy; 4] {
+        [
+            Strategy::Advanced,
+            Strategy::Advanced,
+            Strategy::Advanced,
+            Strategy::Advanced,
+        ]
+    }
+
+    #[test]
+    fn equivalent_hands_share_a_key() {
+        let (key, roles) = TableKey::create(
+            &hand(["JS", "JC", "AS", "KD", "9H"]),
+            &card("9S"),
+            Position::West,
+            Position::South,
+        )
+        .unwrap();
+        assert_eq!(
+            [Suit::Spades, Suit::Clubs, Suit::Hearts, Suit::Diamonds],
+            roles
+        );
+        let (swapped, swapped_roles) = TableKey::create(
+            &hand(["JS", "JC", "AS", "KH", "9D"]),
+            &card("9S"),
+            Position::West,
+            Position::South,
+        )
+        .unwrap();
+        assert_eq!(key, swapped);
+        assert_eq!(
+            [Suit::Spades, Suit::Clubs, Suit::Diamonds, Suit::Hearts],
+            swapped_roles
+        );
+        let (translated, _) = TableKey::create(
+            &hand(["KS", "JD", "JH", "AH", "9C"]),
+            &card("9H"),
+            Position::South,
+            Position::East,
+        )
+        .unwrap();
+        assert_eq!(key, translated);
+        assert_eq!(hand(["JH", "AH", "JD", "9S", "KC"]).cards, key.hand().cards);
+        assert_eq!(Position::West, key.dealer());
+        let (partner_dealing, _) = TableKey::create(
+            &hand(["JS", "JC", "AS", "KD", "9H"]),
+            &card("9S"),
+            Position::North,
+            Position::South,
+        )
+        .unwrap();
+        assert_ne!(key, partner_dealing);
+        assert_eq!(
+            None,
+            TableKey::create(
+                &hand(["JS", "JC", "AS", "KD", "9H"]),
+                &CardBeforeBidding::JOKER,
+                Position::West,
+                Position::South,
+            )
+        );
+    }
+
+    #[test]
+    fn all_keys_are_canonical() {
+        let keys = all_keys(&Rules::default());
+        assert!(keys.len() > 6 * 4 * 33_649 / 2 && keys.len() < 6 * 4 * 33_649);
+        assert!(keys.iter().step_by(997).all(|key| {
+            TableKey::create(
+                &key.hand(),
+                &key.trump_candidate(),
+                key.dealer(),
+                Position::South,
+            )
+            .map(|(canonical, _)| canonical)
+                == Some(*key)
+        }));
+    }
+
+    #[test]
+    fn round_trip() {
+        let (key, _) = TableKey::create(
+            &hand(["JS", "JC", "AS", "KD", "9H"]),
+            &card("9S"),
+            Position::West,
+            Position::South,
+        )
+        .unwrap();
+        let table = BidTable::create(
+            Rules {
+                joker: true,
+                deck_size: DeckSize::ThirtyTwo,
+            },
+            500,
+            vec![(key, [125, -80, 3, -400, 0, 17, -200, 400])],
+        );
+        let bytes = table.to_bytes();
+        assert_eq!(HEADER_SIZE + ENTRY_SIZE, bytes.len());
+        assert_eq!(Ok(table), BidTable::from_bytes(&bytes));
+        assert_eq!(
+            Err("The bid table should have 1 entries but is 41 bytes long".to_string()),
+            BidTable::from_bytes(&bytes[..41])
+        );
+        assert_eq!(
+            Err("This isn't a bid table".to_string()),
+            BidTable::from_bytes(b"not a table at all")
+        );
+    }
+
+    #[test]
+    fn lookup_uses_the_actual_suits() {
+        let (key, _) = TableKey::create(
+            &hand(["JS", "JC", "AS", "KD", "9H"]),
+            &card("9S"),
+            Position::West,
+            Position::South,
+        )
+        .unwrap();
+        let table = BidTable::create(
+            Rules::default(),
+            100,
+            vec![(key, [125, -80, 3, -400, 50, 17, -200, 400])],
+        );
+        let values = table
+            .lookup(
+                &hand(["JS", "JC", "AS", "KH", "9D"]),
+                &card("9S"),
+                Position::West,
+                Position::South,
+            )
+            .unwrap();
+        assert_eq!(1.25, values.order_up);
+        assert_eq!(-0.8, values.order_up_alone);
+        assert_eq!(Suit::Clubs, values.calls[0].trump);
+        assert_eq!(Suit::Diamonds, values.calls[1].trump);
+        assert_eq!(0.5, values.calls[1].call);
+        assert_eq!(Suit::Hearts, values.calls[2].trump);
+        assert_eq!(4.0, values.calls[2].call_alone);
+        assert_eq!(
+            None,
+            table.lookup(
+                &hand(["JS", "JC", "AS", "KH", "9D"]),
+                &card("9S"),
+                Position::North,
+                Position::South,
+            )
+        );
+        assert_eq!(
+            vec!["seat=right_of_dealer trump_candidate=9H hand=JH AH JD 9S KC order_up=1.25 order_up_alone=-0.80 call_D=0.03 call_D_alone=-4.00 call_S=0.50 call_S_alone=0.17 call_C=-2.00 call_C_alone=4.00"],
+            table.chart()
+        );
+    }
+
+    #[test]
+    fn generated_values_favor_strong_hands() {
+        let keys: Vec<TableKey> = [
+            hand(["JH", "JD", "AH", "KH", "AS"]),
+            hand(["9C", "10C", "9S", "10S", "QD"]),
+        ]
+        .iter()
+        .map(|hand| {
+            TableKey::create(hand, &card("9H"), Position::South, Position::South)
+                .unwrap()
+                .0
+        })
+        .collect();
+        let table = BidTable::generate(Rules::default(), &strategies(), &keys, 50, 3);
+        assert_eq!(2, table.len());
+        let order_up = |names| {
+            table
+                .lookup(&hand(names), &card("9H"), Position::South, Position::South)
+                .unwrap()
+                .order_up
+        };
+        assert!(
+            order_up(["JH", "JD", "AH", "KH", "AS"]) > order_up(["9C", "10C", "9S", "10S", "QD"])
+        );
+    }
+}
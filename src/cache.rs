@@ -0,0 +1,301 @@
+// Exhaustive simulation results are cached as one JSON file per scenario,
+// named after a hash of the scenario's canonical key. The key lists every
+// input that affects the results, including each seat's strategy version, so a
+// changed strategy simply misses the cache. The file repeats the key so a hash
+// collision is treated as a miss. KEY_VERSION changes whenever the simulation
+// itself does, so results from older builds miss too.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    protocol::{card_name, constraint_name, pass_name, position_name, suit_name},
+    simulation::{self, Bid, HandResult, Scenario, SimulationResults},
+};
+
+// Bump this whenever a change to the simulation itself changes its results,
+// and record the new fingerprints in `versions_track_results`.
+const KEY_VERSION: u32 = 2;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Cache {
+    directory: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    total_count: u64,
+    result_counts: Vec<(HandResult, u64)>,
+}
+
+impl Cache {
+    pub(crate) fn create(directory: PathBuf) -> Cache {
+        Cache { directory }
+    }
+
+    pub(crate) fn default_directory() -> Option<PathBuf> {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .map(|directory| directory.join("euchre"))
+    }
+
+    pub(crate) fn simulate(&self, scenario: &Scenario) -> SimulationResults {
+        let key = match canonical_key(&canonical(scenario)) {
+            Some(key) => key,
+            None => return simulation::simulate(scenario),
+        };
+        if let Some(results) = self.load(&key) {
+            return results;
+        }
+        let results = simulation::simulate(scenario);
+        if let Err(error) = self.save(&key, &results) {
+            log::warn!("Couldn't cache simulation results: {}", error);
+        }
+        results
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{:016x}.json", fnv_hash(key)))
+    }
+
+    fn load(&self, key: &str) -> Option<SimulationResults> {
+        let entry: CacheEntry =
+            serde_json::from_str(&fs::read_to_string(self.path(key)).ok()?).ok()?;
+        (entry.key == key).then(|| SimulationResults {
+            total_count: entry.total_count,
+            result_counts: entry.result_counts.into_iter().collect(),
+        })
+    }
+
+    fn save(&self, key: &str, results: &SimulationResults) -> io::Result<()> {
+        let mut result_counts: Vec<(HandResult, u64)> = results
+            .result_counts
+            .iter()
+            .map(|(result, count)| (result.clone(), *count))
+            .collect();
+        result_counts.sort();
+        let entry = CacheEntry {
+            key: key.to_string(),
+            total_count: results.total_count,
+            result_counts,
+        };
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.path(key), serde_json::to_string(&entry)?)
+    }
+}
+
+fn canonical(scenario: &Scenario) -> Scenario {
+    let mut scenario = scenario.clone();
+    scenario.hand.cards.sort_by_key(card_name);
+    scenario.constraints.pinned.sort_by_key(constraint_name);
+    scenario.constraints.excluded.sort_by_key(constraint_name);
+    scenario.passes.sort_by_key(pass_name);
+    scenario
+}
+
+fn canonical_key(scenario: &Scenario) -> Option<String> {
+    let strategies = scenario
+        .strategies
+        .iter()
+        .map(|strategy| Some(format!("{}@{}", strategy, strategy.version()?)))
+        .collect::<Option<Vec<String>>>()?;
+    let names = |names: Vec<String>| names.join(" ");
+    let bid = match scenario.bid {
+        Bid::OrderUp => "order_up".to_string(),
+        Bid::Call(trump) => format!("call:{}", suit_name(&trump)),
+    };
+    Some(
+        [
+            format!("euchre@{}", env!("CARGO_PKG_VERSION")),
+            format!("key_version={}", KEY_VERSION),
+            format!("joker={}", scenario.rules.joker),
+            format!("deck_size={}", scenario.rules.deck_size.card_count()),
+            format!("dealer={}", position_name(&scenario.dealer)),
+            format!("trump_candidate={}", card_name(&scenario.trump_candidate)),
+            format!(
+                "hand={}",
+                names(scenario.hand.cards.iter().map(card_name).collect())
+            ),
+            format!("bid={}", bid),
+            format!("alone={}", scenario.go_alone),
+            format!("ignore_other_bids={}", scenario.ignore_other_bids),
+            format!("strategies={}", strategies.join(" ")),
+            format!(
+                "pinned={}",
+                names(
+                    scenario
+                        .constraints
+                        .pinned
+                        .iter()
+                        .map(constraint_name)
+                        .collect()
+                )
+            ),
+            format!(
+                "excluded={}",
+                names(
+                    scenario
+                        .constraints
+                        .excluded
+                        .iter()
+                        .map(constraint_name)
+                        .collect()
+                )
+            ),
+            format!(
+                "passed={}",
+                names(scenario.passes.iter().map(pass_name).collect())
+            ),
+            format!(
+                "discard={}",
+                scenario.discard.as_ref().map(card_name).unwrap_or_default()
+            ),
+        ]
+        .join("\n"),
+    )
+}
+
+fn fnv_hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        card::CardBeforeBidding,
+        hand::HandBeforeBidding,
+        hands_iterator::{CardConstraint, Constraints},
+        players::registry::Strategy,
+        position::Position,
+        rules::Rules,
+    };
+    use std::str::FromStr;
+    use test_case::test_case;
+
+    fn scenario(hand: [&str; 5]) -> Scenario {
+        Scenario {
+            rules: Rules::default(),
+            dealer: Position::South,
+            trump_candidate: CardBeforeBidding::from_str("9H").unwrap(),
+            hand: HandBeforeBidding {
+                cards: hand
+                    .iter()
+                    .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                    .collect(),
+            },
+            bid: Bid::OrderUp,
+            go_alone: false,
+            ignore_other_bids: true,
+            strategies: [
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+            ],
+            constraints: Constraints {
+                pinned: [
+                    "10H:W", "QH:W", "KH:W", "9D:W", "10D:W", "QD:N", "KD:N", "AD:N", "9S:N",
+                    "10S:N", "QS:E", "KS:E", "9C:E", "10C:E",
+                ]
+                .map(|name| CardConstraint::from_str(name).unwrap())
+                .to_vec(),
+                excluded: Vec::new(),
+            },
+            passes: Vec::new(),
+            discard: None,
+        }
+    }
+
+    fn cache(name: &str) -> Cache {
+        let directory =
+            env::temp_dir().join(format!("euchre-cache-{}-{}", name, std::process::id()));
+        if directory.exists() {
+            fs::remove_dir_all(&directory).unwrap();
+        }
+        Cache::create(directory)
+    }
+
+    #[test]
+    fn canonical_keys() {
+        let key = canonical_key(&canonical(&scenario(["JH", "JD", "AH", "AS", "AC"]))).unwrap();
+        assert_eq!(
+            key,
+            canonical_key(&canonical(&scenario(["AC", "AS", "JD", "JH", "AH"]))).unwrap()
+        );
+        assert!(key.contains(&format!("advanced@{}", crate::players::advanced::VERSION)));
+        let mut basic = scenario(["JH", "JD", "AH", "AS", "AC"]);
+        basic.strategies[Position::West.index()] = Strategy::Basic;
+        assert_ne!(Some(key), canonical_key(&canonical(&basic)));
+        let mut random = scenario(["JH", "JD", "AH", "AS", "AC"]);
+        random.strategies[Position::West.index()] = Strategy::Random;
+        assert_eq!(None, canonical_key(&canonical(&random)));
+    }
+
+    #[test]
+    fn caches_results() {
+        let cache = cache("results");
+        let scenario = scenario(["JH", "JD", "AH", "AS", "AC"]);
+        let results = cache.simulate(&scenario);
+        assert_eq!(simulation::simulate(&scenario), results);
+        let key = canonical_key(&canonical(&scenario)).unwrap();
+        assert_eq!(Some(results.clone()), cache.load(&key));
+        assert_eq!(results, cache.simulate(&scenario));
+        let path = cache.path(&key);
+        let mut entry: CacheEntry =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        entry.key = "another scenario".to_string();
+        fs::write(&path, serde_json::to_string(&entry).unwrap()).unwrap();
+        assert_eq!(None, cache.load(&key));
+        fs::remove_dir_all(&cache.directory).unwrap();
+    }
+
+    // The results of a fixed scenario, tagged with the versions that produced
+    // them. When this fails, bump the strategy's VERSION (or KEY_VERSION) so
+    // stale cache entries miss, then update the fingerprint.
+    #[test_case(Strategy::Basic => (2, 1, 8611105399472053927))]
+    #[test_case(Strategy::Advanced => (2, 1, 9693958173045736018))]
+    fn versions_track_results(strategy: Strategy) -> (u32, u32, u64) {
+        let mut scenario = scenario(["JH", "JD", "AH", "AS", "AC"]);
+        scenario.constraints.pinned.truncate(9);
+        scenario.strategies = [
+            strategy.clone(),
+            strategy.clone(),
+            strategy.clone(),
+            strategy.clone(),
+        ];
+        let mut result_counts: Vec<(HandResult, u64)> = simulation::simulate(&scenario)
+            .result_counts
+            .into_iter()
+            .collect();
+        result_counts.sort();
+        (
+            KEY_VERSION,
+            strategy.version().unwrap(),
+            fnv_hash(&serde_json::to_string(&result_counts).unwrap()),
+        )
+    }
+
+    #[test]
+    fn cached_results_match_uncached_results() {
+        let cache = cache("unsorted");
+        let mut scenario = scenario(["10C", "KS", "QC", "AC", "JH"]);
+        scenario.constraints.pinned = [
+            "QH:W", "KH:W", "9D:W", "10D:W", "QD:N", "KD:N", "AD:N", "9S:N", "10S:N",
+        ]
+        .map(|name| CardConstraint::from_str(name).unwrap())
+        .to_vec();
+        let results = simulation::simulate(&scenario);
+        assert_eq!(results, cache.simulate(&scenario));
+        assert_eq!(results, cache.simulate(&scenario));
+        fs::remove_dir_all(&cache.directory).unwrap();
+    }
+}
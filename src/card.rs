@@ -2,12 +2,33 @@ use crate::{rank::Rank, rank_with_bowers::RankWithBowers, suit::Suit};
 use core::fmt;
 use std::str::FromStr;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+const JOKER_UNICODE: u32 = 0x1F0CF;
+
+#[derive(Copy, Clone, Debug, Eq)]
 pub struct CardBeforeBidding {
     pub suit: Suit,
     pub rank: Rank,
 }
 
+impl CardBeforeBidding {
+    pub const JOKER: CardBeforeBidding = CardBeforeBidding {
+        suit: Suit::Diamonds,
+        rank: Rank::Joker,
+    };
+
+    pub fn is_trump(&self, trump: &Suit) -> bool {
+        self.rank == Rank::Joker
+            || self.suit == *trump
+            || self.rank == Rank::Jack && self.suit == trump.other_suit_of_same_color()
+    }
+}
+
+impl PartialEq for CardBeforeBidding {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank && (self.suit == other.suit || self.rank == Rank::Joker)
+    }
+}
+
 impl From<Card> for CardBeforeBidding {
     fn from(value: Card) -> Self {
         CardBeforeBidding {
@@ -25,6 +46,9 @@ impl FromStr for CardBeforeBidding {
     type Err = String;
 
     fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if let Ok(Rank::Joker) = Rank::from_str(name) {
+            return Ok(CardBeforeBidding::JOKER);
+        }
         let (rank_name, suit_name) = name.split_at(name.len() - 1);
         match Rank::from_str(rank_name) {
             Ok(rank) => Suit::from_str(suit_name).map(|suit| CardBeforeBidding { rank, suit }),
@@ -35,9 +59,7 @@ impl FromStr for CardBeforeBidding {
 
 impl fmt::Display for CardBeforeBidding {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let unicode_value =
-            self.suit.starting_point_for_unicode_card() + self.rank.offset_for_unicode_card();
-        let unicode_char = char::from_u32(unicode_value);
+        let unicode_char = char::from_u32(unicode_value(&self.suit, &self.rank));
         match unicode_char {
             Some(c) => write!(f, "{}", c),
             _ => write!(f, "{}{}", self.rank, self.suit),
@@ -53,12 +75,10 @@ pub struct Card {
 
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let unicode_value = self
-            .rank
-            .suit_for_display(&self.suit)
-            .starting_point_for_unicode_card()
-            + self.rank.rank_for_display().offset_for_unicode_card();
-        let unicode_char = char::from_u32(unicode_value);
+        let unicode_char = char::from_u32(unicode_value(
+            &self.rank.suit_for_display(&self.suit),
+            &self.rank.rank_for_display(),
+        ));
         match unicode_char {
             Some(c) => write!(f, "{}", c),
             _ => write!(
@@ -76,6 +96,10 @@ impl Card {
         cards
             .into_iter()
             .map(|card| match card.rank {
+                Rank::Joker => Card {
+                    suit: trump,
+                    rank: RankWithBowers::Joker,
+                },
                 Rank::Jack if card.suit == trump => Card {
                     suit: trump,
                     rank: RankWithBowers::RightBower,
@@ -93,11 +117,20 @@ impl Card {
     }
 }
 
+fn unicode_value(suit: &Suit, rank: &Rank) -> u32 {
+    match rank {
+        Rank::Joker => JOKER_UNICODE,
+        rank => suit.starting_point_for_unicode_card() + rank.offset_for_unicode_card(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use test_case::test_case;
 
+    #[test_case(Card {suit: Suit::Clubs, rank: RankWithBowers::Joker} => "\u{1F0CF}")]
+    #[test_case(Card {suit: Suit::Hearts, rank: RankWithBowers::Joker} => "\u{1F0CF}")]
     #[test_case(Card {suit: Suit::Clubs, rank: RankWithBowers::RightBower} => "\u{1F0DB}")]
     #[test_case(Card {suit: Suit::Clubs, rank: RankWithBowers::LeftBower} => "\u{1F0AB}")]
     #[test_case(Card {suit: Suit::Clubs, rank: RankWithBowers::Ace} => "\u{1F0D1}")]
@@ -130,6 +163,8 @@ mod tests {
     #[test_case(Card {suit: Suit::Spades, rank: RankWithBowers::Jack} => "\u{1F0AB}")]
     #[test_case(Card {suit: Suit::Spades, rank: RankWithBowers::Ten} => "\u{1F0AA}")]
     #[test_case(Card {suit: Suit::Spades, rank: RankWithBowers::Nine} => "\u{1F0A9}")]
+    #[test_case(Card {suit: Suit::Spades, rank: RankWithBowers::Eight} => "\u{1F0A8}")]
+    #[test_case(Card {suit: Suit::Spades, rank: RankWithBowers::Seven} => "\u{1F0A7}")]
     fn display(card: Card) -> String {
         card.to_string()
     }
@@ -145,7 +180,62 @@ mod tests {
     #[test_case("QC" => Ok(CardBeforeBidding {rank:Rank::Queen, suit: Suit::Clubs}))]
     #[test_case("KC" => Ok(CardBeforeBidding {rank:Rank::King, suit: Suit::Clubs}))]
     #[test_case("AC" => Ok(CardBeforeBidding {rank:Rank::Ace, suit: Suit::Clubs}))]
+    #[test_case("8C" => Ok(CardBeforeBidding {rank:Rank::Eight, suit: Suit::Clubs}))]
+    #[test_case("7C" => Ok(CardBeforeBidding {rank:Rank::Seven, suit: Suit::Clubs}))]
+    #[test_case("EC" => Err("Invalid rank: E".to_string()))]
+    #[test_case("SC" => Err("Invalid rank: S".to_string()))]
+    #[test_case("JK" => Ok(CardBeforeBidding::JOKER))]
     fn from_str(name: &str) -> Result<CardBeforeBidding, String> {
         CardBeforeBidding::from_str(name)
     }
+
+    #[test_case(CardBeforeBidding::JOKER, Suit::Spades => true)]
+    #[test_case(CardBeforeBidding::JOKER, Suit::Hearts => true)]
+    #[test_case(CardBeforeBidding {rank: Rank::Jack, suit: Suit::Clubs}, Suit::Spades => true)]
+    #[test_case(CardBeforeBidding {rank: Rank::Jack, suit: Suit::Hearts}, Suit::Spades => false)]
+    #[test_case(CardBeforeBidding {rank: Rank::Nine, suit: Suit::Spades}, Suit::Spades => true)]
+    #[test_case(CardBeforeBidding {rank: Rank::Ace, suit: Suit::Clubs}, Suit::Spades => false)]
+    fn is_trump(card: CardBeforeBidding, trump: Suit) -> bool {
+        card.is_trump(&trump)
+    }
+
+    #[test]
+    fn jokers_are_equal_regardless_of_suit() {
+        assert_eq!(
+            CardBeforeBidding::JOKER,
+            CardBeforeBidding {
+                suit: Suit::Clubs,
+                rank: Rank::Joker
+            }
+        );
+    }
+
+    #[test_case(Suit::Spades ; "spades")]
+    #[test_case(Suit::Hearts ; "hearts")]
+    fn update_bowers_makes_joker_highest_trump(trump: Suit) {
+        let cards = Card::update_bowers(
+            vec![
+                CardBeforeBidding::JOKER,
+                CardBeforeBidding {
+                    suit: trump,
+                    rank: Rank::Jack,
+                },
+            ],
+            &trump,
+        );
+        assert_eq!(
+            vec![
+                Card {
+                    suit: trump,
+                    rank: RankWithBowers::Joker
+                },
+                Card {
+                    suit: trump,
+                    rank: RankWithBowers::RightBower
+                },
+            ],
+            cards
+        );
+        assert!(cards[0].rank > cards[1].rank);
+    }
 }
@@ -1,29 +1,39 @@
 use crate::card::*;
 use crate::hand::HandBeforeBidding;
 use crate::rank::Rank;
+use crate::rules::Rules;
 use crate::suit::Suit;
 use enum_iterator::IntoEnumIterator;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 
 pub(crate) struct Deck {
     pub(crate) cards: Vec<CardBeforeBidding>,
 }
 
 impl Deck {
-    pub(crate) fn create_all_cards() -> Vec<CardBeforeBidding> {
-        let mut cards = Vec::with_capacity(24);
+    pub(crate) fn create_all_cards(rules: &Rules) -> Vec<CardBeforeBidding> {
+        let mut cards = Vec::with_capacity(33);
         for suit in Suit::into_enum_iter() {
-            for rank in Rank::into_enum_iter() {
+            for rank in Rank::into_enum_iter()
+                .filter(|&rank| rank != Rank::Joker && rules.deck_size.includes(rank))
+            {
                 cards.push(CardBeforeBidding { suit, rank });
             }
         }
+        if rules.joker {
+            cards.push(CardBeforeBidding::JOKER);
+        }
         cards
     }
 
-    pub(crate) fn create_shuffled_deck() -> Deck {
-        let mut cards = Deck::create_all_cards();
-        cards.shuffle(&mut thread_rng());
+    pub(crate) fn create_shuffled_deck(rules: &Rules) -> Deck {
+        Deck::create_shuffled_deck_with_rng(rules, &mut thread_rng())
+    }
+
+    pub(crate) fn create_shuffled_deck_with_rng(rules: &Rules, rng: &mut impl Rng) -> Deck {
+        let mut cards = Deck::create_all_cards(rules);
+        cards.shuffle(rng);
         Deck { cards }
     }
 
@@ -56,4 +66,71 @@ impl Deck {
         hands[3].cards.push(self.cards.pop().unwrap());
         (hands, self.cards.pop().unwrap())
     }
+
+    pub(crate) fn deal_bid_euchre(&mut self) -> [HandBeforeBidding; 4] {
+        let mut hands = [
+            HandBeforeBidding { cards: Vec::new() },
+            HandBeforeBidding { cards: Vec::new() },
+            HandBeforeBidding { cards: Vec::new() },
+            HandBeforeBidding { cards: Vec::new() },
+        ];
+        for _ in 0..2 {
+            for hand in hands.iter_mut() {
+                hand.cards.push(self.cards.pop().unwrap());
+                hand.cards.push(self.cards.pop().unwrap());
+                hand.cards.push(self.cards.pop().unwrap());
+            }
+        }
+        hands
+    }
+
+    pub(crate) fn deal_two_handed(&mut self) -> ([HandBeforeBidding; 2], CardBeforeBidding) {
+        let mut hands = [
+            HandBeforeBidding { cards: Vec::new() },
+            HandBeforeBidding { cards: Vec::new() },
+        ];
+        hands[0].cards.push(self.cards.pop().unwrap());
+        hands[0].cards.push(self.cards.pop().unwrap());
+        hands[0].cards.push(self.cards.pop().unwrap());
+        hands[1].cards.push(self.cards.pop().unwrap());
+        hands[1].cards.push(self.cards.pop().unwrap());
+        hands[0].cards.push(self.cards.pop().unwrap());
+        hands[0].cards.push(self.cards.pop().unwrap());
+        hands[1].cards.push(self.cards.pop().unwrap());
+        hands[1].cards.push(self.cards.pop().unwrap());
+        hands[1].cards.push(self.cards.pop().unwrap());
+        (hands, self.cards.pop().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::DeckSize;
+    use test_case::test_case;
+
+    #[test_case(DeckSize::TwentyFour, false => 24)]
+    #[test_case(DeckSize::TwentyFour, true => 25)]
+    #[test_case(DeckSize::TwentyEight, false => 28)]
+    #[test_case(DeckSize::TwentyEight, true => 29)]
+    #[test_case(DeckSize::ThirtyTwo, false => 32)]
+    #[test_case(DeckSize::ThirtyTwo, true => 33)]
+    fn create_all_cards(deck_size: DeckSize, joker: bool) -> usize {
+        Deck::create_all_cards(&Rules { joker, deck_size }).len()
+    }
+
+    #[test_case(DeckSize::TwentyFour => 4)]
+    #[test_case(DeckSize::TwentyEight => 8)]
+    #[test_case(DeckSize::ThirtyTwo => 12)]
+    fn deal(deck_size: DeckSize) -> usize {
+        let mut deck = Deck::create_shuffled_deck(&Rules {
+            joker: false,
+            deck_size,
+        });
+        let (hands, _) = deck.deal();
+        for hand in hands {
+            assert_eq!(5, hand.cards.len());
+        }
+        deck.cards.len() + 1
+    }
 }
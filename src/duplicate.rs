@@ -0,0 +1,162 @@
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    card::CardBeforeBidding, deck::Deck, hand::HandBeforeBidding, hand_state::HandState,
+    match_play::FirstDealer, players::registry::Strategy, position::Position, rules::Rules,
+    tournament,
+};
+
+#[derive(Clone, Debug)]
+pub(crate) struct Board {
+    pub(crate) dealer: Position,
+    pub(crate) hands: [HandBeforeBidding; 4],
+    pub(crate) trump_candidate: CardBeforeBidding,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BoardResult {
+    pub(crate) scores: [i8; 2],
+}
+
+impl Board {
+    pub(crate) fn deal(rules: &Rules, dealer: Position) -> Board {
+        let (hands, trump_candidate) = Deck::create_shuffled_deck(rules).deal();
+        Board {
+            dealer,
+            hands,
+            trump_candidate,
+        }
+    }
+
+    pub(crate) fn play(&self, [north_south, east_west]: [&Strategy; 2], rules: Rules) -> i8 {
+        let mut players = [
+            north_south.create(Position::North, rules),
+            east_west.create(Position::East, rules),
+            north_south.create(Position::South, rules),
+            east_west.create(Position::West, rules),
+        ];
+        let mut hand_state =
+            HandState::create(self.dealer, self.trump_candidate, self.hands.clone());
+        loop {
+            if let Some((winner, score)) = hand_state.step(&mut players) {
+                return if winner.team_index() == 0 {
+                    score as i8
+                } else {
+                    -(score as i8)
+                };
+            }
+        }
+    }
+}
+
+impl BoardResult {
+    pub(crate) fn difference(&self) -> i8 {
+        self.scores[0] + self.scores[1]
+    }
+}
+
+pub(crate) fn play_boards(
+    strategies: &[Strategy; 2],
+    rules: Rules,
+    boards: u32,
+) -> Vec<BoardResult> {
+    (0..boards)
+        .into_par_iter()
+        .map(|board| {
+            let board = Board::deal(&rules, FirstDealer::Rotate.dealer(board));
+            BoardResult {
+                scores: [
+                    board.play([&strategies[0], &strategies[1]], rules),
+                    -board.play([&strategies[1], &strategies[0]], rules),
+                ],
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn mean_difference(board_results: &[BoardResult]) -> f64 {
+    board_results
+        .iter()
+        .map(|board_result| board_result.difference() as f64)
+        .sum::<f64>()
+        / board_results.len() as f64
+}
+
+pub(crate) fn standard_error(board_results: &[BoardResult]) -> f64 {
+    if board_results.len() < 2 {
+        return 0f64;
+    }
+    let count = board_results.len() as f64;
+    let mean = mean_difference(board_results);
+    let variance = board_results
+        .iter()
+        .map(|board_result| (board_result.difference() as f64 - mean).powi(2))
+        .sum::<f64>()
+        / (count - 1f64);
+    (variance / count).sqrt()
+}
+
+pub(crate) fn p_value(board_results: &[BoardResult]) -> f64 {
+    if board_results.len() < 2 {
+        return 1f64;
+    }
+    let standard_error = standard_error(board_results);
+    if standard_error == 0f64 {
+        return if mean_difference(board_results) == 0f64 {
+            1f64
+        } else {
+            0f64
+        };
+    }
+    tournament::p_value(mean_difference(board_results) / standard_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_results(scores: &[[i8; 2]]) -> Vec<BoardResult> {
+        scores
+            .iter()
+            .map(|&scores| BoardResult { scores })
+            .collect()
+    }
+
+    #[test]
+    fn difference() {
+        assert_eq!(3, BoardResult { scores: [1, 2] }.difference());
+        assert_eq!(0, BoardResult { scores: [-2, 2] }.difference());
+    }
+
+    #[test]
+    fn statistics() {
+        let board_results = board_results(&[[1, 1], [2, -2], [2, 0], [-1, 1]]);
+        assert_eq!(1f64, mean_difference(&board_results));
+        assert!((standard_error(&board_results) - (1f64 / 3f64).sqrt()).abs() < 1e-9);
+        assert!((p_value(&board_results) - 0.083).abs() < 0.001);
+    }
+
+    #[test]
+    fn single_board_statistics() {
+        let board_results = board_results(&[[2, -1]]);
+        assert_eq!(1f64, mean_difference(&board_results));
+        assert_eq!(0f64, standard_error(&board_results));
+        assert_eq!(1f64, p_value(&board_results));
+    }
+
+    #[test]
+    fn same_strategy_ties_every_board() {
+        let strategies = [&Strategy::Advanced, &Strategy::Advanced];
+        let board = Board::deal(&Rules::default(), Position::South);
+        let first = board.play(strategies, Rules::default());
+        let second = board.play(strategies, Rules::default());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn plays_boards() {
+        let board_results =
+            play_boards(&[Strategy::Advanced, Strategy::Basic], Rules::default(), 4);
+        assert_eq!(4, board_results.len());
+    }
+}
@@ -0,0 +1,301 @@
+// A game record is a JSON file holding the rules, and for each hand the
+// dealer, the up card, the four hands as dealt (North, East, South, West) and
+// every decision in the order it was made. Decisions are tagged by `type` like
+// the network protocol: `{"player": "S", "type": "order_up", "yes": true}`,
+// `{"player": "W", "type": "call_trump", "suit": null}`,
+// `{"player": "N", "type": "play", "card": "JD"}`.
+
+use std::{
+    fmt, fs, io,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bid_state::BidPhase,
+    card::CardBeforeBidding,
+    game_state::{GamePhase, GameState},
+    hand::HandBeforeBidding,
+    hand_state::{HandPhase, HandState},
+    player::Player,
+    position::Position,
+    protocol::{self, card_name, position_name, suit_name},
+    rules::{DeckSize, Rules},
+    suit::Suit,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct GameRecord {
+    pub(crate) joker: bool,
+    pub(crate) deck_size: u8,
+    pub(crate) hands: Vec<HandRecord>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HandRecord {
+    pub(crate) dealer: String,
+    pub(crate) up_card: String,
+    pub(crate) hands: [Vec<String>; 4],
+    pub(crate) decisions: Vec<Decision>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Decision {
+    pub(crate) player: String,
+    #[serde(flatten)]
+    pub(crate) choice: Choice,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum Choice {
+    OrderUp { yes: bool },
+    OrderUpAlone { yes: bool },
+    DefendAloneOrdered { yes: bool },
+    CallTrump { suit: Option<String> },
+    CallAlone { yes: bool },
+    DefendAloneCalled { yes: bool },
+    JokerSuit { suit: String },
+    Discard { card: String },
+    Play { card: String },
+}
+
+#[derive(Clone)]
+pub(crate) struct Recorder {
+    record: Arc<Mutex<GameRecord>>,
+}
+
+impl GameRecord {
+    pub(crate) fn create(rules: Rules) -> GameRecord {
+        GameRecord {
+            joker: rules.joker,
+            deck_size: rules.deck_size.card_count(),
+            hands: Vec::new(),
+        }
+    }
+
+    pub(crate) fn load(path: &Path) -> io::Result<GameRecord> {
+        serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub(crate) fn rules(&self) -> Result<Rules, String> {
+        Ok(Rules {
+            joker: self.joker,
+            deck_size: DeckSize::from_str(&self.deck_size.to_string())?,
+        })
+    }
+}
+
+impl HandRecord {
+    pub(crate) fn dealer(&self) -> Result<Position, String> {
+        Position::from_str(&self.dealer)
+    }
+
+    pub(crate) fn up_card(&self) -> Result<CardBeforeBidding, String> {
+        protocol::parse_card(&self.up_card)
+    }
+
+    pub(crate) fn dealt_hands(&self) -> Result<[HandBeforeBidding; 4], String> {
+        let [north, east, south, west] = &self.hands;
+        Ok([
+            parse_hand(north)?,
+            parse_hand(east)?,
+            parse_hand(south)?,
+            parse_hand(west)?,
+        ])
+    }
+}
+
+impl Decision {
+    pub(crate) fn position(&self) -> Result<Position, String> {
+        Position::from_str(&self.player)
+    }
+}
+
+impl Choice {
+    pub(crate) fn call(suit: Option<&Suit>) -> Choice {
+        Choice::CallTrump {
+            suit: suit.map(|suit| suit_name(suit).to_string()),
+        }
+    }
+
+    pub(crate) fn joker_suit(suit: &Suit) -> Choice {
+        Choice::JokerSuit {
+            suit: suit_name(suit).to_string(),
+        }
+    }
+
+    pub(crate) fn discard(card: &CardBeforeBidding) -> Choice {
+        Choice::Discard {
+            card: card_name(card),
+        }
+    }
+
+    pub(crate) fn play(card: &CardBeforeBidding) -> Choice {
+        Choice::Play {
+            card: card_name(card),
+        }
+    }
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let card = |name: &str| {
+            protocol::parse_card(name)
+                .map(|card| card.to_string())
+                .unwrap_or_else(|_| name.to_string())
+        };
+        let suit = |name: &str| {
+            Suit::from_str(name)
+                .map(|suit| suit.to_string())
+                .unwrap_or_else(|_| name.to_string())
+        };
+        match self {
+            Self::OrderUp { yes: true } => write!(f, "order up"),
+            Self::OrderUpAlone { yes: true } | Self::CallAlone { yes: true } => {
+                write!(f, "go alone")
+            }
+            Self::OrderUpAlone { yes: false } | Self::CallAlone { yes: false } => {
+                write!(f, "play with partner")
+            }
+            Self::DefendAloneOrdered { yes: true } | Self::DefendAloneCalled { yes: true } => {
+                write!(f, "defend alone")
+            }
+            Self::DefendAloneOrdered { yes: false } | Self::DefendAloneCalled { yes: false } => {
+                write!(f, "defend with partner")
+            }
+            Self::OrderUp { yes: false } | Self::CallTrump { suit: None } => write!(f, "pass"),
+            Self::CallTrump { suit: Some(name) } => write!(f, "call {}", suit(name)),
+            Self::JokerSuit { suit: name } => write!(f, "name {}", suit(name)),
+            Self::Discard { card: name } => write!(f, "discard {}", card(name)),
+            Self::Play { card: name } => write!(f, "play {}", card(name)),
+        }
+    }
+}
+
+impl Recorder {
+    pub(crate) fn create(rules: Rules) -> Recorder {
+        Recorder {
+            record: Arc::new(Mutex::new(GameRecord::create(rules))),
+        }
+    }
+
+    pub(crate) fn observe<T: Player>(&self, game_state: &GameState<T>) {
+        if let GamePhase::Playing { hand_state } = &game_state.phase {
+            if let Some(hand_record) = new_deal(hand_state) {
+                let mut record = self.record.lock().unwrap();
+                if record.hands.last().map(|hand| &hand.dealer) != Some(&hand_record.dealer) {
+                    record.hands.push(hand_record);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn record(&self, player: &Position, choice: Choice) {
+        if let Some(hand) = self.record.lock().unwrap().hands.last_mut() {
+            hand.decisions.push(Decision {
+                player: position_name(player).to_string(),
+                choice,
+            });
+        }
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        self.record.lock().unwrap().save(path)
+    }
+}
+
+fn parse_hand(names: &[String]) -> Result<HandBeforeBidding, String> {
+    Ok(HandBeforeBidding {
+        cards: names
+            .iter()
+            .map(|name| protocol::parse_card(name))
+            .collect::<Result<Vec<CardBeforeBidding>, String>>()?,
+    })
+}
+
+fn new_deal(hand_state: &HandState) -> Option<HandRecord> {
+    match &hand_state.phase {
+        HandPhase::Bidding { bid_state, hands } => match bid_state.phase {
+            BidPhase::DealerNamesJokerSuit { trump_candidate }
+            | BidPhase::FirstRoundFirstPlayer { trump_candidate } => Some(HandRecord {
+                dealer: position_name(&hand_state.dealer).to_string(),
+                up_card: card_name(&trump_candidate),
+                hands: hands
+                    .clone()
+                    .map(|hand| hand.cards.iter().map(card_name).collect()),
+                decisions: Vec::new(),
+            }),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::{advanced::AdvancedPlayer, recording::RecordingPlayer};
+
+    #[test]
+    fn records_every_hand() {
+        let rules = Rules::default();
+        let recorder = Recorder::create(rules);
+        let players = [
+            Position::North,
+            Position::East,
+            Position::South,
+            Position::West,
+        ]
+        .map(|position| {
+            RecordingPlayer::create(
+                position,
+                Box::new(AdvancedPlayer::create(position)),
+                recorder.clone(),
+            )
+        });
+        let mut game_state = GameState::create_seeded(players, rules, Position::South, 3);
+        loop {
+            recorder.observe(&game_state);
+            if game_state.step().is_some() {
+                break;
+            }
+        }
+        let record = recorder.record.lock().unwrap().clone();
+        assert_eq!(game_state.hands_played as usize, record.hands.len());
+        let first_hand = &record.hands[0];
+        assert_eq!("S", first_hand.dealer);
+        assert!(first_hand
+            .dealt_hands()
+            .unwrap()
+            .iter()
+            .all(|hand| hand.cards.len() == 5));
+        assert!(first_hand.decisions.iter().any(|decision| matches!(
+            decision.choice,
+            Choice::OrderUp { .. } | Choice::CallTrump { .. }
+        )));
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(record, serde_json::from_str::<GameRecord>(&json).unwrap());
+    }
+
+    #[test]
+    fn describes_choices() {
+        assert_eq!("pass", Choice::call(None).to_string());
+        assert_eq!(
+            format!("call {}", Suit::Hearts),
+            Choice::call(Some(&Suit::Hearts)).to_string()
+        );
+        assert_eq!(
+            format!("play {}", CardBeforeBidding::from_str("JD").unwrap()),
+            Choice::play(&CardBeforeBidding::from_str("JD").unwrap()).to_string()
+        );
+    }
+}
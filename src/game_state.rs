@@ -1,10 +1,24 @@
-use crate::{deck::Deck, hand_state::HandState, player::Player, position::Position};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+use crate::{
+    bid_result::BidResultAll,
+    deck::Deck,
+    hand_state::{HandPhase, HandState},
+    player::Player,
+    position::Position,
+    rules::Rules,
+};
 
 pub(crate) struct GameState<T: Player> {
     pub(crate) players: [T; 4],
+    pub(crate) rules: Rules,
     pub(crate) phase: GamePhase,
     pub(crate) north_south_score: u8,
     pub(crate) east_west_score: u8,
+    pub(crate) hands_played: u32,
+    pub(crate) loners: [u32; 2],
+    pub(crate) euchres: [u32; 2],
+    rng: StdRng,
 }
 
 pub(crate) enum GamePhase {
@@ -13,22 +27,53 @@ pub(crate) enum GamePhase {
 }
 
 impl<T: Player> GameState<T> {
-    pub(crate) fn create(players: [T; 4]) -> GameState<T> {
-        let (hands, trump_candidate) = Deck::create_shuffled_deck().deal();
+    pub(crate) fn create(players: [T; 4], rules: Rules, dealer: Position) -> GameState<T> {
+        GameState::create_seeded(players, rules, dealer, thread_rng().gen())
+    }
+
+    pub(crate) fn create_seeded(
+        players: [T; 4],
+        rules: Rules,
+        dealer: Position,
+        seed: u64,
+    ) -> GameState<T> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (hands, trump_candidate) = Deck::create_shuffled_deck_with_rng(&rules, &mut rng).deal();
         GameState {
             players,
+            rules,
             phase: GamePhase::Playing {
-                hand_state: HandState::create(Position::South, trump_candidate, hands),
+                hand_state: HandState::create(dealer, trump_candidate, hands),
             },
             north_south_score: 0,
             east_west_score: 0,
+            hands_played: 0,
+            loners: [0; 2],
+            euchres: [0; 2],
+            rng,
         }
     }
 
     pub(crate) fn step(&mut self) -> Option<String> {
         match &mut self.phase {
             GamePhase::Playing { ref mut hand_state } => {
-                if let Some((player, score)) = hand_state.step(&mut self.players) {
+                let bid_phase = match &hand_state.phase {
+                    HandPhase::Bidding { bid_state, .. } => Some(bid_state.phase.clone()),
+                    _ => None,
+                };
+                let step = hand_state.step(&mut self.players);
+                if let (Some(before), HandPhase::Bidding { bid_state, .. }) =
+                    (bid_phase, &hand_state.phase)
+                {
+                    if let Some(message) = before.announcement(&bid_state.phase, &bid_state.dealer)
+                    {
+                        log::info!("{}", message);
+                    }
+                }
+                if let Some((player, score)) = step {
+                    if let Some(bid_result) = hand_state.final_bid_result() {
+                        self.record_hand(&bid_result, &player);
+                    }
                     self.finish_hand(player, score)
                 }
                 None
@@ -47,6 +92,22 @@ impl<T: Player> GameState<T> {
         }
     }
 
+    fn record_hand(&mut self, bid_result: &BidResultAll, winner: &Position) {
+        self.hands_played += 1;
+        let caller = match bid_result {
+            BidResultAll::Called { caller, .. } => caller,
+            BidResultAll::CalledAlone { caller, .. }
+            | BidResultAll::DefendedAlone { caller, .. } => {
+                self.loners[caller.team_index()] += 1;
+                caller
+            }
+            BidResultAll::NoOneCalled => return,
+        };
+        if caller.team_index() != winner.team_index() {
+            self.euchres[caller.team_index()] += 1;
+        }
+    }
+
     fn finish_hand(&mut self, player: Position, score: u8) {
         if score > 0 {
             match player {
@@ -78,7 +139,8 @@ impl<T: Player> GameState<T> {
         }
         match &self.phase {
             GamePhase::Playing { hand_state, .. } => {
-                let (hands, trump_candidate) = Deck::create_shuffled_deck().deal();
+                let (hands, trump_candidate) =
+                    Deck::create_shuffled_deck_with_rng(&self.rules, &mut self.rng).deal();
                 self.phase = GamePhase::Playing {
                     hand_state: HandState::create(
                         hand_state.dealer.next_position_bidding(),
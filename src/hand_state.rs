@@ -1,21 +1,22 @@
-use rayon::{
-    iter::{IterBridge, MapWith},
-    prelude::{ParallelBridge, ParallelIterator},
-};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use rayon::prelude::{IntoParallelIterator, ParallelBridge, ParallelIterator};
 
 use crate::{
     bid_result::{BidResultAll, BidResultCalled},
     bid_state::BidState,
-    card::CardBeforeBidding,
+    card::{Card, CardBeforeBidding},
     deck::Deck,
     hand::{Hand, HandBeforeBidding},
-    hands_iterator::{CardLocation, HandsIterator},
+    hands_iterator::{CardConstraint, CardLocation, Constraints, HandsIterator},
     player::Player,
     position::Position,
+    rules::Rules,
     suit::Suit,
-    trick_state::TrickState,
+    trick_state::{PlayedCard, TrickPhase, TrickState},
 };
 
+const MAX_SHUFFLES: usize = 1_000;
+
 #[derive(Debug)]
 pub(crate) struct HandState {
     pub(crate) dealer: Position,
@@ -78,47 +79,152 @@ impl HandState {
         }
     }
 
+    pub(crate) fn create_with_lead(
+        bid_result: BidResultCalled,
+        leader: Position,
+        mut hands: [Hand; 4],
+        lead: Card,
+    ) -> HandState {
+        hands[leader.index()].cards.retain(|card| *card != lead);
+        HandState {
+            dealer: leader.partner().next_position_bidding(),
+            phase: HandPhase::FirstTrick {
+                trick_state: TrickState {
+                    bid_result: bid_result.clone(),
+                    leader,
+                    phase: TrickPhase::BeforeSecondCard {
+                        cards_played: [PlayedCard {
+                            player: leader,
+                            card: lead,
+                        }],
+                    },
+                },
+                hands,
+                bid_result,
+            },
+        }
+    }
+
     pub(crate) fn create_with_scenario(
+        rules: &Rules,
         dealer: Position,
         trump_candidate: CardBeforeBidding,
         my_hand: HandBeforeBidding,
-    ) -> MapWith<
-        IterBridge<HandsIterator>,
-        (
-            Position,
-            CardBeforeBidding,
-            HandBeforeBidding,
-            [CardBeforeBidding; 18],
-        ),
-        impl Fn(
-            &mut (
-                Position,
-                CardBeforeBidding,
-                HandBeforeBidding,
-                [CardBeforeBidding; 18],
-            ),
-            [CardLocation; 18],
-        ) -> HandState,
-    > {
-        let mut available_cards = Deck::create_all_cards();
-        available_cards.retain(|&card| trump_candidate != card && !my_hand.cards.contains(&card));
-        let available_cards = available_cards.try_into().unwrap();
-        HandsIterator::create().par_bridge().map_with(
-            (dealer, trump_candidate, my_hand, available_cards),
-            |(dealer, trump_candidate, my_hand, available_cards), permutation| {
-                HandState::create(
-                    *dealer,
-                    *trump_candidate,
-                    HandState::generate_hands(my_hand, available_cards, permutation),
-                )
-            },
+        constraints: &Constraints,
+    ) -> impl ParallelIterator<Item = HandState> {
+        HandState::deals(rules, &[trump_candidate], my_hand, constraints)
+            .map(move |hands| HandState::create(dealer, trump_candidate, hands))
+    }
+
+    pub(crate) fn create_with_sampled_scenario(
+        rules: &Rules,
+        dealer: Position,
+        trump_candidate: CardBeforeBidding,
+        my_hand: HandBeforeBidding,
+        constraints: &Constraints,
+        samples: u64,
+        seed: u64,
+    ) -> impl ParallelIterator<Item = HandState> {
+        HandState::sampled_deals(
+            rules,
+            &[trump_candidate],
+            my_hand,
+            constraints,
+            samples,
+            seed,
         )
+        .map(move |hands| HandState::create(dealer, trump_candidate, hands))
+    }
+
+    pub(crate) fn deals(
+        rules: &Rules,
+        known_cards: &[CardBeforeBidding],
+        my_hand: HandBeforeBidding,
+        constraints: &Constraints,
+    ) -> impl ParallelIterator<Item = [HandBeforeBidding; 4]> {
+        let (available_cards, slots, exclusions) =
+            HandState::unknown_cards(rules, known_cards, &my_hand, constraints);
+        let pinned = constraints.pinned.clone();
+        HandsIterator::create_with_slots(slots, exclusions)
+            .par_bridge()
+            .map(move |permutation| {
+                HandState::generate_hands(&my_hand, &available_cards, &pinned, permutation)
+            })
+    }
+
+    pub(crate) fn sampled_deals(
+        rules: &Rules,
+        known_cards: &[CardBeforeBidding],
+        my_hand: HandBeforeBidding,
+        constraints: &Constraints,
+        samples: u64,
+        seed: u64,
+    ) -> impl ParallelIterator<Item = [HandBeforeBidding; 4]> {
+        let (available_cards, slots, exclusions) =
+            HandState::unknown_cards(rules, known_cards, &my_hand, constraints);
+        let pinned = constraints.pinned.clone();
+        let locations = HandsIterator::create_with_slots(slots, Vec::new())
+            .next()
+            .unwrap();
+        (0..samples).into_par_iter().filter_map(move |sample| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(sample));
+            let mut permutation = locations.clone();
+            let shuffled = (0..MAX_SHUFFLES).any(|_| {
+                permutation.shuffle(&mut rng);
+                HandsIterator::allows(&exclusions, &permutation)
+            });
+            if !shuffled {
+                permutation = HandsIterator::construct(slots, &exclusions, &mut rng)?;
+            }
+            Some(HandState::generate_hands(
+                &my_hand,
+                &available_cards,
+                &pinned,
+                permutation,
+            ))
+        })
+    }
+
+    fn unknown_cards(
+        rules: &Rules,
+        known_cards: &[CardBeforeBidding],
+        my_hand: &HandBeforeBidding,
+        constraints: &Constraints,
+    ) -> (
+        Vec<CardBeforeBidding>,
+        [usize; 4],
+        Vec<(usize, CardLocation)>,
+    ) {
+        let mut available_cards = Deck::create_all_cards(rules);
+        available_cards.retain(|card| !known_cards.contains(card) && !my_hand.cards.contains(card));
+        let mut slots = [5, 5, 5, available_cards.len() - 15];
+        for constraint in &constraints.pinned {
+            slots[constraint.location.index()] -= 1;
+        }
+        available_cards.retain(|&card| {
+            !constraints
+                .pinned
+                .iter()
+                .any(|constraint| constraint.card == card)
+        });
+        let exclusions = constraints
+            .excluded
+            .iter()
+            .filter_map(|constraint| {
+                available_cards
+                    .iter()
+                    .position(|&card| card == constraint.card)
+                    .map(|index| (index, constraint.location))
+            })
+            .collect();
+        (available_cards, slots, exclusions)
     }
 
     fn generate_hands(
         my_hand: &HandBeforeBidding,
-        available_cards: &[CardBeforeBidding; 18],
-        permutation: [CardLocation; 18],
+        available_cards: &[CardBeforeBidding],
+        pinned: &[CardConstraint],
+        permutation: Vec<CardLocation>,
     ) -> [HandBeforeBidding; 4] {
         let mut hands = [
             HandBeforeBidding {
@@ -132,7 +238,14 @@ impl HandState {
                 cards: Vec::with_capacity(6),
             },
         ];
-        for (&location, &card) in permutation.iter().zip(available_cards) {
+        let pinned_cards = pinned
+            .iter()
+            .map(|constraint| (constraint.location, constraint.card));
+        for (location, card) in permutation
+            .into_iter()
+            .zip(available_cards.iter().copied())
+            .chain(pinned_cards)
+        {
             match location {
                 CardLocation::West => hands[Position::West.index()].cards.push(card),
                 CardLocation::North => hands[Position::North.index()].cards.push(card),
@@ -284,6 +397,13 @@ impl HandState {
         }
     }
 
+    pub(crate) fn final_bid_result(&self) -> Option<BidResultAll> {
+        match &self.phase {
+            HandPhase::Scoring { bid_result, .. } => Some(bid_result.clone()),
+            _ => None,
+        }
+    }
+
     fn update_bowers(hands: [HandBeforeBidding; 4], trump: &Suit) -> [Hand; 4] {
         hands
             .into_iter()
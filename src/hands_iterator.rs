@@ -1,49 +1,107 @@
+use std::str::FromStr;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::card::CardBeforeBidding;
+
 pub(crate) struct HandsIterator {
-    state: Option<[CardLocation; 18]>,
+    state: Option<Vec<CardLocation>>,
+    exclusions: Vec<(usize, CardLocation)>,
 }
 
 impl HandsIterator {
-    pub(crate) fn create() -> HandsIterator {
+    #[cfg(test)]
+    pub(crate) fn create(unknown_card_count: usize) -> HandsIterator {
+        HandsIterator::create_with_slots([5, 5, 5, unknown_card_count - 15], Vec::new())
+    }
+
+    pub(crate) fn create_with_slots(
+        slots: [usize; 4],
+        exclusions: Vec<(usize, CardLocation)>,
+    ) -> HandsIterator {
+        let mut state = Vec::with_capacity(slots.iter().sum());
+        for (location, count) in CardLocation::ALL.into_iter().zip(slots) {
+            state.extend(vec![location; count]);
+        }
         HandsIterator {
-            state: Some([
-                CardLocation::West,
-                CardLocation::West,
-                CardLocation::West,
-                CardLocation::West,
-                CardLocation::West,
-                CardLocation::North,
-                CardLocation::North,
-                CardLocation::North,
-                CardLocation::North,
-                CardLocation::North,
-                CardLocation::East,
-                CardLocation::East,
-                CardLocation::East,
-                CardLocation::East,
-                CardLocation::East,
-                CardLocation::Kitty,
-                CardLocation::Kitty,
-                CardLocation::Kitty,
-            ]),
+            state: Some(state),
+            exclusions,
         }
     }
-}
 
-impl Iterator for HandsIterator {
-    type Item = [CardLocation; 18];
+    pub(crate) fn allows(
+        exclusions: &[(usize, CardLocation)],
+        permutation: &[CardLocation],
+    ) -> bool {
+        exclusions
+            .iter()
+            .all(|(index, location)| permutation[*index] != *location)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut state = self.state?;
-        let result = state;
+    // Builds a permutation one card at a time, only choosing locations that
+    // leave the remaining cards a valid assignment. It's used when shuffling
+    // keeps hitting exclusions, so it doesn't need to be perfectly uniform.
+    pub(crate) fn construct(
+        slots: [usize; 4],
+        exclusions: &[(usize, CardLocation)],
+        rng: &mut impl Rng,
+    ) -> Option<Vec<CardLocation>> {
+        let mut allowed = vec![[true; 4]; slots.iter().sum()];
+        for (index, location) in exclusions {
+            allowed[*index][location.index()] = false;
+        }
+        if !HandsIterator::assignable(&allowed, &slots) {
+            return None;
+        }
+        let mut remaining = slots;
+        let mut permutation = Vec::with_capacity(allowed.len());
+        for index in 0..allowed.len() {
+            let mut choices = Vec::new();
+            for location in CardLocation::ALL {
+                if !allowed[index][location.index()] || remaining[location.index()] == 0 {
+                    continue;
+                }
+                remaining[location.index()] -= 1;
+                if HandsIterator::assignable(&allowed[index + 1..], &remaining) {
+                    choices.push(location);
+                }
+                remaining[location.index()] += 1;
+            }
+            let location = *choices
+                .choose_weighted(rng, |location| remaining[location.index()])
+                .ok()?;
+            remaining[location.index()] -= 1;
+            permutation.push(location);
+        }
+        Some(permutation)
+    }
+
+    // Hall's condition: every set of locations has room for all the cards
+    // that can't go anywhere else.
+    fn assignable(allowed: &[[bool; 4]], remaining: &[usize; 4]) -> bool {
+        (1..16).all(|locations: usize| {
+            let room: usize = (0..4)
+                .filter(|location| locations & (1 << location) != 0)
+                .map(|location| remaining[location])
+                .sum();
+            let cards = allowed
+                .iter()
+                .filter(|allowed| {
+                    (0..4).all(|location| !allowed[location] || locations & (1 << location) != 0)
+                })
+                .count();
+            cards <= room
+        })
+    }
 
-        let mut i = state.len() - 1;
+    fn advance(state: &mut [CardLocation]) -> bool {
+        let mut i = state.len().saturating_sub(1);
         let mut j = i;
         while i > 0 && state[i - 1] >= state[i] {
             i -= 1;
         }
         if i == 0 {
-            self.state = None;
-            return Some(result);
+            return false;
         }
 
         while state[j] <= state[i - 1] {
@@ -59,9 +117,24 @@ impl Iterator for HandsIterator {
             j -= 1;
         }
 
-        self.state = Some(state);
+        true
+    }
+}
+
+impl Iterator for HandsIterator {
+    type Item = Vec<CardLocation>;
 
-        Some(result)
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let state = self.state.as_mut()?;
+            let permutation = HandsIterator::allows(&self.exclusions, state).then(|| state.clone());
+            if !HandsIterator::advance(state) {
+                self.state = None;
+            }
+            if permutation.is_some() {
+                return permutation;
+            }
+        }
     }
 }
 
@@ -72,3 +145,172 @@ pub(crate) enum CardLocation {
     East,
     Kitty,
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CardConstraint {
+    pub(crate) card: CardBeforeBidding,
+    pub(crate) location: CardLocation,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Constraints {
+    pub(crate) pinned: Vec<CardConstraint>,
+    pub(crate) excluded: Vec<CardConstraint>,
+}
+
+impl CardLocation {
+    pub(crate) const ALL: [CardLocation; 4] = [
+        CardLocation::West,
+        CardLocation::North,
+        CardLocation::East,
+        CardLocation::Kitty,
+    ];
+
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            Self::West => 0,
+            Self::North => 1,
+            Self::East => 2,
+            Self::Kitty => 3,
+        }
+    }
+}
+
+impl FromStr for CardLocation {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "W" => Ok(Self::West),
+            "N" => Ok(Self::North),
+            "E" => Ok(Self::East),
+            "K" => Ok(Self::Kitty),
+            _ => Err(format!("Invalid card location: {}", name)),
+        }
+    }
+}
+
+impl FromStr for CardConstraint {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.split_once(':') {
+            Some((card, location)) => Ok(CardConstraint {
+                card: CardBeforeBidding::from_str(card)?,
+                location: CardLocation::from_str(location)?,
+            }),
+            None => Err(format!("Expected card:location, such as JD:N: {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use test_case::test_case;
+
+    #[test_case(18 => 3)]
+    #[test_case(19 => 4)]
+    #[test_case(22 => 7)]
+    #[test_case(26 => 11)]
+    fn kitty_gets_remaining_cards(unknown_card_count: usize) -> usize {
+        let first = HandsIterator::create(unknown_card_count).next().unwrap();
+        assert_eq!(unknown_card_count, first.len());
+        first
+            .iter()
+            .filter(|&&location| location == CardLocation::Kitty)
+            .count()
+    }
+
+    #[test]
+    fn slots_set_location_counts() {
+        let permutations: Vec<Vec<CardLocation>> =
+            HandsIterator::create_with_slots([1, 0, 1, 1], Vec::new()).collect();
+        assert_eq!(6, permutations.len());
+        for permutation in permutations {
+            assert_eq!(3, permutation.len());
+            assert!(!permutation.contains(&CardLocation::North));
+        }
+    }
+
+    #[test]
+    fn constructs_permutations_shuffling_rarely_finds() {
+        // Three cards can't go to West, North or East, so they fill the kitty.
+        let exclusions: Vec<(usize, CardLocation)> = [0, 7, 14]
+            .into_iter()
+            .flat_map(|index| {
+                [CardLocation::West, CardLocation::North, CardLocation::East]
+                    .map(|location| (index, location))
+            })
+            .collect();
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..20 {
+            let permutation =
+                HandsIterator::construct([5, 5, 5, 3], &exclusions, &mut rng).unwrap();
+            assert!(HandsIterator::allows(&exclusions, &permutation));
+            for location in CardLocation::ALL {
+                assert_eq!(
+                    [5, 5, 5, 3][location.index()],
+                    permutation
+                        .iter()
+                        .filter(|&&other| other == location)
+                        .count()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn construct_rejects_impossible_exclusions() {
+        let exclusions: Vec<(usize, CardLocation)> = [0, 1]
+            .into_iter()
+            .flat_map(|index| {
+                [CardLocation::West, CardLocation::North, CardLocation::East]
+                    .map(|location| (index, location))
+            })
+            .collect();
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(
+            None,
+            HandsIterator::construct([1, 1, 1, 1], &exclusions, &mut rng)
+        );
+    }
+
+    #[test]
+    fn no_unknown_cards_yields_one_deal() {
+        let permutations: Vec<Vec<CardLocation>> =
+            HandsIterator::create_with_slots([0, 0, 0, 0], Vec::new()).collect();
+        assert_eq!(vec![Vec::<CardLocation>::new()], permutations);
+    }
+
+    #[test]
+    fn exclusions_skip_permutations() {
+        let permutations: Vec<Vec<CardLocation>> = HandsIterator::create_with_slots(
+            [1, 1, 1, 0],
+            vec![(0, CardLocation::West), (1, CardLocation::North)],
+        )
+        .collect();
+        assert_eq!(3, permutations.len());
+        for permutation in permutations {
+            assert_ne!(CardLocation::West, permutation[0]);
+            assert_ne!(CardLocation::North, permutation[1]);
+        }
+    }
+
+    #[test_case("JD:N" => Ok(CardConstraint { card: CardBeforeBidding::from_str("JD").unwrap(), location: CardLocation::North }))]
+    #[test_case("AC:K" => Ok(CardConstraint { card: CardBeforeBidding::from_str("AC").unwrap(), location: CardLocation::Kitty }))]
+    #[test_case("AC:S" => Err("Invalid card location: S".to_string()))]
+    #[test_case("AC" => Err("Expected card:location, such as JD:N: AC".to_string()))]
+    fn parse_card_constraint(name: &str) -> Result<CardConstraint, String> {
+        CardConstraint::from_str(name)
+    }
+
+    #[test]
+    fn permutations_are_distinct() {
+        let permutations: Vec<Vec<CardLocation>> = HandsIterator::create(18).take(100).collect();
+        for (i, permutation) in permutations.iter().enumerate() {
+            assert!(!permutations[i + 1..].contains(permutation));
+        }
+    }
+}
@@ -0,0 +1,437 @@
+use std::fmt;
+
+use enum_iterator::IntoEnumIterator;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::CardBeforeBidding,
+    hand::{Hand, HandBeforeBidding},
+    hands_iterator::Constraints,
+    player::Player,
+    players::{advanced::AdvancedPlayer, registry::Strategy},
+    position::Position,
+    rank::Rank,
+    rules::Rules,
+    simulation::{self, Bid, Scenario},
+    suit::Suit,
+    trick_state::PlayedCard,
+};
+
+const HINT_SAMPLES: u64 = 2_000;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Hint {
+    pub(crate) advice: String,
+    pub(crate) reasons: Vec<String>,
+    pub(crate) estimates: Vec<(String, f64)>,
+}
+
+#[derive(Clone)]
+pub(crate) struct Advisor {
+    position: Position,
+    rules: Rules,
+    player: AdvancedPlayer,
+    rng: StdRng,
+}
+
+impl Hint {
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let mut lines = vec![self.advice.clone()];
+        lines.extend(self.reasons.iter().map(|reason| format!("  {}", reason)));
+        if !self.estimates.is_empty() {
+            lines.push(format!(
+                "Estimated points when the bid reaches you ({} sampled deals): {}",
+                HINT_SAMPLES,
+                self.estimates
+                    .iter()
+                    .map(|(option, points)| format!("{} {:+.2}", option, points))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        lines
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+impl Advisor {
+    pub(crate) fn create(position: Position, rules: Rules) -> Advisor {
+        Advisor::create_with_seed(position, rules, thread_rng().gen())
+    }
+
+    pub(crate) fn create_with_seed(position: Position, rules: Rules, seed: u64) -> Advisor {
+        Advisor {
+            position,
+            rules,
+            player: AdvancedPlayer::create_with_rules(position, rules),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub(crate) fn order_up(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> Hint {
+        let order_up = self.player.should_order_up(hand, dealer, trump_candidate);
+        let alone = order_up
+            && self
+                .player
+                .should_order_up_alone(hand, dealer, trump_candidate);
+        let mut reasons = describe_hand(hand, &trump_candidate.suit);
+        reasons.push(if *dealer == self.position {
+            format!("You're dealing, so {} comes to you", trump_candidate)
+        } else if *dealer == self.position.partner() {
+            format!(
+                "Your partner is dealing, so {} goes to them",
+                trump_candidate
+            )
+        } else {
+            format!(
+                "An opponent is dealing, so {} goes to them",
+                trump_candidate
+            )
+        });
+        Hint {
+            advice: match (order_up, alone) {
+                (true, true) => format!("Advanced would order up {} alone", trump_candidate),
+                (true, false) => format!("Advanced would order up {}", trump_candidate),
+                _ => "Advanced would pass".to_string(),
+            },
+            reasons,
+            estimates: self.estimate_bids(hand, dealer, trump_candidate, &[Bid::OrderUp]),
+        }
+    }
+
+    pub(crate) fn call_trump(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Hint {
+        let trump = self.player.call_trump(hand, dealer, turned_down);
+        let alone = trump.is_some_and(|trump| {
+            self.player
+                .should_call_alone(hand, dealer, &trump, turned_down)
+        });
+        let suits: Vec<Suit> = Suit::into_enum_iter()
+            .filter(|suit| *suit != turned_down.suit)
+            .collect();
+        Hint {
+            advice: match (trump, alone) {
+                (Some(trump), true) => format!("Advanced would call {} alone", trump),
+                (Some(trump), false) => format!("Advanced would call {}", trump),
+                (None, _) => "Advanced would pass".to_string(),
+            },
+            reasons: suits
+                .iter()
+                .map(|suit| format!("{}: {}", suit, describe_hand(hand, suit).join(", ")))
+                .collect(),
+            estimates: self.estimate_bids(
+                hand,
+                dealer,
+                turned_down,
+                &suits
+                    .iter()
+                    .map(|suit| Bid::Call(*suit))
+                    .collect::<Vec<Bid>>(),
+            ),
+        }
+    }
+
+    pub(crate) fn call_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> Hint {
+        let alone = self
+            .player
+            .should_call_alone(hand, dealer, trump, turned_down);
+        Hint {
+            advice: if alone {
+                format!("Advanced would call {} alone", trump)
+            } else {
+                format!("Advanced would call {} with its partner", trump)
+            },
+            reasons: describe_hand(hand, trump),
+            estimates: self.estimate_bids(hand, dealer, turned_down, &[Bid::Call(*trump)]),
+        }
+    }
+
+    pub(crate) fn defend_alone_ordered(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> Hint {
+        let defend = self
+            .player
+            .should_defend_alone_ordered(hand, dealer, trump_candidate);
+        Hint {
+            advice: defend_advice(defend),
+            reasons: describe_hand(hand, &trump_candidate.suit),
+            estimates: Vec::new(),
+        }
+    }
+
+    pub(crate) fn defend_alone_called(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> Hint {
+        let defend = self
+            .player
+            .should_defend_alone_called(hand, dealer, trump, turned_down);
+        Hint {
+            advice: defend_advice(defend),
+            reasons: describe_hand(hand, trump),
+            estimates: Vec::new(),
+        }
+    }
+
+    pub(crate) fn joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Hint {
+        let suit = self.player.choose_joker_suit(hand, dealer);
+        Hint {
+            advice: format!("Advanced would name {}", suit),
+            reasons: describe_hand(hand, &suit),
+            estimates: Vec::new(),
+        }
+    }
+
+    pub(crate) fn discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> Hint {
+        let discard = self.player.choose_discard(hand, trump);
+        Hint {
+            advice: format!("Advanced would discard {}", discard),
+            reasons: vec![
+                "It prefers to void a short suit without an ace, then drops its lowest card"
+                    .to_string(),
+            ],
+            estimates: Vec::new(),
+        }
+    }
+
+    pub(crate) fn play_card(
+        &mut self,
+        hand: &Hand,
+        bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Hint {
+        let card = self.player.play_card(hand, bid_result, cards_played);
+        let trump_left = hand
+            .cards
+            .iter()
+            .filter(|card| card.suit == bid_result.trump())
+            .count();
+        Hint {
+            advice: format!("Advanced would play {}", card),
+            reasons: vec![
+                if bid_result.caller() == self.position
+                    || bid_result.caller() == self.position.partner()
+                {
+                    "Your team called trump".to_string()
+                } else {
+                    "The opponents called trump".to_string()
+                },
+                format!("You have {} trump left", trump_left),
+            ],
+            estimates: Vec::new(),
+        }
+    }
+
+    pub(crate) fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
+        self.player.trick_end(bid_result, cards_played);
+    }
+
+    fn estimate_bids(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+        bids: &[Bid],
+    ) -> Vec<(String, f64)> {
+        let mut scenario = Scenario {
+            rules: self.rules,
+            dealer: dealer.seen_from_south(&self.position),
+            trump_candidate: *trump_candidate,
+            hand: hand.clone(),
+            bid: Bid::OrderUp,
+            go_alone: false,
+            ignore_other_bids: false,
+            strategies: [
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+            ],
+            constraints: Constraints::default(),
+            passes: Vec::new(),
+            discard: None,
+        };
+        let seed = self.rng.gen();
+        let mut estimates = Vec::new();
+        for bid in bids {
+            for go_alone in [false, true] {
+                scenario.bid = *bid;
+                scenario.go_alone = go_alone;
+                let results = simulation::simulate_sampled(&scenario, HINT_SAMPLES, seed);
+                if let Some(expected_value) = results.expected_value() {
+                    let trump = match bid {
+                        Bid::OrderUp => trump_candidate.suit,
+                        Bid::Call(trump) => *trump,
+                    };
+                    estimates.push((
+                        format!(
+                            "{} {}{}",
+                            if *bid == Bid::OrderUp {
+                                "order up"
+                            } else {
+                                "call"
+                            },
+                            trump,
+                            if go_alone { " alone" } else { "" }
+                        ),
+                        expected_value,
+                    ));
+                }
+            }
+        }
+        estimates
+    }
+}
+
+fn describe_hand(hand: &HandBeforeBidding, trump: &Suit) -> Vec<String> {
+    let trump_count = hand
+        .cards
+        .iter()
+        .filter(|card| card.is_trump(trump))
+        .count();
+    let mut description = vec![format!(
+        "{} trump{}",
+        trump_count,
+        if trump_count == 1 { "" } else { "s" }
+    )];
+    if hand.cards.iter().any(|card| card.rank == Rank::Joker) {
+        description.push("the joker".to_string());
+    }
+    if hand
+        .cards
+        .iter()
+        .any(|card| card.suit == *trump && card.rank == Rank::Jack)
+    {
+        description.push("the right bower".to_string());
+    }
+    if hand
+        .cards
+        .iter()
+        .any(|card| card.suit == trump.other_suit_of_same_color() && card.rank == Rank::Jack)
+    {
+        description.push("the left bower".to_string());
+    }
+    let off_aces = hand
+        .cards
+        .iter()
+        .filter(|card| card.rank == Rank::Ace && !card.is_trump(trump))
+        .count();
+    if off_aces > 0 {
+        description.push(format!(
+            "{} off-suit ace{}",
+            off_aces,
+            if off_aces == 1 { "" } else { "s" }
+        ));
+    }
+    description
+}
+
+fn defend_advice(defend: bool) -> String {
+    if defend {
+        "Advanced would defend alone".to_string()
+    } else {
+        "Advanced would defend with its partner".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn hand(cards: [&str; 5]) -> HandBeforeBidding {
+        HandBeforeBidding {
+            cards: cards
+                .iter()
+                .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn describes_strong_hand() {
+        assert_eq!(
+            vec![
+                "3 trumps".to_string(),
+                "the right bower".to_string(),
+                "the left bower".to_string(),
+                "1 off-suit ace".to_string()
+            ],
+            describe_hand(&hand(["JH", "JD", "AH", "AS", "9C"]), &Suit::Hearts)
+        );
+    }
+
+    #[test]
+    fn order_up_hint() {
+        let mut advisor = Advisor::create_with_seed(Position::West, Rules::default(), 7);
+        let hint = advisor.order_up(
+            &hand(["JH", "JD", "AH", "KH", "AS"]),
+            &Position::North,
+            &CardBeforeBidding::from_str("9H").unwrap(),
+        );
+        assert!(hint.advice.starts_with("Advanced would order up"));
+        assert!(hint.reasons.contains(&"4 trumps".to_string()));
+        assert_eq!(2, hint.estimates.len());
+        assert!(hint.estimates[0].1 > 1.0);
+        assert!(hint.lines().len() > hint.reasons.len());
+    }
+
+    #[test]
+    fn call_alone_hint() {
+        let mut advisor = Advisor::create_with_seed(Position::West, Rules::default(), 7);
+        let hint = advisor.call_alone(
+            &hand(["JS", "JC", "AS", "KS", "AD"]),
+            &Position::North,
+            &Suit::Spades,
+            &CardBeforeBidding::from_str("9H").unwrap(),
+        );
+        assert!(hint.advice.starts_with("Advanced would call ♠"));
+        assert!(hint.reasons.contains(&"4 trumps".to_string()));
+        assert_eq!(
+            vec!["call ♠", "call ♠ alone"],
+            hint.estimates
+                .iter()
+                .map(|(option, _)| option.as_str())
+                .collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn call_trump_hint() {
+        let mut advisor = Advisor::create(Position::South, Rules::default());
+        let hint = advisor.call_trump(
+            &hand(["9C", "10C", "QD", "KS", "9S"]),
+            &Position::North,
+            &CardBeforeBidding::from_str("9H").unwrap(),
+        );
+        assert_eq!("Advanced would pass", hint.advice);
+        assert_eq!(3, hint.reasons.len());
+    }
+}
@@ -0,0 +1,377 @@
+use rayon::prelude::ParallelIterator;
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    deck::Deck,
+    hand::{Hand, HandBeforeBidding},
+    hand_state::HandState,
+    hands_iterator::{CardConstraint, CardLocation, Constraints},
+    player::Player,
+    players::registry::Strategy,
+    position::Position,
+    rank::Rank,
+    rules::Rules,
+    simulation::{self, HandResult, SimulationResults},
+};
+
+#[derive(Clone, Debug)]
+pub(crate) struct LeadScenario {
+    pub(crate) rules: Rules,
+    pub(crate) bid_result: BidResultCalled,
+    pub(crate) trump_candidate: CardBeforeBidding,
+    pub(crate) ordered_up: bool,
+    pub(crate) leader: Position,
+    pub(crate) hand: HandBeforeBidding,
+    pub(crate) strategies: [Strategy; 4],
+}
+
+impl LeadScenario {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        simulation::validate_strategies(&self.strategies)?;
+        let all_cards = Deck::create_all_cards(&self.rules);
+        for (index, card) in self
+            .hand
+            .cards
+            .iter()
+            .chain(std::iter::once(&self.trump_candidate))
+            .enumerate()
+        {
+            if !all_cards.contains(card) {
+                return Err(format!("{} is not in the deck for these rules", card));
+            }
+            if self.hand.cards[..index.min(self.hand.cards.len())].contains(card) {
+                return Err(format!("{} appears more than once", card));
+            }
+        }
+        if self.hand.cards.len() != 5 {
+            return Err(format!(
+                "The hand must have 5 cards, not {}",
+                self.hand.cards.len()
+            ));
+        }
+        if self.dealer().next_position_playing(&self.bid_result) != self.leader {
+            return Err(format!(
+                "{:?} can't lead, their partner is playing alone",
+                self.leader
+            ));
+        }
+        let trump = self.bid_result.trump();
+        if self.trump_candidate.rank != Rank::Joker {
+            if self.ordered_up && trump != self.trump_candidate.suit {
+                return Err(format!(
+                    "Ordering up {} makes {} trump",
+                    self.trump_candidate, self.trump_candidate.suit
+                ));
+            }
+            if !self.ordered_up && trump == self.trump_candidate.suit {
+                return Err(format!(
+                    "{} can't be trump after {} was turned down",
+                    trump, self.trump_candidate
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn dealer(&self) -> Position {
+        self.leader.partner().next_position_bidding()
+    }
+
+    fn known_cards(&self) -> Vec<CardBeforeBidding> {
+        if self.ordered_up {
+            Vec::new()
+        } else {
+            vec![self.trump_candidate]
+        }
+    }
+
+    fn constraints(&self) -> Constraints {
+        if !self.ordered_up {
+            return Constraints::default();
+        }
+        let location = match self.dealer() {
+            Position::South => self.leader,
+            dealer => dealer,
+        };
+        Constraints {
+            pinned: vec![CardConstraint {
+                card: self.trump_candidate,
+                location: match location {
+                    Position::West => CardLocation::West,
+                    Position::North => CardLocation::North,
+                    _ => CardLocation::East,
+                },
+            }],
+            excluded: Vec::new(),
+        }
+    }
+
+    fn matches_bid(&self, hands: &[HandBeforeBidding; 4]) -> bool {
+        let caller = self.bid_result.caller();
+        let dealer = self.dealer();
+        if caller == self.leader || self.ordered_up && caller == dealer {
+            return true;
+        }
+        let mut trump_candidate = self.trump_candidate;
+        if trump_candidate.rank == Rank::Joker {
+            if !self.ordered_up {
+                return true;
+            }
+            trump_candidate.suit = self.bid_result.trump();
+        }
+        let hand = &hands[caller.index()];
+        let mut player = self.strategies[caller.index()].create(caller, self.rules);
+        let alone = !matches!(self.bid_result, BidResultCalled::Called { .. });
+        if self.ordered_up {
+            player.should_order_up(hand, &dealer, &trump_candidate)
+                && player.should_order_up_alone(hand, &dealer, &trump_candidate) == alone
+        } else {
+            let trump = self.bid_result.trump();
+            player.call_trump(hand, &dealer, &trump_candidate) == Some(trump)
+                && player.should_call_alone(hand, &dealer, &trump, &trump_candidate) == alone
+        }
+    }
+
+    fn leads(&self) -> Vec<Card> {
+        HandBeforeBidding::update_bowers(self.hand.clone(), &self.bid_result.trump()).cards
+    }
+
+    fn run_lead(&self, mut hands: [HandBeforeBidding; 4], lead: Card) -> HandResult {
+        hands.swap(Position::South.index(), self.leader.index());
+        if !self.matches_bid(&hands) {
+            return HandResult::InconsistentBidding;
+        }
+        let hands: [Hand; 4] =
+            hands.map(|hand| HandBeforeBidding::update_bowers(hand, &self.bid_result.trump()));
+        let mut players = [
+            Position::North,
+            Position::East,
+            Position::South,
+            Position::West,
+        ]
+        .map(|position| self.strategies[position.index()].create(position, self.rules));
+        let mut hand_state =
+            HandState::create_with_lead(self.bid_result.clone(), self.leader, hands, lead);
+        loop {
+            if let Some((winner, score)) = hand_state.step(&mut players) {
+                return if winner.team_index() == self.leader.team_index() {
+                    HandResult::ExpectedBidResult { score: score as i8 }
+                } else {
+                    HandResult::ExpectedBidResult {
+                        score: -(score as i8),
+                    }
+                };
+            }
+        }
+    }
+
+    fn tally(
+        &self,
+        deals: impl ParallelIterator<Item = [HandBeforeBidding; 4]>,
+    ) -> Vec<(Card, SimulationResults)> {
+        let leads = self.leads();
+        let empty = || vec![SimulationResults::default(); leads.len()];
+        deals
+            .fold(empty, |mut results, hands| {
+                for (index, lead) in leads.iter().enumerate() {
+                    let result = self.run_lead(hands.clone(), *lead);
+                    results[index] = std::mem::take(&mut results[index]).add(result);
+                }
+                results
+            })
+            .reduce(empty, |a, b| {
+                a.into_iter().zip(b).map(|(a, b)| a.combine(b)).collect()
+            })
+            .into_iter()
+            .enumerate()
+            .map(|(index, results)| (leads[index], results))
+            .collect()
+    }
+}
+
+pub(crate) fn evaluate_leads(scenario: &LeadScenario) -> Vec<(Card, SimulationResults)> {
+    scenario.tally(HandState::deals(
+        &scenario.rules,
+        &scenario.known_cards(),
+        scenario.hand.clone(),
+        &scenario.constraints(),
+    ))
+}
+
+pub(crate) fn evaluate_leads_sampled(
+    scenario: &LeadScenario,
+    samples: u64,
+    seed: u64,
+) -> Vec<(Card, SimulationResults)> {
+    scenario.tally(HandState::sampled_deals(
+        &scenario.rules,
+        &scenario.known_cards(),
+        scenario.hand.clone(),
+        &scenario.constraints(),
+        samples,
+        seed,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suit::Suit;
+    use std::str::FromStr;
+
+    fn scenario(bid_result: BidResultCalled, leader: Position, hand: [&str; 5]) -> LeadScenario {
+        LeadScenario {
+            rules: Rules::default(),
+            bid_result,
+            trump_candidate: CardBeforeBidding::from_str("9H").unwrap(),
+            ordered_up: true,
+            leader,
+            hand: HandBeforeBidding {
+                cards: hand
+                    .iter()
+                    .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                    .collect(),
+            },
+            strategies: [
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+            ],
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let called_alone = BidResultCalled::CalledAlone {
+            trump: Suit::Hearts,
+            caller: Position::North,
+        };
+        assert_eq!(
+            Ok(()),
+            scenario(
+                called_alone.clone(),
+                Position::West,
+                ["JH", "JD", "AH", "KH", "AS"]
+            )
+            .validate()
+        );
+        assert_eq!(
+            Err("South can't lead, their partner is playing alone".to_string()),
+            scenario(
+                called_alone,
+                Position::South,
+                ["JH", "JD", "AH", "KH", "AS"]
+            )
+            .validate()
+        );
+    }
+
+    #[test]
+    fn validate_contract() {
+        let called = BidResultCalled::Called {
+            trump: Suit::Hearts,
+            caller: Position::North,
+        };
+        let mut scenario = scenario(called, Position::West, ["JH", "JD", "AH", "KH", "AS"]);
+        scenario.trump_candidate = CardBeforeBidding::from_str("9S").unwrap();
+        assert_eq!(
+            Err(format!(
+                "Ordering up {} makes {} trump",
+                scenario.trump_candidate,
+                Suit::Spades
+            )),
+            scenario.validate()
+        );
+        scenario.ordered_up = false;
+        assert_eq!(Ok(()), scenario.validate());
+        scenario.trump_candidate = CardBeforeBidding::from_str("9H").unwrap();
+        assert_eq!(
+            Err(format!(
+                "{} can't be trump after {} was turned down",
+                Suit::Hearts,
+                scenario.trump_candidate
+            )),
+            scenario.validate()
+        );
+        scenario.trump_candidate = CardBeforeBidding::from_str("AS").unwrap();
+        assert_eq!(
+            Err(format!(
+                "{} appears more than once",
+                scenario.trump_candidate
+            )),
+            scenario.validate()
+        );
+    }
+
+    #[test]
+    fn pins_the_card_ordered_up_to_the_dealer() {
+        let called = BidResultCalled::Called {
+            trump: Suit::Hearts,
+            caller: Position::North,
+        };
+        let mut scenario = scenario(called, Position::West, ["JH", "JD", "AH", "KH", "AS"]);
+        assert_eq!(
+            vec![CardConstraint {
+                card: scenario.trump_candidate,
+                location: CardLocation::West,
+            }],
+            scenario.constraints().pinned
+        );
+        assert!(scenario.known_cards().is_empty());
+        scenario.leader = Position::North;
+        assert_eq!(
+            CardLocation::West,
+            scenario.constraints().pinned[0].location
+        );
+        scenario.ordered_up = false;
+        assert_eq!(Constraints::default(), scenario.constraints());
+        assert_eq!(vec![scenario.trump_candidate], scenario.known_cards());
+    }
+
+    #[test]
+    fn deals_match_the_callers_bid() {
+        let scenario = scenario(
+            BidResultCalled::Called {
+                trump: Suit::Hearts,
+                caller: Position::North,
+            },
+            Position::West,
+            ["9C", "10C", "QS", "KS", "10D"],
+        );
+        let results = evaluate_leads_sampled(&scenario, 200, 9);
+        let (_, results) = &results[0];
+        let inconsistent = results.result_counts[&HandResult::InconsistentBidding];
+        assert!(inconsistent > 0);
+        assert_eq!(200, results.bid_count() + inconsistent);
+    }
+
+    #[test]
+    fn leading_the_right_bower_beats_leading_a_loser() {
+        let scenario = scenario(
+            BidResultCalled::Called {
+                trump: Suit::Hearts,
+                caller: Position::South,
+            },
+            Position::South,
+            ["JH", "JD", "AH", "9C", "9S"],
+        );
+        let results = evaluate_leads_sampled(&scenario, 200, 9);
+        assert_eq!(5, results.len());
+        assert!(results
+            .iter()
+            .all(|(_, results)| results.total_count == 200));
+        let expected_value = |name: &str| {
+            let card = CardBeforeBidding::from_str(name).unwrap();
+            results
+                .iter()
+                .find(|(lead, _)| CardBeforeBidding::from(*lead) == card)
+                .unwrap()
+                .1
+                .expected_value()
+                .unwrap()
+        };
+        assert!(expected_value("JH") > expected_value("9S"));
+    }
+}
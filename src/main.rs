@@ -14,224 +14,295 @@
 #![warn(unused_tuple_struct_fields)]
 #![warn(variant_size_differences)]
 
-use args::{Commands, EuchreArgs, SimulateHandArgs};
-use bid_result::BidResultCalled;
+use args::{
+    AnalyzeArgs, Commands, DuplicateArgs, EuchreArgs, EvaluateDiscardsArgs, EvaluateLeadArgs,
+    GenerateBidTableArgs, PlayGameArgs, PlayMatchArgs, PlayTwoHandedArgs, RatingsArgs, ServeArgs,
+    ServerArgs, ShowBidTableArgs, SimulateBatchArgs, SimulateHandArgs, TournamentArgs,
+};
+use bid_euchre::BidEuchreGameState;
+use bid_table::BidTable;
+use cache::Cache;
+use card::CardBeforeBidding;
 use clap::Parser;
+use game_record::{GameRecord, Recorder};
 use game_state::GameState;
 use hand::HandBeforeBidding;
-use hand_state::HandState;
+use lead::LeadScenario;
 use log::LevelFilter;
 use logger::Logger;
+use match_play::{FirstDealer, MatchResults};
 use num_format::{Locale, ToFormattedString};
-use players::{
-    advanced::AdvancedPlayer, preprogrammed_bidder::PreprogrammedBidder, wrapper::Wrapper,
-};
+use players::{advanced::AdvancedPlayer, recording::RecordingPlayer, wrapper::Wrapper};
 use position::Position;
-use rayon::prelude::ParallelIterator;
-use std::collections::HashMap;
+use rand::{thread_rng, Rng};
+use ratings::Ratings;
+use report::{OutputFormat, Report};
+use rules::Rules;
+use server::Server;
+use simulation::{HandResult, Scenario, SimulationResults};
+use std::{cmp::Ordering, net::TcpListener, path::Path, time::Duration};
+use two_handed::{TwoHandedGameState, SEATS};
 
+mod analysis;
+mod api;
 mod args;
+mod auction_state;
+mod batch;
+mod bid_euchre;
 mod bid_result;
 mod bid_state;
+mod bid_table;
+mod cache;
 mod card;
 mod deck;
+mod duplicate;
+mod game_record;
 mod game_state;
 mod hand;
 mod hand_state;
 mod hands_iterator;
+mod hint;
+mod lead;
 mod logger;
+mod match_play;
 mod player;
 mod players;
 mod position;
+mod protocol;
 mod rank;
 mod rank_with_bowers;
+mod ratings;
+mod report;
+mod rules;
+mod server;
+mod shared_files;
+mod simulation;
 mod suit;
+mod tournament;
 mod trick_state;
+mod tui;
+mod two_handed;
 
 static LOGGER: Logger = Logger;
 
-#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
-enum HandResult {
-    DifferentBidResult,
-    ExpectedBidResult { score: i8 },
-}
-
 fn main() {
     let args = EuchreArgs::parse();
     match args.command {
-        Commands::PlayGame => simulate_full_game(),
+        Commands::Analyze(args) => analyze_game(args),
+        Commands::Duplicate(args) => play_duplicate(args),
+        Commands::EvaluateDiscards(args) => evaluate_discards(args),
+        Commands::EvaluateLead(args) => evaluate_lead(args),
+        Commands::GenerateBidTable(args) => generate_bid_table(args),
+        Commands::PlayGame(args) => simulate_full_game(args),
+        Commands::PlayTwoHanded(args) => simulate_two_handed_game(args),
+        Commands::PlayBidEuchre => simulate_bid_euchre_game(),
+        Commands::PlayMatch(args) => play_match(args),
+        Commands::Ratings(args) => show_ratings(args),
+        Commands::Serve(args) => serve(args),
+        Commands::Server(args) => run_server(args),
+        Commands::SimulateBatch(args) => simulate_batch(args),
         Commands::SimulateHand(args) => simulate_hand(args),
+        Commands::ShowBidTable(args) => show_bid_table(args),
+        Commands::Tournament(args) => play_tournament(args),
+        Commands::Tui(args) => tui::run(
+            args.seats.strategies(),
+            Rules::from(&args.rules),
+            args.record.as_deref(),
+        )
+        .unwrap_or_else(|error| panic!("Terminal error: {}", error)),
     }
 }
 
 fn simulate_hand(args: SimulateHandArgs) {
-    let (bidder, expected_bid_result) = get_bidding_info(&args);
-    let hand_states = HandState::create_with_scenario(
-        args.dealer,
-        args.trump_candidate,
-        HandBeforeBidding { cards: args.hand },
-    );
-    let (total_count, result_counts) = hand_states
-        .map_with(
-            (bidder, expected_bid_result),
-            |(bidder, expected_bid_result), mut hand_state| {
-                run_permutation(
-                    bidder,
-                    expected_bid_result,
-                    &mut hand_state,
-                    args.ignore_other_bids,
-                )
-            },
-        )
-        .fold(
-            || (0, HashMap::<HandResult, u64>::new()),
-            |(count, mut result_counts), hand_result| {
-                add_to_results(&mut result_counts, hand_result, 1);
-                (count + 1, result_counts)
-            },
-        )
-        .reduce(
-            || (0, HashMap::<HandResult, u64>::new()),
-            |(count_1, mut result_counts_1), (count_2, result_counts_2)| {
-                for (result, count) in result_counts_2.into_iter() {
-                    add_to_results(&mut result_counts_1, result, count);
-                }
-                (count_1 + count_2, result_counts_1)
-            },
-        );
-    tally_results(result_counts, total_count);
+    let scenario = Scenario::from(&args);
+    if let Err(error) = scenario.validate() {
+        panic!("{}", error);
+    }
+    let cache = match (args.no_cache, args.cache_dir) {
+        (true, _) => None,
+        (false, Some(directory)) => Some(Cache::create(directory)),
+        (false, None) => Cache::default_directory().map(Cache::create),
+    };
+    let results = match cache {
+        Some(cache) => cache.simulate(&scenario),
+        None => simulation::simulate(&scenario),
+    };
+    match args.format {
+        OutputFormat::Text => tally_results(results),
+        OutputFormat::Json => println!("{}", Report::create(&scenario, &results).to_json()),
+        OutputFormat::Csv => println!("{}", Report::create(&scenario, &results).to_csv()),
+    }
 }
 
-fn get_bidding_info(args: &SimulateHandArgs) -> (PreprogrammedBidder, BidResultCalled) {
-    let (bidder, bid_result) = if args.order_up {
-        if args.go_alone {
-            (
-                PreprogrammedBidder::orders_up_alone(),
-                BidResultCalled::CalledAlone {
-                    trump: args.trump_candidate.suit,
-                    caller: Position::South,
-                },
-            )
-        } else {
-            (
-                PreprogrammedBidder::orders_up(),
-                BidResultCalled::Called {
-                    trump: args.trump_candidate.suit,
-                    caller: Position::South,
-                },
-            )
-        }
-    } else if let Some(trump) = args.call_suit {
-        if args.go_alone {
-            (
-                PreprogrammedBidder::calls_alone(trump),
-                BidResultCalled::CalledAlone {
-                    trump,
-                    caller: Position::South,
-                },
-            )
-        } else {
-            (
-                PreprogrammedBidder::calls(trump),
-                BidResultCalled::Called {
-                    trump,
-                    caller: Position::South,
-                },
-            )
-        }
-    } else {
-        panic!("You must either order the trump candidate up (--order-up) or call a trump suit (--call-suit {{C|D|H|S}}");
+fn simulate_batch(args: SimulateBatchArgs) {
+    let scenarios = batch::load_scenarios(
+        &args.scenario_file,
+        Rules::from(&args.rules),
+        args.seats.strategies(),
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let results = batch::simulate_all(&scenarios, args.samples, seed);
+    let reports: Vec<Report> = scenarios
+        .iter()
+        .zip(&results)
+        .map(|(scenario, results)| Report::create(scenario, results))
+        .collect();
+    let output = match args.format {
+        OutputFormat::Text => results
+            .iter()
+            .enumerate()
+            .map(|(index, results)| match results.expected_value() {
+                Some(expected_value) => format!(
+                    "Scenario {}: expected value {:.2} over {} deals",
+                    index + 1,
+                    expected_value,
+                    results.bid_count().to_formatted_string(&Locale::en)
+                ),
+                None => format!("Scenario {}: no consistent deals", index + 1),
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+        OutputFormat::Json => report::to_json(&reports),
+        OutputFormat::Csv => report::to_csv(&reports),
     };
-    (bidder, bid_result)
+    match args.output {
+        Some(path) => std::fs::write(&path, output + "\n")
+            .unwrap_or_else(|error| panic!("Couldn't write {}: {}", path.display(), error)),
+        None => println!("{}", output),
+    }
 }
 
-fn run_permutation(
-    bidder: &mut PreprogrammedBidder,
-    expected_bid_result: &BidResultCalled,
-    hand_state: &mut HandState,
-    ignore_other_bids: bool,
-) -> HandResult {
-    let mut players = [
-        if ignore_other_bids {
-            Wrapper::create_separate_bidder(
-                Box::new(PreprogrammedBidder::does_nothing()),
-                Box::new(AdvancedPlayer::create(Position::North)),
-            )
-        } else {
-            Wrapper::create_single_player(Box::new(AdvancedPlayer::create(Position::North)))
-        },
-        if ignore_other_bids {
-            Wrapper::create_separate_bidder(
-                Box::new(PreprogrammedBidder::does_nothing()),
-                Box::new(AdvancedPlayer::create(Position::East)),
-            )
-        } else {
-            Wrapper::create_single_player(Box::new(AdvancedPlayer::create(Position::East)))
-        },
-        Wrapper::create_separate_bidder(
-            Box::new(bidder.clone()),
-            Box::new(AdvancedPlayer::create(Position::South)),
-        ),
-        if ignore_other_bids {
-            Wrapper::create_separate_bidder(
-                Box::new(PreprogrammedBidder::does_nothing()),
-                Box::new(AdvancedPlayer::create(Position::West)),
-            )
-        } else {
-            Wrapper::create_single_player(Box::new(AdvancedPlayer::create(Position::West)))
-        },
-    ];
-    match hand_state.finish_bidding(&mut players) {
-        Some(bid_result) if expected_bid_result.is_equivalent(&bid_result) => (),
-        _ => return HandResult::DifferentBidResult,
-    };
-    loop {
-        if let Some((winner, score)) = hand_state.step(&mut players) {
-            return if winner == Position::South || winner == Position::South.partner() {
-                HandResult::ExpectedBidResult { score: score as i8 }
-            } else {
-                HandResult::ExpectedBidResult {
-                    score: -(score as i8),
-                }
+fn evaluate_discards(args: EvaluateDiscardsArgs) {
+    let scenario = Scenario::from(&args);
+    if let Err(error) = scenario.validate() {
+        panic!("{}", error);
+    }
+    let seed = thread_rng().gen();
+    let mut evaluations: Vec<(CardBeforeBidding, SimulationResults)> = scenario
+        .discard_options()
+        .iter()
+        .map(|option| {
+            let results = match args.samples {
+                Some(samples) => simulation::simulate_sampled(option, samples, seed),
+                None => simulation::simulate(option),
             };
+            (option.discard.unwrap(), results)
+        })
+        .collect();
+    evaluations.sort_by(|(_, a), (_, b)| compare_expected_values(b, a));
+    println!("Expected value by discard:");
+    for (card, results) in evaluations {
+        match results.expected_value() {
+            Some(expected_value) => println!(
+                "Discard {}: {:.2} ({} deals)",
+                card,
+                expected_value,
+                results.bid_count().to_formatted_string(&Locale::en)
+            ),
+            None => println!("Discard {}: no consistent deals", card),
         }
     }
 }
 
-fn add_to_results(
-    result_counts: &mut HashMap<HandResult, u64>,
-    hand_result: HandResult,
-    count: u64,
-) {
-    match result_counts.get_mut(&hand_result) {
-        Some(result_count) => {
-            *result_count += count;
+fn evaluate_lead(args: EvaluateLeadArgs) {
+    let scenario = LeadScenario::try_from(&args).unwrap_or_else(|error| panic!("{}", error));
+    if let Err(error) = scenario.validate() {
+        panic!("{}", error);
+    }
+    let mut evaluations = match args.samples {
+        Some(samples) => lead::evaluate_leads_sampled(&scenario, samples, thread_rng().gen()),
+        None => lead::evaluate_leads(&scenario),
+    };
+    evaluations.sort_by(|(_, a), (_, b)| compare_expected_values(b, a));
+    println!("Expected value by opening lead:");
+    for (card, results) in evaluations {
+        match results.expected_value() {
+            Some(expected_value) => println!(
+                "Lead {}: {:.2} ({} deals)",
+                card,
+                expected_value,
+                results.bid_count().to_formatted_string(&Locale::en)
+            ),
+            None => println!("Lead {}: no consistent deals", card),
         }
-        None => {
-            if let Some(old_value) = result_counts.insert(hand_result, count) {
-                panic!(
-                    "Got an old value after get_mut returned None: {}",
-                    old_value
-                )
-            }
+    }
+}
+
+fn compare_expected_values(a: &SimulationResults, b: &SimulationResults) -> Ordering {
+    let value = |results: &SimulationResults| results.expected_value().unwrap_or(f64::NEG_INFINITY);
+    value(a).total_cmp(&value(b))
+}
+
+fn generate_bid_table(args: GenerateBidTableArgs) {
+    let strategies = args.seats.strategies();
+    if let Err(error) = simulation::validate_strategies(&strategies) {
+        panic!("{}", error);
+    }
+    let rules = Rules::from(&args.rules);
+    let keys = bid_table::all_keys(&rules);
+    println!(
+        "Simulating {} hands with {} samples per bid",
+        keys.len().to_formatted_string(&Locale::en),
+        args.samples.to_formatted_string(&Locale::en)
+    );
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let table = BidTable::generate(rules, &strategies, &keys, args.samples, seed);
+    table
+        .save(&args.output)
+        .unwrap_or_else(|error| panic!("Couldn't write {}: {}", args.output.display(), error));
+    println!(
+        "Wrote {} entries to {}",
+        table.len().to_formatted_string(&Locale::en),
+        args.output.display()
+    );
+}
+
+fn show_bid_table(args: ShowBidTableArgs) {
+    let table = BidTable::load(&args.table)
+        .unwrap_or_else(|error| panic!("Couldn't read {}: {}", args.table.display(), error));
+    let (trump_candidate, dealer) = match (args.trump_candidate, args.dealer) {
+        (Some(trump_candidate), Some(dealer)) => (trump_candidate, dealer),
+        _ => {
+            println!("{}", table.chart().join("\n"));
+            return;
         }
     };
+    let hand = HandBeforeBidding { cards: args.hand };
+    let values = table
+        .lookup(&hand, &trump_candidate, dealer, Position::South)
+        .unwrap_or_else(|| panic!("The table has no entry for that hand"));
+    println!(
+        "Expected values over {} samples per bid:",
+        table.samples().to_formatted_string(&Locale::en)
+    );
+    println!("Order up: {:.2}", values.order_up);
+    println!("Order up alone: {:.2}", values.order_up_alone);
+    for call in values.calls {
+        println!("Call {}: {:.2}", call.trump, call.call);
+        println!("Call {} alone: {:.2}", call.trump, call.call_alone);
+    }
 }
 
-fn tally_results(result_counts: HashMap<HandResult, u64>, total_count: u64) {
-    let mut results: Vec<&HandResult> = result_counts.keys().collect();
-    results.sort();
-    let mut expected_value: i64 = 0;
-    let mut total_bid_count: u64 = 0;
+fn tally_results(results: SimulationResults) {
+    let total_count = results.total_count;
+    let mut hand_results: Vec<&HandResult> = results.result_counts.keys().collect();
+    hand_results.sort();
     println!("Results:");
-    for result in results {
-        let count = result_counts.get(result).unwrap();
+    for result in hand_results {
+        let count = results.result_counts.get(result).unwrap();
         match result {
+            HandResult::InconsistentBidding => {
+                print_score_line(
+                    "The other players wouldn't have bid that way",
+                    count,
+                    &total_count,
+                );
+            }
             HandResult::DifferentBidResult => {
                 print_score_line("You didn't get to bid", count, &total_count);
             }
             HandResult::ExpectedBidResult { score } => {
-                total_bid_count += count;
                 match score {
                     -4 => {
                         print_score_line(
@@ -260,14 +331,16 @@ fn tally_results(result_counts: HashMap<HandResult, u64>, total_count: u64) {
                         );
                     }
                 };
-                expected_value += (*score as i64) * (*count as i64);
             }
         }
     }
-    println!(
-        "Expected value when you're able to bid: {:.2}",
-        (expected_value as f64) / (total_bid_count as f64)
-    )
+    match results.expected_value() {
+        Some(expected_value) => println!(
+            "Expected value when you're able to bid: {:.2}",
+            expected_value
+        ),
+        None => println!("Expected value when you're able to bid: no consistent deals"),
+    }
 }
 
 fn print_score_line(description: &str, count: &u64, total_count: &u64) {
@@ -279,7 +352,98 @@ fn print_score_line(description: &str, count: &u64, total_count: &u64) {
     )
 }
 
-fn simulate_full_game() {
+fn simulate_full_game(args: PlayGameArgs) {
+    log::set_logger(&LOGGER)
+        .map(|()| log::set_max_level(LevelFilter::Info))
+        .unwrap_or_else(|_| println!("Logging initialization failed!"));
+    let rules = Rules::from(&args.rules);
+    let recorder = Recorder::create(rules);
+    let [north, east, south, west] = args.seats.strategies();
+    let players = [
+        (north, Position::North),
+        (east, Position::East),
+        (south, Position::South),
+        (west, Position::West),
+    ]
+    .map(|(strategy, position)| {
+        Wrapper::create_single_player(Box::new(RecordingPlayer::create(
+            position,
+            strategy.create_player(position, rules),
+            recorder.clone(),
+        )))
+    });
+    let mut game_state = GameState::create(players, rules, Position::South);
+    loop {
+        recorder.observe(&game_state);
+        if let Some(result) = game_state.step() {
+            println!("{}", result);
+            break;
+        }
+    }
+    if let Some(record) = args.record {
+        recorder
+            .save(&record)
+            .unwrap_or_else(|error| panic!("Couldn't save {}: {}", record.display(), error));
+        println!("Recorded the game in {}", record.display());
+    }
+}
+
+fn analyze_game(args: AnalyzeArgs) {
+    let record = GameRecord::load(&args.game_file)
+        .unwrap_or_else(|error| panic!("Couldn't load {}: {}", args.game_file.display(), error));
+    let costs = analysis::analyze(&record).unwrap_or_else(|error| panic!("{}", error));
+    println!("Costliest decisions:");
+    for cost in costs.iter().filter(|cost| cost.cost > 0.0).take(args.top) {
+        println!(
+            "Hand {}, {:?}: chose to {}, {} was worth {:.2} more points",
+            cost.hand, cost.player, cost.choice, cost.best, cost.cost
+        );
+    }
+    println!("Points lost by player:");
+    for position in [
+        Position::North,
+        Position::East,
+        Position::South,
+        Position::West,
+    ] {
+        let (count, total) = costs
+            .iter()
+            .filter(|cost| cost.player == position && cost.cost > 0.0)
+            .fold((0, 0.0), |(count, total), cost| {
+                (count + 1, total + cost.cost)
+            });
+        println!("{:?}: {:.2} in {} decisions", position, total, count);
+    }
+    println!("Points lost by hand:");
+    for hand in 1..=record.hands.len() {
+        let total: f64 = costs
+            .iter()
+            .filter(|cost| cost.hand == hand)
+            .map(|cost| cost.cost)
+            .sum();
+        println!("Hand {}: {:.2}", hand, total);
+    }
+}
+
+fn simulate_two_handed_game(args: PlayTwoHandedArgs) {
+    log::set_logger(&LOGGER)
+        .map(|()| log::set_max_level(LevelFilter::Info))
+        .unwrap_or_else(|_| println!("Logging initialization failed!"));
+    let rules = Rules::from(&args.rules);
+    let players = [
+        AdvancedPlayer::create_with_rules(SEATS[0], rules),
+        AdvancedPlayer::create_with_rules(SEATS[1], rules),
+    ];
+    let mut game_state = TwoHandedGameState::create(players, rules);
+    loop {
+        if let Some(result) = game_state.step() {
+            println!("{}", result);
+            break;
+        }
+    }
+}
+
+fn simulate_bid_euchre_game() {
     log::set_logger(&LOGGER)
         .map(|()| log::set_max_level(LevelFilter::Info))
         .unwrap_or_else(|_| println!("Logging initialization failed!"));
@@ -289,7 +453,7 @@ fn simulate_full_game() {
         AdvancedPlayer::create(Position::South),
         AdvancedPlayer::create(Position::West),
     ];
-    let mut game_state = GameState::create(players);
+    let mut game_state = BidEuchreGameState::create(players);
     loop {
         if let Some(result) = game_state.step() {
             println!("{}", result);
@@ -297,3 +461,188 @@ fn simulate_full_game() {
         }
     }
 }
+
+fn play_match(args: PlayMatchArgs) {
+    let results = match_play::play_match(Rules::from(&args.rules), args.games, args.first_dealer);
+    println!(
+        "Played {} games ({} hands)",
+        results.games.to_formatted_string(&Locale::en),
+        results.hands_played.to_formatted_string(&Locale::en)
+    );
+    print_team_results("North/South", &results, 0);
+    print_team_results("East/West", &results, 1);
+    println!(
+        "Average margin (North/South - East/West): {:.2}",
+        results.average_margin()
+    );
+}
+
+fn print_team_results(team_name: &str, results: &MatchResults, team: usize) {
+    let (low, high) = results.win_rate_confidence_interval(team);
+    println!(
+        "{} won {} games ({:.2}%, 95% CI {:.2}%-{:.2}%), went alone in {:.2}% of hands, and were euchred in {:.2}% of hands",
+        team_name,
+        results.wins[team].to_formatted_string(&Locale::en),
+        results.win_rate(team) * 100f64,
+        low * 100f64,
+        high * 100f64,
+        results.loner_rate(team) * 100f64,
+        results.euchre_rate(team) * 100f64
+    )
+}
+
+fn play_tournament(args: TournamentArgs) {
+    for (index, strategy) in args.players.iter().enumerate() {
+        if args.players[..index].contains(strategy) {
+            panic!("{} is listed more than once", strategy);
+        }
+    }
+    let (standings, pairing_results) =
+        tournament::play_tournament(&args.players, Rules::from(&args.rules), args.boards);
+    println!("Standings:");
+    println!(
+        "{:<12} {:>8} {:>8} {:>8} {:>12}",
+        "Strategy", "Games", "Wins", "Win %", "Points/hand"
+    );
+    for standing in standings {
+        println!(
+            "{:<12} {:>8} {:>8} {:>7.2}% {:>12.3}",
+            standing.strategy.to_string(),
+            standing.games.to_formatted_string(&Locale::en),
+            standing.wins.to_formatted_string(&Locale::en),
+            standing.win_rate() * 100f64,
+            standing.points_per_hand()
+        );
+    }
+    println!("Pairings:");
+    for pairing_result in &pairing_results {
+        println!(
+            "{} vs {}: {:.2}%-{:.2}% over {} duplicate boards (z = {:.2}, p = {:.4})",
+            pairing_result.strategies[0],
+            pairing_result.strategies[1],
+            pairing_result.win_rate(0) * 100f64,
+            pairing_result.win_rate(1) * 100f64,
+            pairing_result.boards.to_formatted_string(&Locale::en),
+            pairing_result.z_score(),
+            pairing_result.p_value()
+        );
+    }
+    if let Some(ratings_file) = args.ratings_file {
+        let mut ratings = load_ratings(&ratings_file);
+        for pairing_result in pairing_results {
+            let [first, second] = pairing_result
+                .strategies
+                .map(|strategy| strategy.to_string());
+            ratings.record_results([[&first, &first], [&second, &second]], pairing_result.wins);
+        }
+        ratings
+            .save(&ratings_file)
+            .unwrap_or_else(|error| panic!("Couldn't save {}: {}", ratings_file.display(), error));
+        println!("Updated ratings in {}", ratings_file.display());
+    }
+}
+
+fn play_duplicate(args: DuplicateArgs) {
+    let strategies = [args.players[0].clone(), args.players[1].clone()];
+    let board_results = duplicate::play_boards(&strategies, Rules::from(&args.rules), args.boards);
+    if args.show_boards {
+        for (index, board_result) in board_results.iter().enumerate() {
+            println!(
+                "Board {}: {:+} as North/South, {:+} as East/West, difference {:+}",
+                index + 1,
+                board_result.scores[0],
+                board_result.scores[1],
+                board_result.difference()
+            );
+        }
+    }
+    let wins = board_results
+        .iter()
+        .filter(|board_result| board_result.difference() > 0)
+        .count();
+    let losses = board_results
+        .iter()
+        .filter(|board_result| board_result.difference() < 0)
+        .count();
+    println!(
+        "{} vs {} over {} boards: won {}, tied {}, lost {}",
+        strategies[0],
+        strategies[1],
+        board_results.len().to_formatted_string(&Locale::en),
+        wins.to_formatted_string(&Locale::en),
+        (board_results.len() - wins - losses).to_formatted_string(&Locale::en),
+        losses.to_formatted_string(&Locale::en)
+    );
+    println!(
+        "Average score difference per board: {:+.3} (standard error {:.3}, p = {:.4})",
+        duplicate::mean_difference(&board_results),
+        duplicate::standard_error(&board_results),
+        duplicate::p_value(&board_results)
+    );
+}
+
+fn serve(args: ServeArgs) {
+    let listener = TcpListener::bind(("127.0.0.1", args.port))
+        .unwrap_or_else(|error| panic!("Couldn't listen on port {}: {}", args.port, error));
+    println!("Listening on http://127.0.0.1:{}", args.port);
+    api::serve(listener).unwrap_or_else(|error| panic!("Server failed: {}", error));
+}
+
+fn run_server(args: ServerArgs) {
+    log::set_logger(&LOGGER)
+        .map(|()| log::set_max_level(LevelFilter::Info))
+        .unwrap_or_else(|_| println!("Logging initialization failed!"));
+    for (index, position) in args.remote_seats.iter().enumerate() {
+        if args.remote_seats[..index].contains(position) {
+            panic!("{:?} is listed more than once", position);
+        }
+    }
+    let server = Server::bind(
+        (args.bind, args.port),
+        Rules::from(&args.rules),
+        Duration::from_secs(args.timeout),
+    )
+    .unwrap_or_else(|error| panic!("Couldn't listen on {}:{}: {}", args.bind, args.port, error));
+    println!(
+        "Listening on {} port {} for {} player(s)",
+        args.bind,
+        server
+            .local_port()
+            .unwrap_or_else(|error| panic!("Couldn't get the server's port: {}", error)),
+        args.remote_seats.len()
+    );
+    let table = server
+        .seat_players(&args.remote_seats)
+        .unwrap_or_else(|error| panic!("Couldn't seat players: {}", error));
+    for game in 0..args.games {
+        let game_result = table.play_game(FirstDealer::Rotate.dealer(game));
+        println!(
+            "Game {}: North/South {}, East/West {}",
+            game + 1,
+            game_result.scores[0],
+            game_result.scores[1]
+        );
+    }
+}
+
+fn show_ratings(args: RatingsArgs) {
+    let ratings = load_ratings(&args.ratings_file);
+    println!(
+        "{:<4} {:<20} {:>8} {:>8}",
+        "Rank", "Strategy", "Rating", "Games"
+    );
+    for (index, (name, rating)) in ratings.leaderboard().into_iter().enumerate() {
+        println!(
+            "{:<4} {:<20} {:>8.1} {:>8}",
+            index + 1,
+            name,
+            rating.rating,
+            rating.games.to_formatted_string(&Locale::en)
+        );
+    }
+}
+
+fn load_ratings(path: &Path) -> Ratings {
+    Ratings::load(path)
+        .unwrap_or_else(|error| panic!("Couldn't load {}: {}", path.display(), error))
+}
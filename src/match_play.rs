@@ -0,0 +1,210 @@
+use std::str::FromStr;
+
+use rand::{thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    game_state::GameState, player::Player, players::advanced::AdvancedPlayer, position::Position,
+    rules::Rules,
+};
+
+const Z_95: f64 = 1.96;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FirstDealer {
+    Random,
+    Rotate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct GameResult {
+    pub(crate) scores: [u8; 2],
+    pub(crate) hands_played: u32,
+    pub(crate) loners: [u32; 2],
+    pub(crate) euchres: [u32; 2],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MatchResults {
+    pub(crate) games: u32,
+    pub(crate) wins: [u32; 2],
+    pub(crate) total_margin: i64,
+    pub(crate) hands_played: u64,
+    pub(crate) loners: [u64; 2],
+    pub(crate) euchres: [u64; 2],
+}
+
+impl FirstDealer {
+    pub(crate) fn dealer(&self, game: u32) -> Position {
+        match self {
+            Self::Random => (0..thread_rng().gen_range(0..4))
+                .fold(Position::South, |dealer, _| dealer.next_position_bidding()),
+            Self::Rotate => {
+                (0..game % 4).fold(Position::South, |dealer, _| dealer.next_position_bidding())
+            }
+        }
+    }
+}
+
+impl FromStr for FirstDealer {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "random" => Ok(Self::Random),
+            "rotate" => Ok(Self::Rotate),
+            _ => Err(format!("Invalid first dealer: {}", name)),
+        }
+    }
+}
+
+impl GameResult {
+    pub(crate) fn winner(&self) -> usize {
+        if self.scores[0] > self.scores[1] {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl MatchResults {
+    pub(crate) fn add(mut self, game_result: GameResult) -> MatchResults {
+        self.games += 1;
+        self.wins[game_result.winner()] += 1;
+        self.total_margin += game_result.scores[0] as i64 - game_result.scores[1] as i64;
+        self.hands_played += game_result.hands_played as u64;
+        for team in 0..2 {
+            self.loners[team] += game_result.loners[team] as u64;
+            self.euchres[team] += game_result.euchres[team] as u64;
+        }
+        self
+    }
+
+    pub(crate) fn combine(mut self, other: MatchResults) -> MatchResults {
+        self.games += other.games;
+        self.total_margin += other.total_margin;
+        self.hands_played += other.hands_played;
+        for team in 0..2 {
+            self.wins[team] += other.wins[team];
+            self.loners[team] += other.loners[team];
+            self.euchres[team] += other.euchres[team];
+        }
+        self
+    }
+
+    pub(crate) fn win_rate(&self, team: usize) -> f64 {
+        self.wins[team] as f64 / self.games as f64
+    }
+
+    pub(crate) fn win_rate_confidence_interval(&self, team: usize) -> (f64, f64) {
+        let win_rate = self.win_rate(team);
+        let error = Z_95 * (win_rate * (1f64 - win_rate) / self.games as f64).sqrt();
+        ((win_rate - error).max(0f64), (win_rate + error).min(1f64))
+    }
+
+    pub(crate) fn average_margin(&self) -> f64 {
+        self.total_margin as f64 / self.games as f64
+    }
+
+    pub(crate) fn loner_rate(&self, team: usize) -> f64 {
+        self.loners[team] as f64 / self.hands_played as f64
+    }
+
+    pub(crate) fn euchre_rate(&self, team: usize) -> f64 {
+        self.euchres[team] as f64 / self.hands_played as f64
+    }
+}
+
+pub(crate) fn play_game<T: Player>(mut game_state: GameState<T>) -> GameResult {
+    while game_state.step().is_none() {}
+    GameResult {
+        scores: [game_state.north_south_score, game_state.east_west_score],
+        hands_played: game_state.hands_played,
+        loners: game_state.loners,
+        euchres: game_state.euchres,
+    }
+}
+
+pub(crate) fn play_match(rules: Rules, games: u32, first_dealer: FirstDealer) -> MatchResults {
+    (0..games)
+        .into_par_iter()
+        .map(|game| {
+            let players = [
+                AdvancedPlayer::create_with_rules(Position::North, rules),
+                AdvancedPlayer::create_with_rules(Position::East, rules),
+                AdvancedPlayer::create_with_rules(Position::South, rules),
+                AdvancedPlayer::create_with_rules(Position::West, rules),
+            ];
+            play_game(GameState::create(players, rules, first_dealer.dealer(game)))
+        })
+        .fold(MatchResults::default, MatchResults::add)
+        .reduce(MatchResults::default, MatchResults::combine)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0 => Position::South)]
+    #[test_case(1 => Position::West)]
+    #[test_case(2 => Position::North)]
+    #[test_case(3 => Position::East)]
+    #[test_case(4 => Position::South)]
+    fn rotating_dealer(game: u32) -> Position {
+        FirstDealer::Rotate.dealer(game)
+    }
+
+    #[test_case("random" => Ok(FirstDealer::Random))]
+    #[test_case("Rotate" => Ok(FirstDealer::Rotate))]
+    #[test_case("south" => Err("Invalid first dealer: south".to_string()))]
+    fn from_str(name: &str) -> Result<FirstDealer, String> {
+        name.parse()
+    }
+
+    #[test]
+    fn add_and_combine() {
+        let first = MatchResults::default().add(GameResult {
+            scores: [10, 4],
+            hands_played: 7,
+            loners: [1, 0],
+            euchres: [0, 2],
+        });
+        let second = MatchResults::default().add(GameResult {
+            scores: [8, 11],
+            hands_played: 9,
+            loners: [0, 1],
+            euchres: [1, 1],
+        });
+        let results = first.combine(second);
+        assert_eq!(2, results.games);
+        assert_eq!([1, 1], results.wins);
+        assert_eq!(3, results.total_margin);
+        assert_eq!(16, results.hands_played);
+        assert_eq!([1, 1], results.loners);
+        assert_eq!([1, 3], results.euchres);
+        assert_eq!(0.5, results.win_rate(0));
+        assert_eq!(1.5, results.average_margin());
+    }
+
+    #[test]
+    fn confidence_interval() {
+        let results = MatchResults {
+            games: 100,
+            wins: [50, 50],
+            ..Default::default()
+        };
+        let (low, high) = results.win_rate_confidence_interval(0);
+        assert!((low - 0.402).abs() < 0.001);
+        assert!((high - 0.598).abs() < 0.001);
+    }
+
+    #[test]
+    fn plays_match() {
+        let results = play_match(Rules::default(), 8, FirstDealer::Rotate);
+        assert_eq!(8, results.games);
+        assert_eq!(8, results.wins[0] + results.wins[1]);
+        assert!(results.hands_played >= 8);
+    }
+}
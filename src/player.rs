@@ -1,6 +1,7 @@
 use dyn_clonable::clonable;
 
 use crate::{
+    auction_state::{AuctionBid, Contract},
     bid_result::BidResultCalled,
     card::{Card, CardBeforeBidding},
     hand::{Hand, HandBeforeBidding},
@@ -67,6 +68,10 @@ pub trait Player: Clone + Send + Sync {
         false
     }
 
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, _dealer: &Position) -> Suit {
+        hand.cards[0].suit
+    }
+
     fn choose_discard(&mut self, hand: &HandBeforeBidding, _trump: &Suit) -> CardBeforeBidding {
         hand.cards[0]
     }
@@ -90,4 +95,34 @@ pub trait Player: Clone + Send + Sync {
     }
 
     fn trick_end(&mut self, _bid_result: &BidResultCalled, _cards_played: &[PlayedCard]) {}
+
+    fn bid_auction(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        _high_bid: Option<&Contract>,
+    ) -> Option<AuctionBid> {
+        None
+    }
+
+    fn play_card_in_contract(
+        &mut self,
+        hand: &Hand,
+        contract: &Contract,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        match contract.bid_result() {
+            Some(bid_result) => self.play_card(hand, &bid_result, cards_played),
+            None => match cards_played.first() {
+                Some(PlayedCard {
+                    card: Card { suit, .. },
+                    ..
+                }) => match hand.cards.iter().find(|card| card.suit == *suit) {
+                    Some(card) => *card,
+                    None => hand.cards[0],
+                },
+                None => hand.cards[0],
+            },
+        }
+    }
 }
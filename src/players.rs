@@ -1,4 +1,14 @@
 pub(crate) mod advanced;
 pub(crate) mod basic;
+pub(crate) mod human;
+pub(crate) mod line;
 pub(crate) mod preprogrammed_bidder;
+pub(crate) mod process;
+pub(crate) mod random;
+pub(crate) mod recording;
+pub(crate) mod registry;
+pub(crate) mod remote;
+pub(crate) mod replay;
+pub(crate) mod table;
+pub(crate) mod tui;
 pub(crate) mod wrapper;
@@ -1,6 +1,10 @@
+use std::{fs, io, path::Path};
+
 use enum_iterator::IntoEnumIterator;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    auction_state::{AuctionBid, Contract, Strain},
     bid_result::BidResultCalled,
     card::{Card, CardBeforeBidding},
     hand::{Hand, HandBeforeBidding},
@@ -8,29 +12,148 @@ use crate::{
     position::Position,
     rank::Rank,
     rank_with_bowers::RankWithBowers,
+    rules::Rules,
+    shared_files::SharedFiles,
     suit::Suit,
     trick_state::PlayedCard,
 };
 
+// Bump this whenever the strategy changes so cached simulations are rerun.
+// cache::tests::versions_track_results fails when play changes without a bump.
+pub(crate) const VERSION: u32 = 1;
+
+// A config file is a JSON object with any of these fields, such as
+// `{"order_up_trump_count": 3}`. Missing fields keep their default values.
+// Trump counts for ordering up are adjusted by one for the up card: up when
+// dealing, down when it goes to an opponent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct AdvancedConfig {
+    pub(crate) order_up_trump_count: i32,
+    pub(crate) order_up_trump_count_with_right_or_to_partner: i32,
+    pub(crate) order_up_trump_count_with_right_and_off_ace: i32,
+    pub(crate) order_up_alone_max_cards_that_could_beat: i32,
+    pub(crate) call_trump_count: usize,
+    pub(crate) call_trump_count_with_right: usize,
+    pub(crate) call_trump_count_with_right_and_off_ace: usize,
+    pub(crate) call_alone_max_cards_that_could_beat: i32,
+}
+
 #[derive(Clone)]
 pub(crate) struct AdvancedPlayer {
     position: Position,
+    rules: Rules,
+    config: AdvancedConfig,
     trump_has_been_led: bool,
     is_definitely_out_of_trump: [bool; 4],
-    trump_played: [bool; RankWithBowers::RightBower as usize + 1],
+    trump_played: [bool; RankWithBowers::Joker as usize + 1],
 }
 
 impl AdvancedPlayer {
     pub(crate) fn create(position: Position) -> AdvancedPlayer {
+        AdvancedPlayer::create_with_rules(position, Rules::default())
+    }
+
+    pub(crate) fn create_with_rules(position: Position, rules: Rules) -> AdvancedPlayer {
+        AdvancedPlayer::create_with_config(position, rules, AdvancedConfig::default())
+    }
+
+    pub(crate) fn create_with_config(
+        position: Position,
+        rules: Rules,
+        config: AdvancedConfig,
+    ) -> AdvancedPlayer {
         AdvancedPlayer {
             position,
+            rules,
+            config,
             trump_has_been_led: false,
             is_definitely_out_of_trump: [false; 4],
-            trump_played: [false; RankWithBowers::RightBower as usize + 1],
+            trump_played: [false; RankWithBowers::Joker as usize + 1],
         }
     }
 }
 
+impl Default for AdvancedConfig {
+    fn default() -> Self {
+        AdvancedConfig {
+            order_up_trump_count: 4,
+            order_up_trump_count_with_right_or_to_partner: 3,
+            order_up_trump_count_with_right_and_off_ace: 2,
+            order_up_alone_max_cards_that_could_beat: 2,
+            call_trump_count: 4,
+            call_trump_count_with_right: 3,
+            call_trump_count_with_right_and_off_ace: 2,
+            call_alone_max_cards_that_could_beat: 2,
+        }
+    }
+}
+
+impl AdvancedConfig {
+    pub(crate) fn load(path: &Path) -> io::Result<AdvancedConfig> {
+        serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub(crate) fn load_shared(path: &str) -> io::Result<AdvancedConfig> {
+        static CONFIGS: SharedFiles<AdvancedConfig> = SharedFiles::new();
+        CONFIGS.load(path, AdvancedConfig::load)
+    }
+}
+
+impl AdvancedPlayer {
+    fn estimate_auction_bid(hand: &HandBeforeBidding, strain: Strain) -> Option<AuctionBid> {
+        let tricks = match strain {
+            Strain::Trump(trump) => {
+                let trump_count = hand
+                    .cards
+                    .iter()
+                    .filter(|card| card.is_trump(&trump))
+                    .count();
+                let has_right = hand
+                    .cards
+                    .iter()
+                    .any(|card| card.suit == trump && card.rank == Rank::Jack);
+                let has_left = hand.cards.iter().any(|card| {
+                    card.suit == trump.other_suit_of_same_color() && card.rank == Rank::Jack
+                });
+                let off_aces = hand
+                    .cards
+                    .iter()
+                    .filter(|card| card.rank == Rank::Ace && !card.is_trump(&trump))
+                    .count();
+                if trump_count >= 5 && has_right && has_left && trump_count + off_aces == 6 {
+                    return Some(AuctionBid::ShootTheMoon { strain });
+                }
+                trump_count + off_aces
+            }
+            Strain::High | Strain::Low => {
+                let (best, second_best) = if strain == Strain::High {
+                    (Rank::Ace, Rank::King)
+                } else {
+                    (Rank::Nine, Rank::Ten)
+                };
+                hand.cards
+                    .iter()
+                    .filter(|card| {
+                        card.rank == best
+                            || card.rank == second_best
+                                && hand.cards.contains(&CardBeforeBidding {
+                                    suit: card.suit,
+                                    rank: best,
+                                })
+                    })
+                    .count()
+                    + 1
+            }
+        };
+        Some(AuctionBid::Tricks {
+            tricks: tricks.min(6) as u8,
+            strain,
+        })
+    }
+}
+
 impl Player for AdvancedPlayer {
     fn should_order_up(
         &mut self,
@@ -48,14 +171,13 @@ impl Player for AdvancedPlayer {
 
         let to_me = self.position == dealer;
         let to_partner = self.position.partner() == dealer;
-        let trump_cards = hand.cards.iter().filter(|card| {
-            card.suit == trump_candidate.suit
-                || card.rank == Rank::Jack
-                    && card.suit == trump_candidate.suit.other_suit_of_same_color()
+        let trump_cards = hand
+            .cards
+            .iter()
+            .filter(|card| card.is_trump(&trump_candidate.suit));
+        let has_right = trump_cards.clone().any(|&card| {
+            card.rank == Rank::Joker || card.suit == trump_candidate.suit && card.rank == Rank::Jack
         });
-        let has_right = trump_cards
-            .clone()
-            .any(|&card| card.suit == trump_candidate.suit && card.rank == Rank::Jack);
         let has_off_ace = hand
             .cards
             .iter()
@@ -69,12 +191,13 @@ impl Player for AdvancedPlayer {
             } else {
                 0
             };
-        match trump_count {
-            6 | 5 | 4 => true,
-            3 if to_partner || has_right => true,
-            2 if has_right && has_off_ace => true,
-            _ => false,
-        }
+        let config = &self.config;
+        trump_count >= config.order_up_trump_count
+            || trump_count >= config.order_up_trump_count_with_right_or_to_partner
+                && (to_partner || has_right)
+            || trump_count >= config.order_up_trump_count_with_right_and_off_ace
+                && has_right
+                && has_off_ace
     }
 
     fn should_order_up_alone(
@@ -92,11 +215,7 @@ impl Player for AdvancedPlayer {
         }
         let trump_cards: Vec<&CardBeforeBidding> = cards
             .iter()
-            .filter(|card| {
-                card.suit == trump_candidate.suit
-                    || card.rank == Rank::Jack
-                        && card.suit == trump_candidate.suit.other_suit_of_same_color()
-            })
+            .filter(|card| card.is_trump(&trump_candidate.suit))
             .collect();
         if trump_cards.len() < 3
             || trump_cards
@@ -109,13 +228,19 @@ impl Player for AdvancedPlayer {
         }
         let mut highest_card_in_suit = [None; 4];
         for &card in &cards {
-            if card.rank == Rank::Jack && card.suit == trump_candidate.suit {
-                highest_card_in_suit[card.suit.index()] = Some(card);
+            if card.rank == Rank::Joker {
+                highest_card_in_suit[trump_candidate.suit.index()] = Some(card);
+            } else if card.rank == Rank::Jack && card.suit == trump_candidate.suit {
+                match highest_card_in_suit[card.suit.index()] {
+                    Some(highest_card) if highest_card.rank == Rank::Joker => (),
+                    _ => highest_card_in_suit[card.suit.index()] = Some(card),
+                }
             } else if card.rank == Rank::Jack
                 && card.suit.other_suit_of_same_color() == trump_candidate.suit
             {
                 match highest_card_in_suit[card.suit.other_suit_of_same_color().index()] {
-                    Some(highest_card) if highest_card.rank == Rank::Jack => (),
+                    Some(highest_card)
+                        if highest_card.rank == Rank::Jack || highest_card.rank == Rank::Joker => {}
                     _ => {
                         highest_card_in_suit[card.suit.other_suit_of_same_color().index()] =
                             Some(card)
@@ -138,7 +263,11 @@ impl Player for AdvancedPlayer {
         for suit in Suit::into_enum_iter() {
             if suit == trump_candidate.suit {
                 if let Some(card) = highest_card_in_suit[suit.index()] {
+                    if self.rules.joker && card.rank != Rank::Joker {
+                        cards_that_could_beat_my_highest += 1;
+                    }
                     match card.rank {
+                        Rank::Joker => (),
                         Rank::Jack if card.suit != trump_candidate.suit => {
                             cards_that_could_beat_my_highest += 1
                         }
@@ -148,22 +277,26 @@ impl Player for AdvancedPlayer {
                         Rank::Queen => cards_that_could_beat_my_highest += 4,
                         Rank::Ten => cards_that_could_beat_my_highest += 5,
                         Rank::Nine => cards_that_could_beat_my_highest += 6,
+                        Rank::Eight => cards_that_could_beat_my_highest += 7,
+                        Rank::Seven => cards_that_could_beat_my_highest += 8,
                     }
                 }
                 continue;
             }
             if let Some(card) = highest_card_in_suit[suit.index()] {
                 match card.rank {
-                    Rank::Ace => (),
+                    Rank::Joker | Rank::Ace => (),
                     Rank::King => cards_that_could_beat_my_highest += 1,
                     Rank::Queen => cards_that_could_beat_my_highest += 2,
                     Rank::Jack => cards_that_could_beat_my_highest += 3,
                     Rank::Ten => cards_that_could_beat_my_highest += 4,
                     Rank::Nine => cards_that_could_beat_my_highest += 5,
+                    Rank::Eight => cards_that_could_beat_my_highest += 6,
+                    Rank::Seven => cards_that_could_beat_my_highest += 7,
                 }
             }
         }
-        cards_that_could_beat_my_highest <= 2
+        cards_that_could_beat_my_highest <= self.config.order_up_alone_max_cards_that_could_beat
     }
 
     fn should_defend_alone_ordered(
@@ -183,14 +316,13 @@ impl Player for AdvancedPlayer {
     ) -> Option<Suit> {
         let mut suit_scores = [0; 4];
         for trump_candidate in Suit::into_enum_iter().filter(|&suit| suit != turned_down.suit) {
-            let trump_cards = hand.cards.iter().filter(|card| {
-                card.suit == trump_candidate
-                    || card.rank == Rank::Jack
-                        && card.suit == trump_candidate.other_suit_of_same_color()
+            let trump_cards = hand
+                .cards
+                .iter()
+                .filter(|card| card.is_trump(&trump_candidate));
+            let has_right = trump_cards.clone().any(|&card| {
+                card.rank == Rank::Joker || card.suit == trump_candidate && card.rank == Rank::Jack
             });
-            let has_right = trump_cards
-                .clone()
-                .any(|&card| card.suit == trump_candidate && card.rank == Rank::Jack);
             let has_off_ace = hand
                 .cards
                 .iter()
@@ -201,9 +333,7 @@ impl Player for AdvancedPlayer {
                 .cards
                 .iter()
                 .map(|card| {
-                    if card.rank == Rank::Jack
-                        && card.suit == trump_candidate.other_suit_of_same_color()
-                    {
+                    if card.is_trump(&trump_candidate) {
                         trump_candidate
                     } else {
                         card.suit
@@ -213,13 +343,15 @@ impl Player for AdvancedPlayer {
             suits.sort();
             suits.dedup();
             let suit_count = suits.len();
-            match trump_count {
-                5 | 4 => suit_scores[trump_candidate.index()] = trump_count,
-                3 if has_right => suit_scores[trump_candidate.index()] = trump_count,
-                2 if has_right && has_off_ace && suit_count < 4 => {
-                    suit_scores[trump_candidate.index()] = trump_count
-                }
-                _ => (),
+            let config = &self.config;
+            if trump_count >= config.call_trump_count
+                || trump_count >= config.call_trump_count_with_right && has_right
+                || trump_count >= config.call_trump_count_with_right_and_off_ace
+                    && has_right
+                    && has_off_ace
+                    && suit_count < 4
+            {
+                suit_scores[trump_candidate.index()] = trump_count
             }
         }
         let mut max_score = 0;
@@ -241,13 +373,8 @@ impl Player for AdvancedPlayer {
         _turned_down: &CardBeforeBidding,
     ) -> bool {
         let cards = &hand.cards;
-        let trump_cards: Vec<&CardBeforeBidding> = cards
-            .iter()
-            .filter(|card| {
-                card.suit == trump
-                    || card.rank == Rank::Jack && card.suit == trump.other_suit_of_same_color()
-            })
-            .collect();
+        let trump_cards: Vec<&CardBeforeBidding> =
+            cards.iter().filter(|card| card.is_trump(&trump)).collect();
         if trump_cards.len() < 3
             || trump_cards
                 .iter()
@@ -259,11 +386,17 @@ impl Player for AdvancedPlayer {
         }
         let mut highest_card_in_suit = [None; 4];
         for &card in cards {
-            if card.rank == Rank::Jack && card.suit == trump {
-                highest_card_in_suit[card.suit.index()] = Some(card);
+            if card.rank == Rank::Joker {
+                highest_card_in_suit[trump.index()] = Some(card);
+            } else if card.rank == Rank::Jack && card.suit == trump {
+                match highest_card_in_suit[card.suit.index()] {
+                    Some(highest_card) if highest_card.rank == Rank::Joker => (),
+                    _ => highest_card_in_suit[card.suit.index()] = Some(card),
+                }
             } else if card.rank == Rank::Jack && card.suit.other_suit_of_same_color() == trump {
                 match highest_card_in_suit[card.suit.other_suit_of_same_color().index()] {
-                    Some(highest_card) if highest_card.rank == Rank::Jack => (),
+                    Some(highest_card)
+                        if highest_card.rank == Rank::Jack || highest_card.rank == Rank::Joker => {}
                     _ => {
                         highest_card_in_suit[card.suit.other_suit_of_same_color().index()] =
                             Some(card)
@@ -286,7 +419,11 @@ impl Player for AdvancedPlayer {
         for suit in Suit::into_enum_iter() {
             if suit == trump {
                 if let Some(card) = highest_card_in_suit[suit.index()] {
+                    if self.rules.joker && card.rank != Rank::Joker {
+                        cards_that_could_beat_my_highest += 1;
+                    }
                     match card.rank {
+                        Rank::Joker => (),
                         Rank::Jack if card.suit != trump => cards_that_could_beat_my_highest += 1,
                         Rank::Jack => (),
                         Rank::Ace => cards_that_could_beat_my_highest += 2,
@@ -294,22 +431,26 @@ impl Player for AdvancedPlayer {
                         Rank::Queen => cards_that_could_beat_my_highest += 4,
                         Rank::Ten => cards_that_could_beat_my_highest += 5,
                         Rank::Nine => cards_that_could_beat_my_highest += 6,
+                        Rank::Eight => cards_that_could_beat_my_highest += 7,
+                        Rank::Seven => cards_that_could_beat_my_highest += 8,
                     }
                 }
                 continue;
             }
             if let Some(card) = highest_card_in_suit[suit.index()] {
                 match card.rank {
-                    Rank::Ace => (),
+                    Rank::Joker | Rank::Ace => (),
                     Rank::King => cards_that_could_beat_my_highest += 1,
                     Rank::Queen => cards_that_could_beat_my_highest += 2,
                     Rank::Jack => cards_that_could_beat_my_highest += 3,
                     Rank::Ten => cards_that_could_beat_my_highest += 4,
                     Rank::Nine => cards_that_could_beat_my_highest += 5,
+                    Rank::Eight => cards_that_could_beat_my_highest += 6,
+                    Rank::Seven => cards_that_could_beat_my_highest += 7,
                 }
             }
         }
-        cards_that_could_beat_my_highest <= 2
+        cards_that_could_beat_my_highest <= self.config.call_alone_max_cards_that_could_beat
     }
 
     fn should_defend_alone_called(
@@ -322,11 +463,21 @@ impl Player for AdvancedPlayer {
         false
     }
 
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, _dealer: &Position) -> Suit {
+        Suit::into_enum_iter()
+            .max_by_key(|suit| hand.cards.iter().filter(|card| card.is_trump(suit)).count())
+            .unwrap()
+    }
+
     fn choose_discard(&mut self, hand: &HandBeforeBidding, &trump: &Suit) -> CardBeforeBidding {
         let mut suit_counts: [u8; 4] = [0; 4];
         let mut has_ace: [bool; 4] = [false; 4];
         let mut lowest_cards: [Option<CardBeforeBidding>; 4] = [None; 4];
         for &card in &hand.cards {
+            if card.rank == Rank::Joker {
+                suit_counts[trump.index()] += 1;
+                continue;
+            }
             suit_counts[card.suit.index()] += 1;
             if card.rank == Rank::Ace {
                 has_ace[card.suit.index()] = true;
@@ -413,6 +564,21 @@ impl Player for AdvancedPlayer {
         }
     }
 
+    fn bid_auction(
+        &mut self,
+        hand: &HandBeforeBidding,
+        _dealer: &Position,
+        high_bid: Option<&Contract>,
+    ) -> Option<AuctionBid> {
+        Strain::all()
+            .filter_map(|strain| AdvancedPlayer::estimate_auction_bid(hand, strain))
+            .filter(|bid| bid.is_valid_over(high_bid))
+            .max_by_key(|bid| match bid {
+                AuctionBid::Tricks { tricks, .. } => *tricks,
+                AuctionBid::ShootTheMoon { .. } => 7,
+            })
+    }
+
     fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
         let trump = bid_result.trump();
 
@@ -500,6 +666,10 @@ mod tests {
         BidResultAll::alone("S"), Some("AH") ; "Right left ace king off ace, pick up the queen alone")]
     #[test_case(["JS", "AS", "KS", "KH", "QH"], "JC", Position::East,
         BidResultAll::alone("S"), None ; "Right ace king, off king queen, call alone")]
+    #[test_case(["JK", "JS", "AS", "KS", "QS"], "AD", Position::West,
+        BidResultAll::alone("S"), None ; "Joker right ace king queen, call alone")]
+    #[test_case(["JK", "NS", "AC", "TD", "ND"], "TS", Position::North,
+        BidResultAll::called("S"), None ; "Joker nine off ace 3 suited, order to partner")]
     //TODO: test right nine 3 off aces
     //TODO: test not waiting for the next suit if the current one is better
     //TODO: better test logic around turned down card making things good
@@ -587,4 +757,52 @@ mod tests {
             discard,
         );
     }
+
+    #[test]
+    fn parse_config() {
+        let config: AdvancedConfig =
+            serde_json::from_str(r#"{"order_up_trump_count": 3}"#).unwrap();
+        assert_eq!(
+            AdvancedConfig {
+                order_up_trump_count: 3,
+                ..AdvancedConfig::default()
+            },
+            config
+        );
+        assert!(serde_json::from_str::<AdvancedConfig>(r#"{"order_up_trump": 3}"#).is_err());
+    }
+
+    #[test]
+    fn configured_thresholds() {
+        let hand = |names: [&str; 5]| HandBeforeBidding {
+            cards: names
+                .iter()
+                .map(|&card| CardBeforeBidding::from_str(card).unwrap())
+                .collect(),
+        };
+        let order_up_hand = hand(["JS", "NS", "AC", "TD", "ND"]);
+        let call_hand = hand(["JS", "NS", "AC", "AH", "ND"]);
+        let ten_of_spades = CardBeforeBidding::from_str("TS").unwrap();
+        let ace_of_diamonds = CardBeforeBidding::from_str("AD").unwrap();
+        let mut cautious = AdvancedPlayer::create(Position::South);
+        assert!(!cautious.should_order_up(&order_up_hand, &Position::West, &ten_of_spades));
+        assert_eq!(
+            None,
+            cautious.call_trump(&call_hand, &Position::West, &ace_of_diamonds)
+        );
+        let mut aggressive = AdvancedPlayer::create_with_config(
+            Position::South,
+            Rules::default(),
+            AdvancedConfig {
+                order_up_trump_count_with_right_and_off_ace: 1,
+                call_trump_count_with_right: 2,
+                ..AdvancedConfig::default()
+            },
+        );
+        assert!(aggressive.should_order_up(&order_up_hand, &Position::West, &ten_of_spades));
+        assert_eq!(
+            Some(Suit::Spades),
+            aggressive.call_trump(&call_hand, &Position::West, &ace_of_diamonds)
+        );
+    }
 }
@@ -1,8 +1,12 @@
 use crate::{
     card::CardBeforeBidding, hand::HandBeforeBidding, player::Player, position::Position,
-    rank::Rank, suit::Suit,
+    suit::Suit,
 };
 
+// Bump this whenever the strategy changes so cached simulations are rerun,
+// then record its new fingerprint in cache::tests::versions_track_results.
+pub(crate) const VERSION: u32 = 1;
+
 #[derive(Clone)]
 pub(crate) struct BasicPlayer {
     pub(crate) position: Position,
@@ -15,11 +19,10 @@ impl Player for BasicPlayer {
         dealer: &Position,
         trump_candidate: &CardBeforeBidding,
     ) -> bool {
-        let trump_cards = hand.cards.iter().filter(|card| {
-            card.suit == trump_candidate.suit
-                || card.rank == Rank::Jack
-                    && card.suit == trump_candidate.suit.other_suit_of_same_color()
-        });
+        let trump_cards = hand
+            .cards
+            .iter()
+            .filter(|card| card.is_trump(&trump_candidate.suit));
         match trump_cards.count() {
             4 | 5 => true,
             3 if *dealer == self.position || *dealer == self.position.partner() => true,
@@ -0,0 +1,278 @@
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+};
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    hand::{Hand, HandBeforeBidding},
+    hint::{Advisor, Hint},
+    player::Player,
+    position::Position,
+    rules::Rules,
+    suit::Suit,
+    trick_state::{PlayedCard, TrickState},
+};
+
+#[derive(Clone)]
+pub(crate) struct HumanPlayer {
+    position: Position,
+    advisor: Advisor,
+}
+
+impl HumanPlayer {
+    pub(crate) fn create(position: Position, rules: Rules) -> HumanPlayer {
+        HumanPlayer {
+            position,
+            advisor: Advisor::create(position, rules),
+        }
+    }
+
+    fn show_hand<T: Display>(&self, cards: &[T]) {
+        println!(
+            "{:?}, your hand: {}",
+            self.position,
+            cards
+                .iter()
+                .map(|card| card.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        );
+    }
+
+    fn ask_yes_no(question: &str, mut hint: impl FnMut() -> Hint) -> bool {
+        loop {
+            let answer = prompt(&format!("{} (y/n, h for a hint)", question));
+            if is_hint_request(&answer) {
+                println!("{}", hint());
+            } else if let Some(answer) = parse_yes_no(&answer) {
+                return answer;
+            }
+        }
+    }
+
+    fn ask_choice<T: Display>(
+        question: &str,
+        choices: &[T],
+        mut hint: impl FnMut() -> Hint,
+    ) -> usize {
+        let options = choices
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| format!("{}) {}", index + 1, choice))
+            .collect::<Vec<String>>()
+            .join("  ");
+        loop {
+            let answer = prompt(&format!("{} {}  h) hint", question, options));
+            if is_hint_request(&answer) {
+                println!("{}", hint());
+            } else if let Some(index) = parse_choice(&answer, choices.len()) {
+                return index;
+            }
+        }
+    }
+
+    fn ask_suit(question: &str, suits: &[Suit], hint: impl FnMut() -> Hint) -> Suit {
+        suits[Self::ask_choice(question, suits, hint)]
+    }
+}
+
+impl Player for HumanPlayer {
+    fn should_order_up(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.show_hand(&hand.cards);
+        Self::ask_yes_no(
+            &format!("{:?} is dealing. Order up {}?", dealer, trump_candidate),
+            || self.advisor.order_up(hand, dealer, trump_candidate),
+        )
+    }
+
+    fn should_order_up_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        Self::ask_yes_no("Go alone?", || {
+            self.advisor.order_up(hand, dealer, trump_candidate)
+        })
+    }
+
+    fn should_defend_alone_ordered(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.show_hand(&hand.cards);
+        Self::ask_yes_no(
+            &format!(
+                "An opponent is going alone with {} as trump. Defend alone?",
+                trump_candidate.suit
+            ),
+            || {
+                self.advisor
+                    .defend_alone_ordered(hand, dealer, trump_candidate)
+            },
+        )
+    }
+
+    fn call_trump(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Option<Suit> {
+        self.show_hand(&hand.cards);
+        let suits: Vec<Suit> = Suit::into_enum_iter()
+            .filter(|suit| *suit != turned_down.suit)
+            .collect();
+        if Self::ask_yes_no(
+            &format!(
+                "{:?} is dealing and {} was turned down. Call trump?",
+                dealer, turned_down
+            ),
+            || self.advisor.call_trump(hand, dealer, turned_down),
+        ) {
+            Some(Self::ask_suit("Call", &suits, || {
+                self.advisor.call_trump(hand, dealer, turned_down)
+            }))
+        } else {
+            None
+        }
+    }
+
+    fn should_call_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        Self::ask_yes_no("Go alone?", || {
+            self.advisor.call_alone(hand, dealer, trump, turned_down)
+        })
+    }
+
+    fn should_defend_alone_called(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.show_hand(&hand.cards);
+        Self::ask_yes_no(
+            &format!(
+                "An opponent is going alone with {} as trump. Defend alone?",
+                trump
+            ),
+            || {
+                self.advisor
+                    .defend_alone_called(hand, dealer, trump, turned_down)
+            },
+        )
+    }
+
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Suit {
+        self.show_hand(&hand.cards);
+        let suits: Vec<Suit> = Suit::into_enum_iter().collect();
+        Self::ask_suit("The joker was turned up. Name its suit:", &suits, || {
+            self.advisor.joker_suit(hand, dealer)
+        })
+    }
+
+    fn choose_discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> CardBeforeBidding {
+        hand.cards[Self::ask_choice("Discard", &hand.cards, || self.advisor.discard(hand, trump))]
+    }
+
+    fn play_card(
+        &mut self,
+        hand: &Hand,
+        bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        println!(
+            "{} is trump. Cards played: {}",
+            bid_result.trump(),
+            cards_played
+                .iter()
+                .map(|played_card| format!("{:?} {}", played_card.player, played_card.card))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        let legal_cards = TrickState::legal_cards(hand, cards_played);
+        legal_cards[Self::ask_choice("Play", &legal_cards, || {
+            self.advisor.play_card(hand, bid_result, cards_played)
+        })]
+    }
+
+    fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
+        self.advisor.trick_end(bid_result, cards_played);
+    }
+}
+
+fn prompt(question: &str) -> String {
+    print!("{} ", question);
+    io::stdout().flush().unwrap();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).unwrap() == 0 {
+        panic!("Reached the end of input");
+    }
+    answer
+}
+
+fn is_hint_request(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "h" | "hint")
+}
+
+fn parse_yes_no(answer: &str) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_choice(answer: &str, choice_count: usize) -> Option<usize> {
+    match answer.trim().parse::<usize>() {
+        Ok(choice) if (1..=choice_count).contains(&choice) => Some(choice - 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    #[test_case("y\n" => Some(true))]
+    #[test_case("Yes" => Some(true))]
+    #[test_case("n" => Some(false))]
+    #[test_case("maybe" => None)]
+    fn parse_yes_no(answer: &str) -> Option<bool> {
+        super::parse_yes_no(answer)
+    }
+
+    #[test_case("h\n" => true)]
+    #[test_case("Hint" => true)]
+    #[test_case("y" => false)]
+    fn is_hint_request(answer: &str) -> bool {
+        super::is_hint_request(answer)
+    }
+
+    #[test_case("1\n", 5 => Some(0))]
+    #[test_case("5", 5 => Some(4))]
+    #[test_case("6", 5 => None)]
+    #[test_case("0", 5 => None)]
+    #[test_case("J", 5 => None)]
+    fn parse_choice(answer: &str, choice_count: usize) -> Option<usize> {
+        super::parse_choice(answer, choice_count)
+    }
+}
@@ -0,0 +1,358 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    position::Position,
+    protocol::{self, Message, Request},
+    suit::Suit,
+    trick_state::PlayedCard,
+};
+
+pub(crate) trait Transport: Send {
+    fn name(&self) -> &str;
+
+    fn send(&mut self, line: &str) -> io::Result<()>;
+
+    fn unrequested(&mut self) -> Option<String>;
+
+    fn receive(&mut self) -> Result<String, String>;
+
+    fn close(&mut self);
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Encoding {
+    Text,
+    Json,
+}
+
+pub(crate) struct LinePlayer<T: Transport> {
+    line: Arc<Mutex<Line<T>>>,
+    fallback: Fallback,
+}
+
+struct Line<T: Transport> {
+    transport: T,
+    encoding: Encoding,
+    failed: bool,
+}
+
+#[derive(Clone)]
+pub(crate) struct Fallback;
+
+impl Player for Fallback {}
+
+impl Encoding {
+    fn request(&self, request: &Request<'_>) -> String {
+        match self {
+            Encoding::Text => request.to_string(),
+            Encoding::Json => Message::from(request).to_string(),
+        }
+    }
+
+    fn yes_no(&self, reply: &str) -> Result<bool, String> {
+        match self {
+            Encoding::Text => protocol::parse_yes_no(reply),
+            Encoding::Json => protocol::parse_json_yes_no(reply),
+        }
+    }
+
+    fn call(&self, reply: &str) -> Result<Option<Suit>, String> {
+        match self {
+            Encoding::Text => protocol::parse_call(reply),
+            Encoding::Json => protocol::parse_json_call(reply),
+        }
+    }
+
+    fn suit(&self, reply: &str) -> Result<Suit, String> {
+        match self {
+            Encoding::Text => protocol::parse_suit(reply),
+            Encoding::Json => protocol::parse_json_suit(reply),
+        }
+    }
+
+    fn card(&self, reply: &str) -> Result<CardBeforeBidding, String> {
+        match self {
+            Encoding::Text => protocol::parse_card(reply),
+            Encoding::Json => protocol::parse_json_card(reply),
+        }
+    }
+
+    fn card_in_hand(&self, reply: &str, hand: &Hand) -> Result<Card, String> {
+        match self {
+            Encoding::Text => protocol::parse_card_in_hand(reply, hand),
+            Encoding::Json => protocol::parse_json_card_in_hand(reply, hand),
+        }
+    }
+}
+
+impl<T: Transport> Clone for LinePlayer<T> {
+    fn clone(&self) -> Self {
+        LinePlayer {
+            line: self.line.clone(),
+            fallback: Fallback,
+        }
+    }
+}
+
+impl<T: Transport> LinePlayer<T> {
+    pub(crate) fn create_with_transport(
+        transport: T,
+        encoding: Encoding,
+        position: Position,
+    ) -> LinePlayer<T> {
+        let player = LinePlayer {
+            line: Arc::new(Mutex::new(Line {
+                transport,
+                encoding,
+                failed: false,
+            })),
+            fallback: Fallback,
+        };
+        player.tell(Request::Seat { position });
+        player
+    }
+
+    pub(crate) fn tell(&self, request: Request<'_>) {
+        let mut line = self.line.lock().unwrap();
+        if !line.failed {
+            if let Err(error) = line.send(&request) {
+                line.fail(&format!("couldn't send {}: {}", request, error));
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn failed(&self) -> bool {
+        self.line.lock().unwrap().failed
+    }
+
+    fn ask<V>(
+        &self,
+        request: Request<'_>,
+        parse: impl Fn(&Encoding, &str) -> Result<V, String>,
+    ) -> Option<V> {
+        let mut line = self.line.lock().unwrap();
+        if line.failed {
+            return None;
+        }
+        if let Some(unexpected) = line.transport.unrequested() {
+            line.fail(&format!(
+                "sent {:?} before being asked anything",
+                unexpected
+            ));
+            return None;
+        }
+        if let Err(error) = line.send(&request) {
+            line.fail(&format!("couldn't send {}: {}", request, error));
+            return None;
+        }
+        let reply = match line.transport.receive() {
+            Ok(reply) => reply,
+            Err(error) => {
+                line.fail(&format!("no reply to {}: {}", request, error));
+                return None;
+            }
+        };
+        match parse(&line.encoding, &reply) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                log::warn!(
+                    "{} sent an invalid reply to {}: {}",
+                    line.transport.name(),
+                    request,
+                    error
+                );
+                None
+            }
+        }
+    }
+}
+
+impl<T: Transport> Line<T> {
+    fn send(&mut self, request: &Request<'_>) -> io::Result<()> {
+        let message = self.encoding.request(request);
+        self.transport.send(&message)
+    }
+
+    fn fail(&mut self, reason: &str) {
+        log::warn!(
+            "{} {}; using default decisions from now on",
+            self.transport.name(),
+            reason
+        );
+        self.failed = true;
+        self.transport.close();
+    }
+}
+
+impl<T: Transport> Drop for Line<T> {
+    fn drop(&mut self) {
+        if !self.failed {
+            if let Err(error) = self.send(&Request::Quit) {
+                log::debug!("Couldn't tell {} to quit: {}", self.transport.name(), error);
+            }
+            self.transport.close();
+        }
+    }
+}
+
+impl<T: Transport> Player for LinePlayer<T> {
+    fn should_order_up(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        let request = Request::OrderUp {
+            hand,
+            dealer: *dealer,
+            trump_candidate: *trump_candidate,
+        };
+        self.ask(request, Encoding::yes_no)
+            .unwrap_or_else(|| self.fallback.should_order_up(hand, dealer, trump_candidate))
+    }
+
+    fn should_order_up_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        let request = Request::OrderUpAlone {
+            hand,
+            dealer: *dealer,
+            trump_candidate: *trump_candidate,
+        };
+        self.ask(request, Encoding::yes_no).unwrap_or_else(|| {
+            self.fallback
+                .should_order_up_alone(hand, dealer, trump_candidate)
+        })
+    }
+
+    fn should_defend_alone_ordered(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        let request = Request::DefendAloneOrdered {
+            hand,
+            dealer: *dealer,
+            trump_candidate: *trump_candidate,
+        };
+        self.ask(request, Encoding::yes_no).unwrap_or_else(|| {
+            self.fallback
+                .should_defend_alone_ordered(hand, dealer, trump_candidate)
+        })
+    }
+
+    fn call_trump(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Option<Suit> {
+        let request = Request::CallTrump {
+            hand,
+            dealer: *dealer,
+            turned_down: *turned_down,
+        };
+        self.ask(request, Encoding::call)
+            .unwrap_or_else(|| self.fallback.call_trump(hand, dealer, turned_down))
+    }
+
+    fn should_call_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        let request = Request::CallAlone {
+            hand,
+            dealer: *dealer,
+            trump: *trump,
+            turned_down: *turned_down,
+        };
+        self.ask(request, Encoding::yes_no).unwrap_or_else(|| {
+            self.fallback
+                .should_call_alone(hand, dealer, trump, turned_down)
+        })
+    }
+
+    fn should_defend_alone_called(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        let request = Request::DefendAloneCalled {
+            hand,
+            dealer: *dealer,
+            trump: *trump,
+            turned_down: *turned_down,
+        };
+        self.ask(request, Encoding::yes_no).unwrap_or_else(|| {
+            self.fallback
+                .should_defend_alone_called(hand, dealer, trump, turned_down)
+        })
+    }
+
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Suit {
+        let request = Request::JokerSuit {
+            hand,
+            dealer: *dealer,
+        };
+        self.ask(request, Encoding::suit)
+            .unwrap_or_else(|| self.fallback.choose_joker_suit(hand, dealer))
+    }
+
+    fn choose_discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> CardBeforeBidding {
+        let request = Request::Discard {
+            hand,
+            trump: *trump,
+        };
+        self.ask(request, |encoding, reply| {
+            encoding.card(reply).and_then(|card| {
+                if hand.cards.contains(&card) {
+                    Ok(card)
+                } else {
+                    Err(format!("{} is not in the hand", card))
+                }
+            })
+        })
+        .unwrap_or_else(|| self.fallback.choose_discard(hand, trump))
+    }
+
+    fn play_card(
+        &mut self,
+        hand: &Hand,
+        bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        let request = Request::PlayCard {
+            hand,
+            bid_result,
+            cards_played,
+        };
+        self.ask(request, |encoding, reply| {
+            encoding.card_in_hand(reply, hand)
+        })
+        .unwrap_or_else(|| self.fallback.play_card(hand, bid_result, cards_played))
+    }
+
+    fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
+        self.tell(Request::TrickEnd {
+            bid_result,
+            cards_played,
+        });
+    }
+}
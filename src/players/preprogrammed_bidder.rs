@@ -82,7 +82,6 @@ impl PreprogrammedBidder {
     }
 
     pub(crate) fn discards(card: CardBeforeBidding) -> PreprogrammedBidder {
-        #![allow(unused)]
         PreprogrammedBidder {
             order_up: false,
             order_up_alone: false,
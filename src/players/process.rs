@@ -0,0 +1,240 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    players::line::{Encoding, LinePlayer, Transport},
+    position::Position,
+};
+
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) type ProcessPlayer = LinePlayer<Bot>;
+
+pub(crate) struct Bot {
+    command: String,
+    child: Child,
+    stdin: ChildStdin,
+    replies: Receiver<String>,
+    timeout: Duration,
+}
+
+impl ProcessPlayer {
+    pub(crate) fn create(
+        command: &str,
+        position: Position,
+        timeout: Duration,
+    ) -> io::Result<ProcessPlayer> {
+        let words = shell_words::split(command)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        let (program, args) = words
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Empty bot command"))?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, replies) = mpsc::channel();
+        let _reader = thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let bot = Bot {
+            command: command.to_string(),
+            child,
+            stdin,
+            replies,
+            timeout,
+        };
+        Ok(LinePlayer::create_with_transport(
+            bot,
+            Encoding::Text,
+            position,
+        ))
+    }
+}
+
+impl Transport for Bot {
+    fn name(&self) -> &str {
+        &self.command
+    }
+
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{}", line)?;
+        self.stdin.flush()
+    }
+
+    fn unrequested(&mut self) -> Option<String> {
+        self.replies.try_recv().ok()
+    }
+
+    fn receive(&mut self) -> Result<String, String> {
+        self.replies
+            .recv_timeout(self.timeout)
+            .map_err(|error| error.to_string())
+    }
+
+    fn close(&mut self) {
+        if let Err(error) = self.child.kill() {
+            log::debug!("Couldn't stop {}: {}", self.command, error);
+        }
+        if let Err(error) = self.child.wait() {
+            log::debug!("Couldn't wait for {}: {}", self.command, error);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{
+        bid_result::BidResultCalled,
+        card::{Card, CardBeforeBidding},
+        hand::HandBeforeBidding,
+        player::Player,
+        rank::Rank,
+        rank_with_bowers::RankWithBowers,
+        suit::Suit,
+    };
+    use std::{env, fs, str::FromStr};
+
+    fn write_bot(name: &str, script: &str) -> String {
+        let path = env::temp_dir().join(format!("euchre-{}-{}.sh", name, std::process::id()));
+        fs::write(&path, script).unwrap();
+        format!("sh {}", path.display())
+    }
+
+    fn hand() -> HandBeforeBidding {
+        HandBeforeBidding {
+            cards: ["9H", "10H", "JD", "AS", "QC"]
+                .iter()
+                .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                .collect(),
+        }
+    }
+
+    fn trump_candidate() -> CardBeforeBidding {
+        CardBeforeBidding {
+            suit: Suit::Hearts,
+            rank: Rank::Jack,
+        }
+    }
+
+    #[test]
+    fn follows_bot_replies() {
+        let command = write_bot(
+            "follows",
+            "while read request rest; do\n\
+             case $request in\n\
+             order_up) echo yes ;;\n\
+             call_trump) echo S ;;\n\
+             discard) echo QC ;;\n\
+             play) echo JD ;;\n\
+             seat|trick_end) ;;\n\
+             *) echo no ;;\n\
+             esac\n\
+             done\n",
+        );
+        let mut player =
+            ProcessPlayer::create(&command, Position::South, Duration::from_secs(5)).unwrap();
+        assert!(player.should_order_up(&hand(), &Position::North, &trump_candidate()));
+        assert!(!player.should_order_up_alone(&hand(), &Position::North, &trump_candidate()));
+        assert_eq!(
+            Some(Suit::Spades),
+            player.call_trump(&hand(), &Position::North, &trump_candidate())
+        );
+        assert_eq!(
+            CardBeforeBidding::from_str("QC").unwrap(),
+            player.choose_discard(&hand(), &Suit::Hearts)
+        );
+        let bid_result = BidResultCalled::Called {
+            trump: Suit::Hearts,
+            caller: Position::South,
+        };
+        let hand = HandBeforeBidding::update_bowers(hand(), &Suit::Hearts);
+        player.trick_end(&bid_result, &[]);
+        assert_eq!(
+            Card {
+                suit: Suit::Hearts,
+                rank: RankWithBowers::LeftBower
+            },
+            player.play_card(&hand, &bid_result, &[])
+        );
+    }
+
+    #[test]
+    fn invalid_reply_uses_default() {
+        let mut player =
+            ProcessPlayer::create("cat", Position::South, Duration::from_secs(5)).unwrap();
+        assert!(!player.should_order_up(&hand(), &Position::North, &trump_candidate()));
+        assert_eq!(
+            hand().cards[0],
+            player.choose_discard(&hand(), &Suit::Hearts)
+        );
+    }
+
+    #[test]
+    fn timeout_uses_default() {
+        let command = write_bot("silent", "while read request; do :; done\n");
+        let mut player =
+            ProcessPlayer::create(&command, Position::South, Duration::from_millis(100)).unwrap();
+        assert_eq!(
+            None,
+            player.call_trump(&hand(), &Position::North, &trump_candidate())
+        );
+        assert!(player.failed());
+    }
+
+    #[test]
+    fn unrequested_reply_uses_default() {
+        let command = write_bot(
+            "chatty",
+            "while read request rest; do\n\
+             case $request in\n\
+             seat) ;;\n\
+             *) echo yes ;;\n\
+             esac\n\
+             done\n",
+        );
+        let mut player =
+            ProcessPlayer::create(&command, Position::South, Duration::from_secs(5)).unwrap();
+        let bid_result = BidResultCalled::Called {
+            trump: Suit::Hearts,
+            caller: Position::South,
+        };
+        player.trick_end(&bid_result, &[]);
+        thread::sleep(Duration::from_millis(200));
+        assert!(!player.should_order_up(&hand(), &Position::North, &trump_candidate()));
+        assert!(player.failed());
+    }
+
+    #[test]
+    fn quoted_arguments() {
+        let path = env::temp_dir().join(format!("euchre quoted {}.sh", std::process::id()));
+        fs::write(&path, "while read request rest; do echo yes; done\n").unwrap();
+        let command = format!("sh '{}'", path.display());
+        let mut player =
+            ProcessPlayer::create(&command, Position::South, Duration::from_secs(5)).unwrap();
+        assert!(player.should_order_up(&hand(), &Position::North, &trump_candidate()));
+    }
+
+    #[test]
+    fn missing_program_is_an_error() {
+        assert!(
+            ProcessPlayer::create("/nonexistent/euchre-bot", Position::South, DEFAULT_TIMEOUT)
+                .is_err()
+        );
+        assert!(ProcessPlayer::create("", Position::South, DEFAULT_TIMEOUT).is_err());
+        assert!(ProcessPlayer::create("sh 'bot.sh", Position::South, DEFAULT_TIMEOUT).is_err());
+    }
+}
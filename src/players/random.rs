@@ -0,0 +1,348 @@
+use std::{fs, io, path::Path};
+
+use enum_iterator::IntoEnumIterator;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::{
+    auction_state::{AuctionBid, Contract, Strain, MINIMUM_BID},
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    position::Position,
+    shared_files::SharedFiles,
+    suit::Suit,
+    trick_state::{PlayedCard, TrickState},
+};
+
+// A config file is a JSON object with any of these probabilities, such as
+// `{"order_up": 0.5}`. Missing fields keep their default values.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct RandomPlayerConfig {
+    pub(crate) order_up: f64,
+    pub(crate) call_trump: f64,
+    pub(crate) go_alone: f64,
+    pub(crate) defend_alone: f64,
+    pub(crate) bid_auction: f64,
+}
+
+#[derive(Clone)]
+pub(crate) struct RandomPlayer {
+    config: RandomPlayerConfig,
+    rng: StdRng,
+}
+
+impl Default for RandomPlayerConfig {
+    fn default() -> Self {
+        RandomPlayerConfig {
+            order_up: 0.25,
+            call_trump: 0.25,
+            go_alone: 0.05,
+            defend_alone: 0.05,
+            bid_auction: 0.25,
+        }
+    }
+}
+
+impl RandomPlayerConfig {
+    pub(crate) fn load(path: &Path) -> io::Result<RandomPlayerConfig> {
+        RandomPlayerConfig::parse(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub(crate) fn load_shared(path: &str) -> io::Result<RandomPlayerConfig> {
+        static CONFIGS: SharedFiles<RandomPlayerConfig> = SharedFiles::new();
+        CONFIGS.load(path, RandomPlayerConfig::load)
+    }
+
+    fn parse(json: &str) -> Result<RandomPlayerConfig, String> {
+        let config: RandomPlayerConfig =
+            serde_json::from_str(json).map_err(|error| error.to_string())?;
+        let probabilities = [
+            config.order_up,
+            config.call_trump,
+            config.go_alone,
+            config.defend_alone,
+            config.bid_auction,
+        ];
+        if probabilities
+            .iter()
+            .any(|probability| !(0.0..=1.0).contains(probability))
+        {
+            return Err("Probabilities must be between 0 and 1".to_string());
+        }
+        Ok(config)
+    }
+}
+
+impl RandomPlayer {
+    pub(crate) fn create(seed: u64) -> RandomPlayer {
+        RandomPlayer::create_with_config(seed, RandomPlayerConfig::default())
+    }
+
+    pub(crate) fn create_with_config(seed: u64, config: RandomPlayerConfig) -> RandomPlayer {
+        RandomPlayer {
+            config,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn random_legal_card(&mut self, hand: &Hand, cards_played: &[PlayedCard]) -> Card {
+        *TrickState::legal_cards(hand, cards_played)
+            .choose(&mut self.rng)
+            .unwrap()
+    }
+}
+
+impl Player for RandomPlayer {
+    fn should_order_up(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        _trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.rng.gen_bool(self.config.order_up)
+    }
+
+    fn should_order_up_alone(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        _trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.rng.gen_bool(self.config.go_alone)
+    }
+
+    fn should_defend_alone_ordered(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        _trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.rng.gen_bool(self.config.defend_alone)
+    }
+
+    fn call_trump(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Option<Suit> {
+        if !self.rng.gen_bool(self.config.call_trump) {
+            return None;
+        }
+        let suits: Vec<Suit> = Suit::into_enum_iter()
+            .filter(|suit| *suit != turned_down.suit)
+            .collect();
+        suits.choose(&mut self.rng).copied()
+    }
+
+    fn should_call_alone(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        _trump: &Suit,
+        _turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.rng.gen_bool(self.config.go_alone)
+    }
+
+    fn should_defend_alone_called(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        _trump: &Suit,
+        _turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.rng.gen_bool(self.config.defend_alone)
+    }
+
+    fn choose_joker_suit(&mut self, _hand: &HandBeforeBidding, _dealer: &Position) -> Suit {
+        let suits: Vec<Suit> = Suit::into_enum_iter().collect();
+        *suits.choose(&mut self.rng).unwrap()
+    }
+
+    fn choose_discard(&mut self, hand: &HandBeforeBidding, _trump: &Suit) -> CardBeforeBidding {
+        *hand.cards.choose(&mut self.rng).unwrap()
+    }
+
+    fn play_card(
+        &mut self,
+        hand: &Hand,
+        _bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        self.random_legal_card(hand, cards_played)
+    }
+
+    fn bid_auction(
+        &mut self,
+        _hand: &HandBeforeBidding,
+        _dealer: &Position,
+        high_bid: Option<&Contract>,
+    ) -> Option<AuctionBid> {
+        if !self.rng.gen_bool(self.config.bid_auction) {
+            return None;
+        }
+        let strains: Vec<Strain> = Strain::all().collect();
+        let strain = *strains.choose(&mut self.rng).unwrap();
+        (MINIMUM_BID..=6)
+            .map(|tricks| AuctionBid::Tricks { tricks, strain })
+            .chain(std::iter::once(AuctionBid::ShootTheMoon { strain }))
+            .find(|bid| bid.is_valid_over(high_bid))
+    }
+
+    fn play_card_in_contract(
+        &mut self,
+        hand: &Hand,
+        _contract: &Contract,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        self.random_legal_card(hand, cards_played)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rank::Rank, rank_with_bowers::RankWithBowers};
+
+    fn hand() -> Hand {
+        Hand {
+            cards: vec![
+                Card {
+                    suit: Suit::Hearts,
+                    rank: RankWithBowers::LeftBower,
+                },
+                Card {
+                    suit: Suit::Diamonds,
+                    rank: RankWithBowers::Ace,
+                },
+                Card {
+                    suit: Suit::Spades,
+                    rank: RankWithBowers::Nine,
+                },
+            ],
+        }
+    }
+
+    fn led(suit: Suit, rank: RankWithBowers) -> Vec<PlayedCard> {
+        vec![PlayedCard {
+            player: Position::North,
+            card: Card { suit, rank },
+        }]
+    }
+
+    #[test]
+    fn follows_suit_with_left_bower() {
+        let bid_result = BidResultCalled::Called {
+            trump: Suit::Hearts,
+            caller: Position::North,
+        };
+        let cards_played = led(Suit::Hearts, RankWithBowers::Ace);
+        let mut player = RandomPlayer::create(7);
+        for _ in 0..20 {
+            assert_eq!(
+                hand().cards[0],
+                player.play_card(&hand(), &bid_result, &cards_played)
+            );
+        }
+    }
+
+    #[test]
+    fn plays_any_card_when_void() {
+        let bid_result = BidResultCalled::Called {
+            trump: Suit::Hearts,
+            caller: Position::North,
+        };
+        let cards_played = led(Suit::Clubs, RankWithBowers::Ace);
+        let mut player = RandomPlayer::create(7);
+        let mut played: Vec<Card> = (0..50)
+            .map(|_| player.play_card(&hand(), &bid_result, &cards_played))
+            .collect();
+        played.sort_by_key(|card| card.rank);
+        played.dedup();
+        assert_eq!(3, played.len());
+    }
+
+    #[test]
+    fn same_seed_makes_same_choices() {
+        let trump_candidate = CardBeforeBidding {
+            suit: Suit::Clubs,
+            rank: Rank::Nine,
+        };
+        let hand = HandBeforeBidding { cards: Vec::new() };
+        let mut first = RandomPlayer::create(42);
+        let mut second = RandomPlayer::create(42);
+        for _ in 0..20 {
+            assert_eq!(
+                first.should_order_up(&hand, &Position::North, &trump_candidate),
+                second.should_order_up(&hand, &Position::North, &trump_candidate)
+            );
+            assert_eq!(
+                first.call_trump(&hand, &Position::North, &trump_candidate),
+                second.call_trump(&hand, &Position::North, &trump_candidate)
+            );
+        }
+    }
+
+    #[test]
+    fn never_calls_turned_down_suit() {
+        let config = RandomPlayerConfig {
+            call_trump: 1.0,
+            ..Default::default()
+        };
+        let trump_candidate = CardBeforeBidding {
+            suit: Suit::Clubs,
+            rank: Rank::Nine,
+        };
+        let hand = HandBeforeBidding { cards: Vec::new() };
+        let mut player = RandomPlayer::create_with_config(1, config);
+        for _ in 0..20 {
+            let trump = player.call_trump(&hand, &Position::North, &trump_candidate);
+            assert!(trump.is_some());
+            assert_ne!(Some(Suit::Clubs), trump);
+        }
+    }
+
+    #[test]
+    fn auction_bids_are_valid() {
+        let config = RandomPlayerConfig {
+            bid_auction: 1.0,
+            ..Default::default()
+        };
+        let hand = HandBeforeBidding { cards: Vec::new() };
+        let high_bid = Contract {
+            bidder: Position::North,
+            bid: AuctionBid::Tricks {
+                tricks: 5,
+                strain: Strain::High,
+            },
+        };
+        let mut player = RandomPlayer::create_with_config(3, config);
+        for _ in 0..20 {
+            let bid = player
+                .bid_auction(&hand, &Position::North, Some(&high_bid))
+                .unwrap();
+            assert!(bid.is_valid_over(Some(&high_bid)));
+        }
+    }
+
+    #[test]
+    fn parse_config() {
+        assert_eq!(
+            Ok(RandomPlayerConfig {
+                order_up: 0.5,
+                ..Default::default()
+            }),
+            RandomPlayerConfig::parse(r#"{"order_up": 0.5}"#)
+        );
+        assert_eq!(
+            Err("Probabilities must be between 0 and 1".to_string()),
+            RandomPlayerConfig::parse(r#"{"go_alone": 1.5}"#)
+        );
+        assert!(RandomPlayerConfig::parse(r#"{"order": 0.5}"#).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    game_record::{Choice, Recorder},
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    position::Position,
+    suit::Suit,
+    trick_state::PlayedCard,
+};
+
+#[derive(Clone)]
+pub(crate) struct RecordingPlayer {
+    position: Position,
+    player: Box<dyn Player>,
+    recorder: Recorder,
+}
+
+impl RecordingPlayer {
+    pub(crate) fn create(
+        position: Position,
+        player: Box<dyn Player>,
+        recorder: Recorder,
+    ) -> RecordingPlayer {
+        RecordingPlayer {
+            position,
+            player,
+            recorder,
+        }
+    }
+}
+
+impl Player for RecordingPlayer {
+    fn should_order_up(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        let yes = self.player.should_order_up(hand, dealer, trump_candidate);
+        self.recorder
+            .record(&self.position, Choice::OrderUp { yes });
+        yes
+    }
+
+    fn should_order_up_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        let yes = self
+            .player
+            .should_order_up_alone(hand, dealer, trump_candidate);
+        self.recorder
+            .record(&self.position, Choice::OrderUpAlone { yes });
+        yes
+    }
+
+    fn should_defend_alone_ordered(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        let yes = self
+            .player
+            .should_defend_alone_ordered(hand, dealer, trump_candidate);
+        self.recorder
+            .record(&self.position, Choice::DefendAloneOrdered { yes });
+        yes
+    }
+
+    fn call_trump(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Option<Suit> {
+        let trump = self.player.call_trump(hand, dealer, turned_down);
+        self.recorder
+            .record(&self.position, Choice::call(trump.as_ref()));
+        trump
+    }
+
+    fn should_call_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        let yes = self
+            .player
+            .should_call_alone(hand, dealer, trump, turned_down);
+        self.recorder
+            .record(&self.position, Choice::CallAlone { yes });
+        yes
+    }
+
+    fn should_defend_alone_called(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        let yes = self
+            .player
+            .should_defend_alone_called(hand, dealer, trump, turned_down);
+        self.recorder
+            .record(&self.position, Choice::DefendAloneCalled { yes });
+        yes
+    }
+
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Suit {
+        let suit = self.player.choose_joker_suit(hand, dealer);
+        self.recorder
+            .record(&self.position, Choice::joker_suit(&suit));
+        suit
+    }
+
+    fn choose_discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> CardBeforeBidding {
+        let card = self.player.choose_discard(hand, trump);
+        self.recorder.record(&self.position, Choice::discard(&card));
+        card
+    }
+
+    fn play_card(
+        &mut self,
+        hand: &Hand,
+        bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        let card = self.player.play_card(hand, bid_result, cards_played);
+        self.recorder
+            .record(&self.position, Choice::play(&card.into()));
+        card
+    }
+
+    fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
+        self.player.trick_end(bid_result, cards_played);
+    }
+}
@@ -0,0 +1,168 @@
+use std::{fmt, str::FromStr};
+
+use rand::{thread_rng, Rng};
+
+use crate::{
+    bid_table::BidTable,
+    player::Player,
+    players::{
+        advanced::{self, AdvancedConfig, AdvancedPlayer},
+        basic::{self, BasicPlayer},
+        human::HumanPlayer,
+        process::{ProcessPlayer, DEFAULT_TIMEOUT},
+        random::{RandomPlayer, RandomPlayerConfig},
+        table::TablePlayer,
+        wrapper::Wrapper,
+    },
+    position::Position,
+    rules::Rules,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum Strategy {
+    Basic,
+    Advanced,
+    AdvancedWithConfig(String),
+    Random,
+    SeededRandom { seed: u64, config: Option<String> },
+    Human,
+    Process(String),
+    Table(String),
+}
+
+impl Strategy {
+    pub(crate) fn all() -> [Strategy; 4] {
+        [
+            Strategy::Basic,
+            Strategy::Advanced,
+            Strategy::Random,
+            Strategy::Human,
+        ]
+    }
+
+    pub(crate) fn create_player(&self, position: Position, rules: Rules) -> Box<dyn Player> {
+        match self {
+            Self::Basic => Box::new(BasicPlayer { position }),
+            Self::Advanced => Box::new(AdvancedPlayer::create_with_rules(position, rules)),
+            Self::AdvancedWithConfig(path) => {
+                let config = AdvancedConfig::load_shared(path)
+                    .unwrap_or_else(|error| panic!("Couldn't load {}: {}", path, error));
+                Box::new(AdvancedPlayer::create_with_config(position, rules, config))
+            }
+            Self::Random => Box::new(RandomPlayer::create(thread_rng().gen())),
+            Self::SeededRandom { seed, config } => {
+                let config = match config {
+                    Some(path) => RandomPlayerConfig::load_shared(path)
+                        .unwrap_or_else(|error| panic!("Couldn't load {}: {}", path, error)),
+                    None => RandomPlayerConfig::default(),
+                };
+                Box::new(RandomPlayer::create_with_config(
+                    seed.wrapping_add(position.index() as u64),
+                    config,
+                ))
+            }
+            Self::Human => Box::new(HumanPlayer::create(position, rules)),
+            Self::Process(command) => Box::new(
+                ProcessPlayer::create(command, position, DEFAULT_TIMEOUT)
+                    .unwrap_or_else(|error| panic!("Couldn't start {}: {}", command, error)),
+            ),
+            Self::Table(path) => {
+                let table = BidTable::load_shared(path)
+                    .unwrap_or_else(|error| panic!("Couldn't load {}: {}", path, error));
+                if table.rules() != rules {
+                    panic!("{} was generated for different rules", path);
+                }
+                Box::new(TablePlayer::create(position, rules, table))
+            }
+        }
+    }
+
+    pub(crate) fn version(&self) -> Option<u32> {
+        match self {
+            Self::Basic => Some(basic::VERSION),
+            Self::Advanced => Some(advanced::VERSION),
+            Self::AdvancedWithConfig(_)
+            | Self::Random
+            | Self::SeededRandom { .. }
+            | Self::Human
+            | Self::Process(_)
+            | Self::Table(_) => None,
+        }
+    }
+
+    pub(crate) fn create(&self, position: Position, rules: Rules) -> Wrapper {
+        Wrapper::create_single_player(self.create_player(position, rules))
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = name.strip_prefix("advanced:") {
+            return Ok(Self::AdvancedWithConfig(path.to_string()));
+        }
+        if let Some(options) = name.strip_prefix("random:") {
+            let (seed, config) = match options.split_once(':') {
+                Some((seed, path)) => (seed, Some(path.to_string())),
+                None => (options, None),
+            };
+            return seed
+                .parse()
+                .map(|seed| Self::SeededRandom { seed, config })
+                .map_err(|_| format!("Invalid random seed: {}", seed));
+        }
+        if let Some(command) = name.strip_prefix("process:") {
+            return Ok(Self::Process(command.to_string()));
+        }
+        if let Some(path) = name.strip_prefix("table:") {
+            return Ok(Self::Table(path.to_string()));
+        }
+        Strategy::all()
+            .into_iter()
+            .find(|strategy| strategy.to_string() == name.to_lowercase())
+            .ok_or_else(|| format!("Invalid strategy: {}", name))
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Basic => "basic",
+            Self::Advanced => "advanced",
+            Self::Random => "random",
+            Self::Human => "human",
+            Self::AdvancedWithConfig(path) => return write!(f, "advanced:{}", path),
+            Self::SeededRandom { seed, config } => {
+                return match config {
+                    Some(path) => write!(f, "random:{}:{}", seed, path),
+                    None => write!(f, "random:{}", seed),
+                }
+            }
+            Self::Process(command) => return write!(f, "process:{}", command),
+            Self::Table(path) => return write!(f, "table:{}", path),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("basic" => Ok(Strategy::Basic))]
+    #[test_case("Advanced" => Ok(Strategy::Advanced))]
+    #[test_case("advanced:bold.json" => Ok(Strategy::AdvancedWithConfig("bold.json".to_string())))]
+    #[test_case("random" => Ok(Strategy::Random))]
+    #[test_case("random:42" => Ok(Strategy::SeededRandom { seed: 42, config: None }))]
+    #[test_case("random:42:wild.json" => Ok(Strategy::SeededRandom { seed: 42, config: Some("wild.json".to_string()) }))]
+    #[test_case("random:wild.json" => Err("Invalid random seed: wild.json".to_string()))]
+    #[test_case("human" => Ok(Strategy::Human))]
+    #[test_case("process:./bot --fast" => Ok(Strategy::Process("./bot --fast".to_string())))]
+    #[test_case("table:bids.bin" => Ok(Strategy::Table("bids.bin".to_string())))]
+    #[test_case("expert" => Err("Invalid strategy: expert".to_string()))]
+    fn from_str(name: &str) -> Result<Strategy, String> {
+        name.parse()
+    }
+}
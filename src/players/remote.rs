@@ -0,0 +1,86 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpStream},
+    time::Duration,
+};
+
+use crate::{
+    players::line::{Encoding, LinePlayer, Transport},
+    position::Position,
+};
+
+pub(crate) type RemotePlayer = LinePlayer<Connection>;
+
+pub(crate) struct Connection {
+    peer: String,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl RemotePlayer {
+    pub(crate) fn create(
+        stream: TcpStream,
+        position: Position,
+        timeout: Duration,
+    ) -> io::Result<RemotePlayer> {
+        stream.set_read_timeout(Some(timeout))?;
+        let connection = Connection {
+            peer: stream.peer_addr()?.to_string(),
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        };
+        Ok(LinePlayer::create_with_transport(
+            connection,
+            Encoding::Json,
+            position,
+        ))
+    }
+}
+
+impl Connection {
+    fn waiting(&mut self) -> io::Result<Option<String>> {
+        if self.reader.buffer().is_empty() {
+            self.writer.set_nonblocking(true)?;
+            let filled = match self.reader.fill_buf() {
+                Ok(_) => Ok(()),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => Ok(()),
+                Err(error) => Err(error),
+            };
+            self.writer.set_nonblocking(false)?;
+            filled?;
+        }
+        let buffer = self.reader.buffer();
+        Ok((!buffer.is_empty()).then(|| String::from_utf8_lossy(buffer).trim().to_string()))
+    }
+}
+
+impl Transport for Connection {
+    fn name(&self) -> &str {
+        &self.peer
+    }
+
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()
+    }
+
+    fn unrequested(&mut self) -> Option<String> {
+        self.waiting()
+            .unwrap_or_else(|error| Some(error.to_string()))
+    }
+
+    fn receive(&mut self) -> Result<String, String> {
+        let mut reply = String::new();
+        match self.reader.read_line(&mut reply) {
+            Ok(0) => Err("disconnected".to_string()),
+            Ok(_) => Ok(reply),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    fn close(&mut self) {
+        if let Err(error) = self.writer.shutdown(Shutdown::Both) {
+            log::debug!("Couldn't disconnect {}: {}", self.peer, error);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::sync::{Arc, Mutex};
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    game_record::{Choice, Decision},
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    players::advanced::AdvancedPlayer,
+    position::Position,
+    protocol,
+    rules::Rules,
+    suit::Suit,
+    trick_state::{PlayedCard, TrickState},
+};
+
+#[derive(Debug)]
+pub(crate) struct Branch {
+    decisions: Vec<Decision>,
+    next: usize,
+    at: usize,
+    option: usize,
+    pub(crate) options: Vec<Choice>,
+    pub(crate) played: Vec<(Position, CardBeforeBidding)>,
+    pub(crate) voids: Vec<(Position, Suit, Suit)>,
+    pub(crate) diverged: bool,
+}
+
+#[derive(Clone)]
+pub(crate) struct ReplayPlayer {
+    position: Position,
+    player: AdvancedPlayer,
+    branch: Arc<Mutex<Branch>>,
+}
+
+impl Branch {
+    pub(crate) fn create(decisions: Vec<Decision>, at: usize, option: usize) -> Branch {
+        Branch {
+            decisions,
+            next: 0,
+            at,
+            option,
+            options: Vec::new(),
+            played: Vec::new(),
+            voids: Vec::new(),
+            diverged: false,
+        }
+    }
+}
+
+impl ReplayPlayer {
+    pub(crate) fn create(
+        position: Position,
+        rules: Rules,
+        branch: Arc<Mutex<Branch>>,
+    ) -> ReplayPlayer {
+        ReplayPlayer {
+            position,
+            player: AdvancedPlayer::create_with_rules(position, rules),
+            branch,
+        }
+    }
+
+    fn decide(&self, options: Vec<Choice>) -> Option<Choice> {
+        let mut branch = self.branch.lock().unwrap();
+        let index = branch.next;
+        branch.next += 1;
+        match index.cmp(&branch.at) {
+            std::cmp::Ordering::Less => {
+                let choice = branch
+                    .decisions
+                    .get(index)
+                    .map(|decision| decision.choice.clone());
+                if let Some(choice) = &choice {
+                    if !options.contains(choice) && !matches!(choice, Choice::Discard { .. }) {
+                        branch.diverged = true;
+                    }
+                }
+                choice
+            }
+            std::cmp::Ordering::Equal => {
+                branch.options = options;
+                branch.options.get(branch.option).cloned()
+            }
+            std::cmp::Ordering::Greater => None,
+        }
+    }
+
+    fn unplayed_card(&self, hand: &HandBeforeBidding) -> Option<CardBeforeBidding> {
+        let branch = self.branch.lock().unwrap();
+        let played: Vec<&Choice> = branch.decisions[branch.next..]
+            .iter()
+            .filter(|decision| decision.position() == Ok(self.position))
+            .map(|decision| &decision.choice)
+            .collect();
+        hand.cards
+            .iter()
+            .find(|card| !played.contains(&&Choice::play(card)))
+            .copied()
+    }
+
+    fn decide_yes_no(&self, choice: fn(bool) -> Choice) -> Option<bool> {
+        let decision = self.decide(vec![choice(false), choice(true)]);
+        [false, true]
+            .into_iter()
+            .find(|yes| decision.as_ref() == Some(&choice(*yes)))
+    }
+}
+
+impl Player for ReplayPlayer {
+    fn should_order_up(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.decide_yes_no(|yes| Choice::OrderUp { yes })
+            .unwrap_or_else(|| self.player.should_order_up(hand, dealer, trump_candidate))
+    }
+
+    fn should_order_up_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.decide_yes_no(|yes| Choice::OrderUpAlone { yes })
+            .unwrap_or_else(|| {
+                self.player
+                    .should_order_up_alone(hand, dealer, trump_candidate)
+            })
+    }
+
+    fn should_defend_alone_ordered(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.decide_yes_no(|yes| Choice::DefendAloneOrdered { yes })
+            .unwrap_or_else(|| {
+                self.player
+                    .should_defend_alone_ordered(hand, dealer, trump_candidate)
+            })
+    }
+
+    fn call_trump(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Option<Suit> {
+        let mut options = vec![None];
+        options.extend(
+            Suit::into_enum_iter()
+                .filter(|suit| *suit != turned_down.suit)
+                .map(Some),
+        );
+        let decision = self.decide(
+            options
+                .iter()
+                .map(|suit| Choice::call(suit.as_ref()))
+                .collect(),
+        );
+        match options
+            .into_iter()
+            .find(|suit| decision.as_ref() == Some(&Choice::call(suit.as_ref())))
+        {
+            Some(suit) => suit,
+            None => self.player.call_trump(hand, dealer, turned_down),
+        }
+    }
+
+    fn should_call_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.decide_yes_no(|yes| Choice::CallAlone { yes })
+            .unwrap_or_else(|| {
+                self.player
+                    .should_call_alone(hand, dealer, trump, turned_down)
+            })
+    }
+
+    fn should_defend_alone_called(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.decide_yes_no(|yes| Choice::DefendAloneCalled { yes })
+            .unwrap_or_else(|| {
+                self.player
+                    .should_defend_alone_called(hand, dealer, trump, turned_down)
+            })
+    }
+
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Suit {
+        let suits: Vec<Suit> = Suit::into_enum_iter().collect();
+        let decision = self.decide(suits.iter().map(Choice::joker_suit).collect());
+        match suits
+            .into_iter()
+            .find(|suit| decision.as_ref() == Some(&Choice::joker_suit(suit)))
+        {
+            Some(suit) => suit,
+            None => self.player.choose_joker_suit(hand, dealer),
+        }
+    }
+
+    fn choose_discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> CardBeforeBidding {
+        let decision = self.decide(hand.cards.iter().map(Choice::discard).collect());
+        match hand
+            .cards
+            .iter()
+            .find(|card| decision.as_ref() == Some(&Choice::discard(card)))
+        {
+            Some(card) => *card,
+            None if decision.is_some() => self
+                .unplayed_card(hand)
+                .unwrap_or_else(|| self.player.choose_discard(hand, trump)),
+            None => self.player.choose_discard(hand, trump),
+        }
+    }
+
+    fn play_card(
+        &mut self,
+        hand: &Hand,
+        bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        let legal_cards = TrickState::legal_cards(hand, cards_played);
+        let decision = self.decide(
+            legal_cards
+                .iter()
+                .map(|card| Choice::play(&(*card).into()))
+                .collect(),
+        );
+        let card = match decision {
+            Some(Choice::Play { card }) => protocol::parse_card_in_hand(&card, hand)
+                .ok()
+                .filter(|card| legal_cards.contains(card)),
+            _ => None,
+        }
+        .unwrap_or_else(|| self.player.play_card(hand, bid_result, cards_played));
+        let mut branch = self.branch.lock().unwrap();
+        if branch.next <= branch.at {
+            branch.played.push((self.position, card.into()));
+            if let Some(led_card) = cards_played.first() {
+                if card.suit != led_card.card.suit {
+                    branch
+                        .voids
+                        .push((self.position, led_card.card.suit, bid_result.trump()));
+                }
+            }
+        }
+        card
+    }
+
+    fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
+        self.player.trick_end(bid_result, cards_played);
+    }
+}
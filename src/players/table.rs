@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use crate::{
+    auction_state::{AuctionBid, Contract},
+    bid_result::BidResultCalled,
+    bid_table::{BidTable, BidValues},
+    card::{Card, CardBeforeBidding},
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    players::advanced::AdvancedPlayer,
+    position::Position,
+    rules::Rules,
+    suit::Suit,
+    trick_state::PlayedCard,
+};
+
+#[derive(Clone)]
+pub(crate) struct TablePlayer {
+    position: Position,
+    table: Arc<BidTable>,
+    fallback: AdvancedPlayer,
+}
+
+impl TablePlayer {
+    pub(crate) fn create(position: Position, rules: Rules, table: Arc<BidTable>) -> TablePlayer {
+        TablePlayer {
+            position,
+            table,
+            fallback: AdvancedPlayer::create_with_rules(position, rules),
+        }
+    }
+
+    fn lookup(
+        &self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        up_card: &CardBeforeBidding,
+    ) -> Option<BidValues> {
+        self.table.lookup(hand, up_card, *dealer, self.position)
+    }
+}
+
+impl Player for TablePlayer {
+    fn should_order_up(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        match self.lookup(hand, dealer, trump_candidate) {
+            Some(values) => values.order_up.max(values.order_up_alone) > 0.0,
+            None => self.fallback.should_order_up(hand, dealer, trump_candidate),
+        }
+    }
+
+    fn should_order_up_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        match self.lookup(hand, dealer, trump_candidate) {
+            Some(values) => values.order_up_alone > values.order_up,
+            None => self
+                .fallback
+                .should_order_up_alone(hand, dealer, trump_candidate),
+        }
+    }
+
+    fn should_defend_alone_ordered(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.fallback
+            .should_defend_alone_ordered(hand, dealer, trump_candidate)
+    }
+
+    fn call_trump(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Option<Suit> {
+        match self.lookup(hand, dealer, turned_down) {
+            Some(values) => values
+                .calls
+                .iter()
+                .map(|call| (call.trump, call.call.max(call.call_alone)))
+                .filter(|(_, value)| *value > 0.0)
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(trump, _)| trump),
+            None => self.fallback.call_trump(hand, dealer, turned_down),
+        }
+    }
+
+    fn should_call_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        let call = self
+            .lookup(hand, dealer, turned_down)
+            .and_then(|values| values.calls.into_iter().find(|call| call.trump == *trump));
+        match call {
+            Some(call) => call.call_alone > call.call,
+            None => self
+                .fallback
+                .should_call_alone(hand, dealer, trump, turned_down),
+        }
+    }
+
+    fn should_defend_alone_called(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.fallback
+            .should_defend_alone_called(hand, dealer, trump, turned_down)
+    }
+
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Suit {
+        self.fallback.choose_joker_suit(hand, dealer)
+    }
+
+    fn choose_discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> CardBeforeBidding {
+        self.fallback.choose_discard(hand, trump)
+    }
+
+    fn play_card(
+        &mut self,
+        hand: &Hand,
+        bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        self.fallback.play_card(hand, bid_result, cards_played)
+    }
+
+    fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
+        self.fallback.trick_end(bid_result, cards_played)
+    }
+
+    fn bid_auction(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        high_bid: Option<&Contract>,
+    ) -> Option<AuctionBid> {
+        self.fallback.bid_auction(hand, dealer, high_bid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bid_table::TableKey;
+    use std::str::FromStr;
+
+    fn hand(names: [&str; 5]) -> HandBeforeBidding {
+        HandBeforeBidding {
+            cards: names
+                .iter()
+                .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                .collect(),
+        }
+    }
+
+    fn player(hand: &HandBeforeBidding, values: [i16; 8]) -> TablePlayer {
+        let (key, _) = TableKey::create(
+            hand,
+            &CardBeforeBidding::from_str("9S").unwrap(),
+            Position::West,
+            Position::South,
+        )
+        .unwrap();
+        let table = BidTable::create(Rules::default(), 100, vec![(key, values)]);
+        TablePlayer::create(Position::South, Rules::default(), Arc::new(table))
+    }
+
+    #[test]
+    fn bids_by_table_lookup() {
+        let hand = hand(["9C", "10C", "9H", "10D", "QD"]);
+        let up_card = CardBeforeBidding::from_str("9S").unwrap();
+        let mut player = player(&hand, [-50, 20, -10, -300, 80, -200, 90, 150]);
+        assert!(player.should_order_up(&hand, &Position::West, &up_card));
+        assert!(player.should_order_up_alone(&hand, &Position::West, &up_card));
+        assert_eq!(
+            Some(Suit::Diamonds),
+            player.call_trump(&hand, &Position::West, &up_card)
+        );
+        assert!(player.should_call_alone(&hand, &Position::West, &Suit::Diamonds, &up_card));
+        assert!(!player.should_call_alone(&hand, &Position::West, &Suit::Hearts, &up_card));
+    }
+
+    #[test]
+    fn falls_back_without_an_entry() {
+        let hand = hand(["9C", "10C", "9H", "10D", "QD"]);
+        let up_card = CardBeforeBidding::from_str("9S").unwrap();
+        let mut player = player(&hand, [400; 8]);
+        assert!(player.should_order_up(&hand, &Position::West, &up_card));
+        assert!(!player.should_order_up(&hand, &Position::North, &up_card));
+        assert_eq!(None, player.call_trump(&hand, &Position::North, &up_card));
+    }
+}
@@ -0,0 +1,212 @@
+use std::sync::{Arc, Mutex};
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    hand::{Hand, HandBeforeBidding},
+    hint::{Advisor, Hint},
+    player::Player,
+    players::line::Fallback,
+    position::Position,
+    rules::Rules,
+    suit::Suit,
+    trick_state::{PlayedCard, TrickState},
+    tui::Screen,
+};
+
+#[derive(Clone)]
+pub(crate) struct TuiPlayer {
+    position: Position,
+    screen: Arc<Mutex<Screen>>,
+    advisor: Advisor,
+    fallback: Fallback,
+}
+
+impl TuiPlayer {
+    pub(crate) fn create(
+        position: Position,
+        rules: Rules,
+        screen: Arc<Mutex<Screen>>,
+    ) -> TuiPlayer {
+        TuiPlayer {
+            position,
+            screen,
+            advisor: Advisor::create(position, rules),
+            fallback: Fallback,
+        }
+    }
+
+    fn ask_yes_no(
+        &mut self,
+        question: &str,
+        hint: impl FnOnce(&mut Advisor) -> Hint,
+    ) -> Option<bool> {
+        let advisor = &mut self.advisor;
+        self.screen
+            .lock()
+            .unwrap()
+            .ask_yes_no(&format!("{:?}: {}", self.position, question), || {
+                hint(advisor)
+            })
+    }
+
+    fn ask_choice<T: ToString>(
+        &mut self,
+        question: &str,
+        options: &[T],
+        hint: impl FnOnce(&mut Advisor) -> Hint,
+    ) -> Option<usize> {
+        let options: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+        let advisor = &mut self.advisor;
+        self.screen.lock().unwrap().ask_choice(
+            &format!("{:?}: {}", self.position, question),
+            &options,
+            || hint(advisor),
+        )
+    }
+}
+
+impl Player for TuiPlayer {
+    fn should_order_up(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.ask_yes_no(&format!("Order up {}?", trump_candidate), |advisor| {
+            advisor.order_up(hand, dealer, trump_candidate)
+        })
+        .unwrap_or_else(|| self.fallback.should_order_up(hand, dealer, trump_candidate))
+    }
+
+    fn should_order_up_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.ask_yes_no("Go alone?", |advisor| {
+            advisor.order_up(hand, dealer, trump_candidate)
+        })
+        .unwrap_or_else(|| {
+            self.fallback
+                .should_order_up_alone(hand, dealer, trump_candidate)
+        })
+    }
+
+    fn should_defend_alone_ordered(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump_candidate: &CardBeforeBidding,
+    ) -> bool {
+        self.ask_yes_no(
+            &format!(
+                "An opponent is going alone with {} as trump. Defend alone?",
+                trump_candidate.suit
+            ),
+            |advisor| advisor.defend_alone_ordered(hand, dealer, trump_candidate),
+        )
+        .unwrap_or_else(|| {
+            self.fallback
+                .should_defend_alone_ordered(hand, dealer, trump_candidate)
+        })
+    }
+
+    fn call_trump(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        turned_down: &CardBeforeBidding,
+    ) -> Option<Suit> {
+        let suits: Vec<Suit> = Suit::into_enum_iter()
+            .filter(|suit| *suit != turned_down.suit)
+            .collect();
+        let mut options: Vec<String> = suits.iter().map(|suit| suit.to_string()).collect();
+        options.push("Pass".to_string());
+        match self.ask_choice("Call trump", &options, |advisor| {
+            advisor.call_trump(hand, dealer, turned_down)
+        }) {
+            Some(index) => suits.get(index).copied(),
+            None => self.fallback.call_trump(hand, dealer, turned_down),
+        }
+    }
+
+    fn should_call_alone(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.ask_yes_no("Go alone?", |advisor| {
+            advisor.call_alone(hand, dealer, trump, turned_down)
+        })
+        .unwrap_or_else(|| {
+            self.fallback
+                .should_call_alone(hand, dealer, trump, turned_down)
+        })
+    }
+
+    fn should_defend_alone_called(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        trump: &Suit,
+        turned_down: &CardBeforeBidding,
+    ) -> bool {
+        self.ask_yes_no(
+            &format!(
+                "An opponent is going alone with {} as trump. Defend alone?",
+                trump
+            ),
+            |advisor| advisor.defend_alone_called(hand, dealer, trump, turned_down),
+        )
+        .unwrap_or_else(|| {
+            self.fallback
+                .should_defend_alone_called(hand, dealer, trump, turned_down)
+        })
+    }
+
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Suit {
+        let suits: Vec<Suit> = Suit::into_enum_iter().collect();
+        match self.ask_choice(
+            "The joker was turned up. Name its suit",
+            &suits,
+            |advisor| advisor.joker_suit(hand, dealer),
+        ) {
+            Some(index) => suits[index],
+            None => self.fallback.choose_joker_suit(hand, dealer),
+        }
+    }
+
+    fn choose_discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> CardBeforeBidding {
+        match self.ask_choice("Discard", &hand.cards, |advisor| {
+            advisor.discard(hand, trump)
+        }) {
+            Some(index) => hand.cards[index],
+            None => self.fallback.choose_discard(hand, trump),
+        }
+    }
+
+    fn play_card(
+        &mut self,
+        hand: &Hand,
+        bid_result: &BidResultCalled,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        let legal_cards = TrickState::legal_cards(hand, cards_played);
+        match self.ask_choice("Play", &legal_cards, |advisor| {
+            advisor.play_card(hand, bid_result, cards_played)
+        }) {
+            Some(index) => legal_cards[index],
+            None => self.fallback.play_card(hand, bid_result, cards_played),
+        }
+    }
+
+    fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
+        self.advisor.trick_end(bid_result, cards_played);
+    }
+}
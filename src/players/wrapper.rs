@@ -1,4 +1,5 @@
 use crate::{
+    auction_state::{AuctionBid, Contract},
     bid_result::BidResultCalled,
     card::{Card, CardBeforeBidding},
     hand::{Hand, HandBeforeBidding},
@@ -11,6 +12,7 @@ use crate::{
 #[derive(Clone)]
 pub(crate) struct Wrapper {
     bidder: Box<dyn Player>,
+    discarder: Option<Box<dyn Player>>,
     player: Box<dyn Player>,
 }
 
@@ -19,12 +21,29 @@ impl Wrapper {
         bidder: Box<dyn Player>,
         player: Box<dyn Player>,
     ) -> Wrapper {
-        Wrapper { bidder, player }
+        Wrapper {
+            bidder,
+            discarder: None,
+            player,
+        }
+    }
+
+    pub(crate) fn create_with_discarder(
+        bidder: Box<dyn Player>,
+        discarder: Box<dyn Player>,
+        player: Box<dyn Player>,
+    ) -> Wrapper {
+        Wrapper {
+            bidder,
+            discarder: Some(discarder),
+            player,
+        }
     }
 
     pub(crate) fn create_single_player(player: Box<dyn Player>) -> Wrapper {
         Wrapper {
             bidder: player.clone(),
+            discarder: None,
             player: player.clone(),
         }
     }
@@ -91,8 +110,15 @@ impl Player for Wrapper {
             .should_defend_alone_called(hand, dealer, trump, turned_down)
     }
 
+    fn choose_joker_suit(&mut self, hand: &HandBeforeBidding, dealer: &Position) -> Suit {
+        self.bidder.choose_joker_suit(hand, dealer)
+    }
+
     fn choose_discard(&mut self, hand: &HandBeforeBidding, trump: &Suit) -> CardBeforeBidding {
-        self.player.choose_discard(hand, trump)
+        match &mut self.discarder {
+            Some(discarder) => discarder.choose_discard(hand, trump),
+            None => self.player.choose_discard(hand, trump),
+        }
     }
 
     fn play_card(
@@ -107,4 +133,23 @@ impl Player for Wrapper {
     fn trick_end(&mut self, bid_result: &BidResultCalled, cards_played: &[PlayedCard]) {
         self.player.trick_end(bid_result, cards_played)
     }
+
+    fn bid_auction(
+        &mut self,
+        hand: &HandBeforeBidding,
+        dealer: &Position,
+        high_bid: Option<&Contract>,
+    ) -> Option<AuctionBid> {
+        self.bidder.bid_auction(hand, dealer, high_bid)
+    }
+
+    fn play_card_in_contract(
+        &mut self,
+        hand: &Hand,
+        contract: &Contract,
+        cards_played: &[PlayedCard],
+    ) -> Card {
+        self.player
+            .play_card_in_contract(hand, contract, cards_played)
+    }
 }
@@ -20,6 +20,13 @@ impl Position {
         }
     }
 
+    pub fn team_index(&self) -> usize {
+        match self {
+            Self::North | Self::South => 0,
+            Self::East | Self::West => 1,
+        }
+    }
+
     pub fn partner(&self) -> Position {
         match self {
             Self::West => Self::East,
@@ -56,6 +63,16 @@ impl Position {
             _ => next_position,
         }
     }
+
+    pub fn seen_from_south(&self, me: &Position) -> Position {
+        let mut from = *me;
+        let mut to = Position::South;
+        while from != *self {
+            from = from.next();
+            to = to.next();
+        }
+        to
+    }
 }
 
 impl FromStr for Position {
@@ -103,4 +120,11 @@ mod tests {
     fn next_position_playing(player: Position, bid_result: BidResultCalled) -> Position {
         player.next_position_playing(&bid_result)
     }
+
+    #[test_case(Position::North, Position::West => Position::West)]
+    #[test_case(Position::North, Position::North => Position::South)]
+    #[test_case(Position::East, Position::West => Position::North)]
+    fn seen_from_south(position: Position, me: Position) -> Position {
+        position.seen_from_south(&me)
+    }
 }
@@ -0,0 +1,747 @@
+// Each request is one line sent to the bot's stdin; requests other than `seat`,
+// `trick_end` and `quit` expect a one-line reply on stdout: `yes`/`no` for the
+// order/alone questions, a suit (`C`, `D`, `H`, `S`) or `pass` for `call_trump`,
+// a suit for `joker_suit`, and a card such as `10H`, `JD` or `JK` for `discard`
+// and `play`. Cards are always named by their printed rank and suit.
+//
+// Network clients get the same requests as one JSON object per line, tagged by
+// `type`, and reply with one JSON object per line: `{"yes": true}` for the
+// order/alone questions, `{"suit": "S"}` (or `{"suit": null}` to pass) for
+// `call_trump` and `joker_suit`, and `{"card": "JD"}` for `discard` and `play`.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::{Card, CardBeforeBidding},
+    hand::{Hand, HandBeforeBidding},
+    hands_iterator::{CardConstraint, CardLocation},
+    position::Position,
+    rank::Rank,
+    simulation::{BidRound, Pass},
+    suit::Suit,
+    trick_state::PlayedCard,
+};
+
+pub(crate) enum Request<'a> {
+    Seat {
+        position: Position,
+    },
+    OrderUp {
+        hand: &'a HandBeforeBidding,
+        dealer: Position,
+        trump_candidate: CardBeforeBidding,
+    },
+    OrderUpAlone {
+        hand: &'a HandBeforeBidding,
+        dealer: Position,
+        trump_candidate: CardBeforeBidding,
+    },
+    DefendAloneOrdered {
+        hand: &'a HandBeforeBidding,
+        dealer: Position,
+        trump_candidate: CardBeforeBidding,
+    },
+    CallTrump {
+        hand: &'a HandBeforeBidding,
+        dealer: Position,
+        turned_down: CardBeforeBidding,
+    },
+    CallAlone {
+        hand: &'a HandBeforeBidding,
+        dealer: Position,
+        trump: Suit,
+        turned_down: CardBeforeBidding,
+    },
+    DefendAloneCalled {
+        hand: &'a HandBeforeBidding,
+        dealer: Position,
+        trump: Suit,
+        turned_down: CardBeforeBidding,
+    },
+    JokerSuit {
+        hand: &'a HandBeforeBidding,
+        dealer: Position,
+    },
+    Discard {
+        hand: &'a HandBeforeBidding,
+        trump: Suit,
+    },
+    PlayCard {
+        hand: &'a Hand,
+        bid_result: &'a BidResultCalled,
+        cards_played: &'a [PlayedCard],
+    },
+    TrickEnd {
+        bid_result: &'a BidResultCalled,
+        cards_played: &'a [PlayedCard],
+    },
+    GameOver {
+        scores: [u8; 2],
+    },
+    Quit,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum Message {
+    Seat {
+        position: &'static str,
+    },
+    OrderUp {
+        dealer: &'static str,
+        candidate: String,
+        hand: Vec<String>,
+    },
+    OrderUpAlone {
+        dealer: &'static str,
+        candidate: String,
+        hand: Vec<String>,
+    },
+    DefendAloneOrdered {
+        dealer: &'static str,
+        candidate: String,
+        hand: Vec<String>,
+    },
+    CallTrump {
+        dealer: &'static str,
+        turned_down: String,
+        hand: Vec<String>,
+    },
+    CallAlone {
+        dealer: &'static str,
+        trump: &'static str,
+        turned_down: String,
+        hand: Vec<String>,
+    },
+    DefendAloneCalled {
+        dealer: &'static str,
+        trump: &'static str,
+        turned_down: String,
+        hand: Vec<String>,
+    },
+    JokerSuit {
+        dealer: &'static str,
+        hand: Vec<String>,
+    },
+    Discard {
+        trump: &'static str,
+        hand: Vec<String>,
+    },
+    Play {
+        #[serde(flatten)]
+        contract: ContractMessage,
+        played: Vec<PlayedCardMessage>,
+        hand: Vec<String>,
+    },
+    TrickEnd {
+        #[serde(flatten)]
+        contract: ContractMessage,
+        played: Vec<PlayedCardMessage>,
+    },
+    GameOver {
+        scores: [u8; 2],
+    },
+    Quit,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct ContractMessage {
+    trump: &'static str,
+    caller: &'static str,
+    alone: bool,
+    defender: Option<&'static str>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct PlayedCardMessage {
+    player: &'static str,
+    card: String,
+}
+
+#[derive(Deserialize)]
+struct YesNoReply {
+    yes: bool,
+}
+
+#[derive(Deserialize)]
+struct SuitReply {
+    suit: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CardReply {
+    card: String,
+}
+
+impl fmt::Display for Request<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Seat { position } => write!(f, "seat {}", position_name(position)),
+            Self::OrderUp {
+                hand,
+                dealer,
+                trump_candidate,
+            } => write!(
+                f,
+                "order_up dealer {} candidate {} hand {}",
+                position_name(dealer),
+                card_name(trump_candidate),
+                hand_names(hand)
+            ),
+            Self::OrderUpAlone {
+                hand,
+                dealer,
+                trump_candidate,
+            } => write!(
+                f,
+                "order_up_alone dealer {} candidate {} hand {}",
+                position_name(dealer),
+                card_name(trump_candidate),
+                hand_names(hand)
+            ),
+            Self::DefendAloneOrdered {
+                hand,
+                dealer,
+                trump_candidate,
+            } => write!(
+                f,
+                "defend_alone_ordered dealer {} candidate {} hand {}",
+                position_name(dealer),
+                card_name(trump_candidate),
+                hand_names(hand)
+            ),
+            Self::CallTrump {
+                hand,
+                dealer,
+                turned_down,
+            } => write!(
+                f,
+                "call_trump dealer {} turned_down {} hand {}",
+                position_name(dealer),
+                card_name(turned_down),
+                hand_names(hand)
+            ),
+            Self::CallAlone {
+                hand,
+                dealer,
+                trump,
+                turned_down,
+            } => write!(
+                f,
+                "call_alone dealer {} trump {} turned_down {} hand {}",
+                position_name(dealer),
+                suit_name(trump),
+                card_name(turned_down),
+                hand_names(hand)
+            ),
+            Self::DefendAloneCalled {
+                hand,
+                dealer,
+                trump,
+                turned_down,
+            } => write!(
+                f,
+                "defend_alone_called dealer {} trump {} turned_down {} hand {}",
+                position_name(dealer),
+                suit_name(trump),
+                card_name(turned_down),
+                hand_names(hand)
+            ),
+            Self::JokerSuit { hand, dealer } => write!(
+                f,
+                "joker_suit dealer {} hand {}",
+                position_name(dealer),
+                hand_names(hand)
+            ),
+            Self::Discard { hand, trump } => write!(
+                f,
+                "discard trump {} hand {}",
+                suit_name(trump),
+                hand_names(hand)
+            ),
+            Self::PlayCard {
+                hand,
+                bid_result,
+                cards_played,
+            } => write!(
+                f,
+                "play {} played{} hand {}",
+                bid_result_names(bid_result),
+                played_names(cards_played),
+                hand.cards
+                    .iter()
+                    .map(played_card_name)
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+            Self::TrickEnd {
+                bid_result,
+                cards_played,
+            } => write!(
+                f,
+                "trick_end {} played{}",
+                bid_result_names(bid_result),
+                played_names(cards_played)
+            ),
+            Self::GameOver { scores } => write!(f, "game_over {} {}", scores[0], scores[1]),
+            Self::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+impl From<&Request<'_>> for Message {
+    fn from(request: &Request<'_>) -> Self {
+        match *request {
+            Request::Seat { position } => Message::Seat {
+                position: position_name(&position),
+            },
+            Request::OrderUp {
+                hand,
+                dealer,
+                trump_candidate,
+            } => Message::OrderUp {
+                dealer: position_name(&dealer),
+                candidate: card_name(&trump_candidate),
+                hand: hand_list(hand),
+            },
+            Request::OrderUpAlone {
+                hand,
+                dealer,
+                trump_candidate,
+            } => Message::OrderUpAlone {
+                dealer: position_name(&dealer),
+                candidate: card_name(&trump_candidate),
+                hand: hand_list(hand),
+            },
+            Request::DefendAloneOrdered {
+                hand,
+                dealer,
+                trump_candidate,
+            } => Message::DefendAloneOrdered {
+                dealer: position_name(&dealer),
+                candidate: card_name(&trump_candidate),
+                hand: hand_list(hand),
+            },
+            Request::CallTrump {
+                hand,
+                dealer,
+                turned_down,
+            } => Message::CallTrump {
+                dealer: position_name(&dealer),
+                turned_down: card_name(&turned_down),
+                hand: hand_list(hand),
+            },
+            Request::CallAlone {
+                hand,
+                dealer,
+                trump,
+                turned_down,
+            } => Message::CallAlone {
+                dealer: position_name(&dealer),
+                trump: suit_name(&trump),
+                turned_down: card_name(&turned_down),
+                hand: hand_list(hand),
+            },
+            Request::DefendAloneCalled {
+                hand,
+                dealer,
+                trump,
+                turned_down,
+            } => Message::DefendAloneCalled {
+                dealer: position_name(&dealer),
+                trump: suit_name(&trump),
+                turned_down: card_name(&turned_down),
+                hand: hand_list(hand),
+            },
+            Request::JokerSuit { hand, dealer } => Message::JokerSuit {
+                dealer: position_name(&dealer),
+                hand: hand_list(hand),
+            },
+            Request::Discard { hand, trump } => Message::Discard {
+                trump: suit_name(&trump),
+                hand: hand_list(hand),
+            },
+            Request::PlayCard {
+                hand,
+                bid_result,
+                cards_played,
+            } => Message::Play {
+                contract: ContractMessage::from(bid_result),
+                played: played_list(cards_played),
+                hand: hand.cards.iter().map(played_card_name).collect(),
+            },
+            Request::TrickEnd {
+                bid_result,
+                cards_played,
+            } => Message::TrickEnd {
+                contract: ContractMessage::from(bid_result),
+                played: played_list(cards_played),
+            },
+            Request::GameOver { scores } => Message::GameOver { scores },
+            Request::Quit => Message::Quit,
+        }
+    }
+}
+
+impl From<&BidResultCalled> for ContractMessage {
+    fn from(bid_result: &BidResultCalled) -> Self {
+        match bid_result {
+            BidResultCalled::Called { trump, caller } => ContractMessage {
+                trump: suit_name(trump),
+                caller: position_name(caller),
+                alone: false,
+                defender: None,
+            },
+            BidResultCalled::CalledAlone { trump, caller } => ContractMessage {
+                trump: suit_name(trump),
+                caller: position_name(caller),
+                alone: true,
+                defender: None,
+            },
+            BidResultCalled::DefendedAlone {
+                trump,
+                caller,
+                defender,
+            } => ContractMessage {
+                trump: suit_name(trump),
+                caller: position_name(caller),
+                alone: true,
+                defender: Some(position_name(defender)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+pub(crate) fn parse_yes_no(reply: &str) -> Result<bool, String> {
+    match reply.trim() {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        reply => Err(format!("Expected yes or no: {}", reply)),
+    }
+}
+
+pub(crate) fn parse_suit(reply: &str) -> Result<Suit, String> {
+    Suit::from_str(reply.trim())
+}
+
+pub(crate) fn parse_call(reply: &str) -> Result<Option<Suit>, String> {
+    match reply.trim() {
+        "pass" => Ok(None),
+        reply => parse_suit(reply).map(Some),
+    }
+}
+
+pub(crate) fn parse_card(reply: &str) -> Result<CardBeforeBidding, String> {
+    let reply = reply.trim();
+    if reply.is_empty() || !reply.is_ascii() {
+        return Err(format!("Expected a card: {}", reply));
+    }
+    CardBeforeBidding::from_str(reply)
+}
+
+pub(crate) fn parse_card_in_hand(reply: &str, hand: &Hand) -> Result<Card, String> {
+    let card_name = card_name(&parse_card(reply)?);
+    hand.cards
+        .iter()
+        .find(|card| played_card_name(card) == card_name)
+        .copied()
+        .ok_or_else(|| format!("{} is not in the hand", reply.trim()))
+}
+
+pub(crate) fn parse_json_yes_no(reply: &str) -> Result<bool, String> {
+    parse_json::<YesNoReply>(reply).map(|reply| reply.yes)
+}
+
+pub(crate) fn parse_json_call(reply: &str) -> Result<Option<Suit>, String> {
+    parse_json::<SuitReply>(reply)?
+        .suit
+        .map(|suit| parse_suit(&suit))
+        .transpose()
+}
+
+pub(crate) fn parse_json_suit(reply: &str) -> Result<Suit, String> {
+    parse_json_call(reply)?.ok_or_else(|| "Expected a suit".to_string())
+}
+
+pub(crate) fn parse_json_card(reply: &str) -> Result<CardBeforeBidding, String> {
+    parse_card(&parse_json::<CardReply>(reply)?.card)
+}
+
+pub(crate) fn parse_json_card_in_hand(reply: &str, hand: &Hand) -> Result<Card, String> {
+    parse_card_in_hand(&parse_json::<CardReply>(reply)?.card, hand)
+}
+
+fn parse_json<'a, T: Deserialize<'a>>(reply: &'a str) -> Result<T, String> {
+    serde_json::from_str(reply)
+        .map_err(|error| format!("Invalid reply {}: {}", reply.trim(), error))
+}
+
+pub(crate) fn position_name(position: &Position) -> &'static str {
+    match position {
+        Position::North => "N",
+        Position::East => "E",
+        Position::South => "S",
+        Position::West => "W",
+    }
+}
+
+pub(crate) fn suit_name(suit: &Suit) -> &'static str {
+    match suit {
+        Suit::Spades => "S",
+        Suit::Hearts => "H",
+        Suit::Diamonds => "D",
+        Suit::Clubs => "C",
+    }
+}
+
+fn rank_card_name(suit: &Suit, rank: &Rank) -> String {
+    match rank {
+        Rank::Joker => rank.to_string(),
+        _ => format!("{}{}", rank, suit_name(suit)),
+    }
+}
+
+pub(crate) fn card_name(card: &CardBeforeBidding) -> String {
+    rank_card_name(&card.suit, &card.rank)
+}
+
+pub(crate) fn constraint_name(constraint: &CardConstraint) -> String {
+    let location = match constraint.location {
+        CardLocation::West => "W",
+        CardLocation::North => "N",
+        CardLocation::East => "E",
+        CardLocation::Kitty => "K",
+    };
+    format!("{}:{}", card_name(&constraint.card), location)
+}
+
+pub(crate) fn pass_name(pass: &Pass) -> String {
+    let round = match pass.round {
+        BidRound::First => 1,
+        BidRound::Second => 2,
+    };
+    format!("{}:{}", position_name(&pass.position), round)
+}
+
+pub(crate) fn played_card_name(card: &Card) -> String {
+    rank_card_name(
+        &card.rank.suit_for_display(&card.suit),
+        &card.rank.rank_for_display(),
+    )
+}
+
+fn hand_names(hand: &HandBeforeBidding) -> String {
+    hand.cards
+        .iter()
+        .map(card_name)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn hand_list(hand: &HandBeforeBidding) -> Vec<String> {
+    hand.cards.iter().map(card_name).collect()
+}
+
+fn played_list(cards_played: &[PlayedCard]) -> Vec<PlayedCardMessage> {
+    cards_played
+        .iter()
+        .map(|played_card| PlayedCardMessage {
+            player: position_name(&played_card.player),
+            card: played_card_name(&played_card.card),
+        })
+        .collect()
+}
+
+fn played_names(cards_played: &[PlayedCard]) -> String {
+    cards_played
+        .iter()
+        .map(|played_card| {
+            format!(
+                " {}:{}",
+                position_name(&played_card.player),
+                played_card_name(&played_card.card)
+            )
+        })
+        .collect()
+}
+
+fn bid_result_names(bid_result: &BidResultCalled) -> String {
+    match bid_result {
+        BidResultCalled::Called { trump, caller } => format!(
+            "trump {} caller {}",
+            suit_name(trump),
+            position_name(caller)
+        ),
+        BidResultCalled::CalledAlone { trump, caller } => format!(
+            "trump {} caller {} alone",
+            suit_name(trump),
+            position_name(caller)
+        ),
+        BidResultCalled::DefendedAlone {
+            trump,
+            caller,
+            defender,
+        } => format!(
+            "trump {} caller {} alone defender {}",
+            suit_name(trump),
+            position_name(caller),
+            position_name(defender)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rank_with_bowers::RankWithBowers;
+    use test_case::test_case;
+
+    fn hand_before_bidding() -> HandBeforeBidding {
+        HandBeforeBidding {
+            cards: ["9H", "10H", "JD", "AS", "QC"]
+                .iter()
+                .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn order_up() {
+        let request = Request::OrderUp {
+            hand: &hand_before_bidding(),
+            dealer: Position::North,
+            trump_candidate: CardBeforeBidding::from_str("JH").unwrap(),
+        };
+        assert_eq!(
+            "order_up dealer N candidate JH hand 9H 10H JD AS QC",
+            request.to_string()
+        );
+    }
+
+    #[test]
+    fn play_card() {
+        let hand = HandBeforeBidding::update_bowers(hand_before_bidding(), &Suit::Hearts);
+        let bid_result = BidResultCalled::CalledAlone {
+            trump: Suit::Hearts,
+            caller: Position::East,
+        };
+        let cards_played = [PlayedCard {
+            player: Position::East,
+            card: Card {
+                suit: Suit::Hearts,
+                rank: RankWithBowers::RightBower,
+            },
+        }];
+        let request = Request::PlayCard {
+            hand: &hand,
+            bid_result: &bid_result,
+            cards_played: &cards_played,
+        };
+        assert_eq!(
+            "play trump H caller E alone played E:JH hand 9H 10H JD AS QC",
+            request.to_string()
+        );
+        assert_eq!(
+            Ok(Card {
+                suit: Suit::Hearts,
+                rank: RankWithBowers::LeftBower
+            }),
+            parse_card_in_hand("JD\n", &hand)
+        );
+        assert!(parse_card_in_hand("KD", &hand).is_err());
+    }
+
+    #[test]
+    fn trick_end() {
+        let bid_result = BidResultCalled::Called {
+            trump: Suit::Clubs,
+            caller: Position::South,
+        };
+        let request = Request::TrickEnd {
+            bid_result: &bid_result,
+            cards_played: &[],
+        };
+        assert_eq!("trick_end trump C caller S played", request.to_string());
+    }
+
+    #[test]
+    fn json_messages() {
+        let request = Request::OrderUp {
+            hand: &hand_before_bidding(),
+            dealer: Position::North,
+            trump_candidate: CardBeforeBidding::from_str("JH").unwrap(),
+        };
+        assert_eq!(
+            r#"{"type":"order_up","dealer":"N","candidate":"JH","hand":["9H","10H","JD","AS","QC"]}"#,
+            Message::from(&request).to_string()
+        );
+        let bid_result = BidResultCalled::DefendedAlone {
+            trump: Suit::Clubs,
+            caller: Position::South,
+            defender: Position::West,
+        };
+        let request = Request::TrickEnd {
+            bid_result: &bid_result,
+            cards_played: &[],
+        };
+        assert_eq!(
+            r#"{"type":"trick_end","trump":"C","caller":"S","alone":true,"defender":"W","played":[]}"#,
+            Message::from(&request).to_string()
+        );
+        assert_eq!(
+            r#"{"type":"quit"}"#,
+            Message::from(&Request::Quit).to_string()
+        );
+    }
+
+    #[test_case(r#"{"yes": true}"# => Ok(true))]
+    #[test_case(r#"{"yes": false}"# => Ok(false))]
+    #[test_case("yes" => matches Err(_))]
+    fn parse_json_yes_no(reply: &str) -> Result<bool, String> {
+        super::parse_json_yes_no(reply)
+    }
+
+    #[test_case(r#"{"suit": "H"}"# => Ok(Some(Suit::Hearts)))]
+    #[test_case(r#"{"suit": null}"# => Ok(None))]
+    #[test_case("{}" => Ok(None))]
+    #[test_case(r#"{"suit": "X"}"# => Err("Invalid suit: X".to_string()))]
+    fn parse_json_call(reply: &str) -> Result<Option<Suit>, String> {
+        super::parse_json_call(reply)
+    }
+
+    #[test]
+    fn parse_json_card() {
+        assert_eq!(
+            CardBeforeBidding::from_str("10H"),
+            super::parse_json_card(r#"{"card": "10H"}"#)
+        );
+        assert!(parse_json_suit("{}").is_err());
+    }
+
+    #[test_case("yes\n" => Ok(true))]
+    #[test_case("no" => Ok(false))]
+    #[test_case("sure" => Err("Expected yes or no: sure".to_string()))]
+    fn parse_yes_no(reply: &str) -> Result<bool, String> {
+        super::parse_yes_no(reply)
+    }
+
+    #[test_case("pass" => Ok(None))]
+    #[test_case("S\n" => Ok(Some(Suit::Spades)))]
+    #[test_case("X" => Err("Invalid suit: X".to_string()))]
+    fn parse_call(reply: &str) -> Result<Option<Suit>, String> {
+        super::parse_call(reply)
+    }
+}
@@ -4,23 +4,29 @@ use std::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, IntoEnumIterator, PartialOrd, Ord)]
 pub enum Rank {
+    Joker = 15,
     Ace = 14,
     King = 13,
     Queen = 12,
     Jack = 11,
     Ten = 10,
     Nine = 9,
+    Eight = 8,
+    Seven = 7,
 }
 
 impl Rank {
     pub fn offset_for_unicode_card(&self) -> u32 {
         match self {
+            Self::Joker => 0xF,
             Self::Ace => 0x1,
             Self::King => 0xE,
             Self::Queen => 0xD,
             Self::Jack => 0xB,
             Self::Ten => 0xA,
             Self::Nine => 0x9,
+            Self::Eight => 0x8,
+            Self::Seven => 0x7,
         }
     }
 }
@@ -30,12 +36,15 @@ impl FromStr for Rank {
 
     fn from_str(name: &str) -> Result<Self, Self::Err> {
         match name {
+            "JK" => Ok(Self::Joker),
             "A" => Ok(Self::Ace),
             "K" => Ok(Self::King),
             "Q" => Ok(Self::Queen),
             "J" => Ok(Self::Jack),
             "10" | "T" => Ok(Self::Ten),
             "9" | "N" => Ok(Self::Nine),
+            "8" => Ok(Self::Eight),
+            "7" => Ok(Self::Seven),
             _ => Err(format!("Invalid rank: {}", name)),
         }
     }
@@ -44,12 +53,15 @@ impl FromStr for Rank {
 impl fmt::Display for Rank {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let rank = match self {
+            Self::Joker => "JK",
             Self::Ace => "A",
             Self::King => "K",
             Self::Queen => "Q",
             Self::Jack => "J",
             Self::Ten => "10",
             Self::Nine => "9",
+            Self::Eight => "8",
+            Self::Seven => "7",
         };
         write!(f, "{}", rank)
     }
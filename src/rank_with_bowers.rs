@@ -5,6 +5,7 @@ use enum_iterator::IntoEnumIterator;
 
 #[derive(Copy, Clone, Debug, IntoEnumIterator, PartialEq, Eq, PartialOrd, Ord)]
 pub enum RankWithBowers {
+    Joker = 17,
     RightBower = 16,
     LeftBower = 15,
     Ace = 14,
@@ -13,11 +14,14 @@ pub enum RankWithBowers {
     Jack = 11,
     Ten = 10,
     Nine = 9,
+    Eight = 8,
+    Seven = 7,
 }
 
 impl Display for RankWithBowers {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let rank = match self {
+            Self::Joker => "JK",
             Self::RightBower => "R",
             Self::LeftBower => "L",
             Self::Ace => "A",
@@ -26,6 +30,8 @@ impl Display for RankWithBowers {
             Self::Jack => "J",
             Self::Ten => "10",
             Self::Nine => "9",
+            Self::Eight => "8",
+            Self::Seven => "7",
         };
         write!(f, "{}", rank)
     }
@@ -34,12 +40,15 @@ impl Display for RankWithBowers {
 impl From<Rank> for RankWithBowers {
     fn from(rank: Rank) -> Self {
         match rank {
+            Rank::Joker => Self::Joker,
             Rank::Ace => Self::Ace,
             Rank::King => Self::King,
             Rank::Queen => Self::Queen,
             Rank::Jack => Self::Jack,
             Rank::Ten => Self::Ten,
             Rank::Nine => Self::Nine,
+            Rank::Eight => Self::Eight,
+            Rank::Seven => Self::Seven,
         }
     }
 }
@@ -47,12 +56,15 @@ impl From<Rank> for RankWithBowers {
 impl RankWithBowers {
     pub fn rank_for_display(&self) -> Rank {
         match self {
+            Self::Joker => Rank::Joker,
             Self::RightBower | Self::LeftBower | Self::Jack => Rank::Jack,
             Self::Ace => Rank::Ace,
             Self::King => Rank::King,
             Self::Queen => Rank::Queen,
             Self::Ten => Rank::Ten,
             Self::Nine => Rank::Nine,
+            Self::Eight => Rank::Eight,
+            Self::Seven => Rank::Seven,
         }
     }
 
@@ -69,6 +81,7 @@ mod tests {
     use super::*;
     use test_case::test_case;
 
+    #[test_case(RankWithBowers::Joker => Rank::Joker)]
     #[test_case(RankWithBowers::RightBower => Rank::Jack)]
     #[test_case(RankWithBowers::LeftBower => Rank::Jack)]
     #[test_case(RankWithBowers::Ace => Rank::Ace)]
@@ -77,10 +90,13 @@ mod tests {
     #[test_case(RankWithBowers::Jack => Rank::Jack)]
     #[test_case(RankWithBowers::Ten => Rank::Ten)]
     #[test_case(RankWithBowers::Nine => Rank::Nine)]
+    #[test_case(RankWithBowers::Eight => Rank::Eight)]
+    #[test_case(RankWithBowers::Seven => Rank::Seven)]
     fn rank_for_display(rank: RankWithBowers) -> Rank {
         rank.rank_for_display()
     }
 
+    #[test_case(RankWithBowers::Joker, Suit::Clubs => Suit::Clubs)]
     #[test_case(RankWithBowers::RightBower, Suit::Clubs => Suit::Clubs)]
     #[test_case(RankWithBowers::LeftBower, Suit::Clubs => Suit::Spades)]
     #[test_case(RankWithBowers::Ace, Suit::Clubs => Suit::Clubs)]
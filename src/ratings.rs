@@ -0,0 +1,186 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
+
+pub(crate) const INITIAL_RATING: f64 = 1500f64;
+const K_FACTOR: f64 = 32f64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Rating {
+    pub(crate) rating: f64,
+    pub(crate) games: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Ratings {
+    pub(crate) ratings: BTreeMap<String, Rating>,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            rating: INITIAL_RATING,
+            games: 0,
+        }
+    }
+}
+
+impl Ratings {
+    pub(crate) fn load(path: &Path) -> io::Result<Ratings> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Ratings::default()),
+            Err(error) => return Err(error),
+        };
+        contents.parse()
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Rating {
+        self.ratings.get(name).copied().unwrap_or_default()
+    }
+
+    pub(crate) fn expected_score(&self, teams: &[[&str; 2]; 2]) -> f64 {
+        let [first, second] = teams.map(|team| self.team_rating(&team));
+        1f64 / (1f64 + 10f64.powf((second - first) / 400f64))
+    }
+
+    pub(crate) fn record_results(&mut self, teams: [[&str; 2]; 2], wins: [u32; 2]) {
+        let games = wins[0] + wins[1];
+        for game in 0..games {
+            let first_won = (game + 1) * wins[0] / games > game * wins[0] / games;
+            self.record_game(teams, first_won);
+        }
+    }
+
+    pub(crate) fn record_game(&mut self, teams: [[&str; 2]; 2], first_won: bool) {
+        let actual = if first_won { 1f64 } else { 0f64 };
+        let change = K_FACTOR * (actual - self.expected_score(&teams));
+        for (team, change) in [(teams[0], change), (teams[1], -change)] {
+            for (index, name) in team.iter().enumerate() {
+                let rating = self.ratings.entry(name.to_string()).or_default();
+                rating.rating += change / 2f64;
+                if index == 0 || team[0] != team[1] {
+                    rating.games += 1;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn leaderboard(&self) -> Vec<(&String, &Rating)> {
+        let mut leaderboard: Vec<(&String, &Rating)> = self.ratings.iter().collect();
+        leaderboard.sort_by(|(_, a), (_, b)| b.rating.total_cmp(&a.rating));
+        leaderboard
+    }
+
+    fn team_rating(&self, team: &[&str; 2]) -> f64 {
+        team.iter().map(|name| self.get(name).rating).sum::<f64>() / 2f64
+    }
+}
+
+impl FromStr for Ratings {
+    type Err = io::Error;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut ratings = BTreeMap::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let invalid_line =
+                || io::Error::new(ErrorKind::InvalidData, format!("Invalid rating: {}", line));
+            let mut fields = line.split('\t');
+            let (Some(name), Some(rating), Some(games), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid_line());
+            };
+            let rating = Rating {
+                rating: rating.parse().map_err(|_| invalid_line())?,
+                games: games.parse().map_err(|_| invalid_line())?,
+            };
+            if ratings.insert(name.to_string(), rating).is_some() {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Duplicate rating: {}", name),
+                ));
+            }
+        }
+        Ok(Ratings { ratings })
+    }
+}
+
+impl fmt::Display for Ratings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, rating) in &self.ratings {
+            writeln!(f, "{}\t{}\t{}", name, rating.rating, rating.games)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn even_teams_expect_half() {
+        let ratings = Ratings::default();
+        assert_eq!(0.5, ratings.expected_score(&[["a", "a"], ["b", "b"]]));
+    }
+
+    #[test]
+    fn record_game() {
+        let mut ratings = Ratings::default();
+        ratings.record_game([["a", "a"], ["b", "b"]], true);
+        assert_eq!(1516f64, ratings.get("a").rating);
+        assert_eq!(1484f64, ratings.get("b").rating);
+        assert_eq!(1, ratings.get("a").games);
+    }
+
+    #[test]
+    fn mixed_partnership() {
+        let mut ratings = Ratings::default();
+        ratings.record_game([["a", "b"], ["c", "c"]], true);
+        assert_eq!(1508f64, ratings.get("a").rating);
+        assert_eq!(1508f64, ratings.get("b").rating);
+        assert_eq!(1484f64, ratings.get("c").rating);
+    }
+
+    #[test]
+    fn record_results_converges() {
+        let mut ratings = Ratings::default();
+        ratings.record_results([["a", "a"], ["b", "b"]], [750, 250]);
+        assert_eq!(1000, ratings.get("a").games);
+        let expected_score = ratings.expected_score(&[["a", "a"], ["b", "b"]]);
+        assert!((expected_score - 0.75).abs() < 0.05);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut ratings = Ratings::default();
+        ratings.record_results([["advanced", "advanced"], ["basic", "basic"]], [7, 2]);
+        let parsed: Ratings = ratings.to_string().parse().unwrap();
+        assert_eq!(ratings, parsed);
+    }
+
+    #[test]
+    fn invalid_line() {
+        assert!("advanced\tstrong\t3".parse::<Ratings>().is_err());
+        assert!("advanced\t1500".parse::<Ratings>().is_err());
+        assert!("basic\t1500\t2\nbasic\t1400\t4".parse::<Ratings>().is_err());
+    }
+
+    #[test]
+    fn leaderboard_is_sorted() {
+        let mut ratings = Ratings::default();
+        ratings.record_results([["a", "a"], ["b", "b"]], [0, 2]);
+        let leaderboard = ratings.leaderboard();
+        assert_eq!("b", leaderboard[0].0);
+        assert_eq!("a", leaderboard[1].0);
+    }
+}
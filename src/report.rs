@@ -0,0 +1,378 @@
+// Machine-readable simulation results. JSON output is a single object with the
+// scenario inputs, the totals and one entry per outcome. CSV output has a
+// header row and one row per outcome, repeating the scenario inputs and totals
+// on every row; list values such as the hand are separated by spaces. A
+// scenario without any outcomes still gets a row, with the outcome fields left
+// empty.
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::{
+    protocol::{card_name, constraint_name, pass_name, position_name, suit_name},
+    simulation::{Bid, HandResult, Scenario, SimulationResults},
+};
+
+const CSV_HEADER: [&str; 21] = [
+    "dealer",
+    "trump_candidate",
+    "hand",
+    "bid",
+    "trump",
+    "go_alone",
+    "ignore_other_bids",
+    "joker",
+    "deck_size",
+    "strategies",
+    "pinned",
+    "excluded",
+    "passed",
+    "discard",
+    "total_count",
+    "bid_count",
+    "expected_value",
+    "outcome",
+    "score",
+    "count",
+    "percent",
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Report {
+    scenario: ScenarioReport,
+    total_count: u64,
+    bid_count: u64,
+    expected_value: Option<f64>,
+    outcomes: Vec<OutcomeReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct ScenarioReport {
+    dealer: &'static str,
+    trump_candidate: String,
+    hand: Vec<String>,
+    bid: &'static str,
+    trump: &'static str,
+    go_alone: bool,
+    ignore_other_bids: bool,
+    joker: bool,
+    deck_size: u8,
+    strategies: Vec<String>,
+    pinned: Vec<String>,
+    excluded: Vec<String>,
+    passed: Vec<String>,
+    discard: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OutcomeReport {
+    outcome: &'static str,
+    score: Option<i8>,
+    count: u64,
+    percent: f64,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Invalid output format: {}", name)),
+        }
+    }
+}
+
+impl Report {
+    pub(crate) fn create(scenario: &Scenario, results: &SimulationResults) -> Report {
+        let mut hand_results: Vec<(&HandResult, &u64)> = results.result_counts.iter().collect();
+        hand_results.sort();
+        Report {
+            scenario: ScenarioReport::create(scenario),
+            total_count: results.total_count,
+            bid_count: results.bid_count(),
+            expected_value: results.expected_value(),
+            outcomes: hand_results
+                .into_iter()
+                .map(|(result, count)| OutcomeReport {
+                    outcome: outcome_name(result),
+                    score: match result {
+                        HandResult::ExpectedBidResult { score } => Some(*score),
+                        _ => None,
+                    },
+                    count: *count,
+                    percent: *count as f64 / results.total_count as f64 * 100.0,
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub(crate) fn to_csv(&self) -> String {
+        to_csv(std::slice::from_ref(self))
+    }
+
+    fn csv_rows(&self) -> Vec<String> {
+        let scenario = &self.scenario;
+        let scenario_fields = [
+            scenario.dealer.to_string(),
+            scenario.trump_candidate.clone(),
+            scenario.hand.join(" "),
+            scenario.bid.to_string(),
+            scenario.trump.to_string(),
+            scenario.go_alone.to_string(),
+            scenario.ignore_other_bids.to_string(),
+            scenario.joker.to_string(),
+            scenario.deck_size.to_string(),
+            scenario.strategies.join(" "),
+            scenario.pinned.join(" "),
+            scenario.excluded.join(" "),
+            scenario.passed.join(" "),
+            scenario.discard.clone().unwrap_or_default(),
+            self.total_count.to_string(),
+            self.bid_count.to_string(),
+            self.expected_value
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+        ];
+        let outcome_rows: Vec<[String; 4]> = if self.outcomes.is_empty() {
+            vec![Default::default()]
+        } else {
+            self.outcomes
+                .iter()
+                .map(|outcome| {
+                    [
+                        outcome.outcome.to_string(),
+                        outcome
+                            .score
+                            .map(|score| score.to_string())
+                            .unwrap_or_default(),
+                        outcome.count.to_string(),
+                        outcome.percent.to_string(),
+                    ]
+                })
+                .collect()
+        };
+        outcome_rows
+            .iter()
+            .map(|outcome_fields| {
+                scenario_fields
+                    .iter()
+                    .chain(outcome_fields.iter())
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn to_json(reports: &[Report]) -> String {
+    serde_json::to_string_pretty(reports).unwrap()
+}
+
+pub(crate) fn to_csv(reports: &[Report]) -> String {
+    std::iter::once(CSV_HEADER.join(","))
+        .chain(reports.iter().flat_map(Report::csv_rows))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+impl ScenarioReport {
+    fn create(scenario: &Scenario) -> ScenarioReport {
+        ScenarioReport {
+            dealer: position_name(&scenario.dealer),
+            trump_candidate: card_name(&scenario.trump_candidate),
+            hand: scenario.hand.cards.iter().map(card_name).collect(),
+            bid: match scenario.bid {
+                Bid::OrderUp => "order_up",
+                Bid::Call(_) => "call",
+            },
+            trump: suit_name(&match scenario.bid {
+                Bid::OrderUp => scenario.trump_candidate.suit,
+                Bid::Call(trump) => trump,
+            }),
+            go_alone: scenario.go_alone,
+            ignore_other_bids: scenario.ignore_other_bids,
+            joker: scenario.rules.joker,
+            deck_size: scenario.rules.deck_size.card_count(),
+            strategies: scenario
+                .strategies
+                .iter()
+                .map(|strategy| strategy.to_string())
+                .collect(),
+            pinned: scenario
+                .constraints
+                .pinned
+                .iter()
+                .map(constraint_name)
+                .collect(),
+            excluded: scenario
+                .constraints
+                .excluded
+                .iter()
+                .map(constraint_name)
+                .collect(),
+            passed: scenario.passes.iter().map(pass_name).collect(),
+            discard: scenario.discard.as_ref().map(card_name),
+        }
+    }
+}
+
+fn outcome_name(result: &HandResult) -> &'static str {
+    match result {
+        HandResult::InconsistentBidding => "inconsistent_bidding",
+        HandResult::DifferentBidResult => "different_bid_result",
+        HandResult::ExpectedBidResult { score: -4 } => "defended_alone",
+        HandResult::ExpectedBidResult { score: -2 } => "euchred",
+        HandResult::ExpectedBidResult { score: 1 } => "made_it",
+        HandResult::ExpectedBidResult { score: 2 } => "took_all_five",
+        HandResult::ExpectedBidResult { score: 4 } => "made_it_alone",
+        HandResult::ExpectedBidResult { .. } => "unexpected_score",
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        card::CardBeforeBidding,
+        hand::HandBeforeBidding,
+        hands_iterator::{CardConstraint, Constraints},
+        players::registry::Strategy,
+        position::Position,
+        rules::Rules,
+        simulation::Pass,
+        suit::Suit,
+    };
+    use std::collections::HashMap;
+    use test_case::test_case;
+
+    fn scenario() -> Scenario {
+        Scenario {
+            rules: Rules::default(),
+            dealer: Position::South,
+            trump_candidate: CardBeforeBidding::from_str("9H").unwrap(),
+            hand: HandBeforeBidding {
+                cards: ["JH", "JD", "AH", "KH", "AS"]
+                    .iter()
+                    .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                    .collect(),
+            },
+            bid: Bid::Call(Suit::Spades),
+            go_alone: false,
+            ignore_other_bids: false,
+            strategies: [
+                Strategy::Advanced,
+                Strategy::Basic,
+                Strategy::Advanced,
+                Strategy::Process("bot --level 3,4".to_string()),
+            ],
+            constraints: Constraints {
+                pinned: vec![CardConstraint::from_str("JC:N").unwrap()],
+                excluded: Vec::new(),
+            },
+            passes: vec![Pass::from_str("W:1").unwrap()],
+            discard: None,
+        }
+    }
+
+    fn report() -> Report {
+        let results = SimulationResults {
+            total_count: 4,
+            result_counts: HashMap::from([
+                (HandResult::DifferentBidResult, 1),
+                (HandResult::ExpectedBidResult { score: 1 }, 2),
+                (HandResult::ExpectedBidResult { score: -2 }, 1),
+            ]),
+        };
+        Report::create(&scenario(), &results)
+    }
+
+    #[test_case("json" => Ok(OutputFormat::Json))]
+    #[test_case("csv" => Ok(OutputFormat::Csv))]
+    #[test_case("xml" => Err("Invalid output format: xml".to_string()))]
+    fn parse_output_format(name: &str) -> Result<OutputFormat, String> {
+        OutputFormat::from_str(name)
+    }
+
+    #[test]
+    fn json_report() {
+        let json: serde_json::Value = serde_json::from_str(&report().to_json()).unwrap();
+        assert_eq!("S", json["scenario"]["dealer"]);
+        assert_eq!("S", json["scenario"]["trump"]);
+        assert_eq!("JC:N", json["scenario"]["pinned"][0]);
+        assert_eq!("W:1", json["scenario"]["passed"][0]);
+        assert!(json["scenario"]["discard"].is_null());
+        assert_eq!(3, json["bid_count"]);
+        assert_eq!(0.0, json["expected_value"]);
+        assert_eq!("different_bid_result", json["outcomes"][0]["outcome"]);
+        assert!(json["outcomes"][0]["score"].is_null());
+        assert_eq!("euchred", json["outcomes"][1]["outcome"]);
+        assert_eq!(25.0, json["outcomes"][1]["percent"]);
+    }
+
+    #[test]
+    fn combined_csv_report() {
+        let csv = to_csv(&[report(), report()]);
+        assert_eq!(7, csv.lines().count());
+        assert_eq!(
+            1,
+            csv.lines()
+                .filter(|line| line.starts_with("dealer,"))
+                .count()
+        );
+    }
+
+    #[test]
+    fn csv_report() {
+        let csv = report().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(4, lines.len());
+        assert_eq!(CSV_HEADER.join(","), lines[0]);
+        assert_eq!(
+            "S,9H,JH JD AH KH AS,call,S,false,false,false,24,\"advanced basic advanced process:bot --level 3,4\",JC:N,,W:1,,4,3,0,made_it,1,2,50",
+            lines[3]
+        );
+    }
+
+    #[test]
+    fn reports_discard() {
+        let mut scenario = scenario();
+        scenario.discard = Some(CardBeforeBidding::from_str("AS").unwrap());
+        let report = Report::create(&scenario, &SimulationResults::default());
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!("AS", json["scenario"]["discard"]);
+        assert!(report.to_csv().lines().nth(1).unwrap().contains(",W:1,AS,"));
+    }
+
+    #[test]
+    fn csv_row_for_scenario_without_outcomes() {
+        let csv = Report::create(&scenario(), &SimulationResults::default()).to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(2, lines.len());
+        assert!(lines[1].ends_with(",W:1,,0,0,,,,,"));
+    }
+}
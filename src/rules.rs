@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use crate::rank::Rank;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Rules {
+    pub(crate) joker: bool,
+    pub(crate) deck_size: DeckSize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum DeckSize {
+    #[default]
+    TwentyFour,
+    TwentyEight,
+    ThirtyTwo,
+}
+
+impl DeckSize {
+    pub(crate) fn card_count(&self) -> u8 {
+        match self {
+            Self::TwentyFour => 24,
+            Self::TwentyEight => 28,
+            Self::ThirtyTwo => 32,
+        }
+    }
+
+    pub(crate) fn lowest_rank(&self) -> Rank {
+        match self {
+            Self::TwentyFour => Rank::Nine,
+            Self::TwentyEight => Rank::Seven,
+            Self::ThirtyTwo => Rank::Seven,
+        }
+    }
+
+    pub(crate) fn includes(&self, rank: Rank) -> bool {
+        match self {
+            Self::TwentyEight if rank == Rank::Eight => false,
+            _ => rank >= self.lowest_rank(),
+        }
+    }
+}
+
+impl FromStr for DeckSize {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "24" => Ok(Self::TwentyFour),
+            "28" => Ok(Self::TwentyEight),
+            "32" => Ok(Self::ThirtyTwo),
+            _ => Err(format!("Invalid deck size: {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(DeckSize::TwentyFour, Rank::Nine => true)]
+    #[test_case(DeckSize::TwentyFour, Rank::Eight => false)]
+    #[test_case(DeckSize::TwentyFour, Rank::Seven => false)]
+    #[test_case(DeckSize::TwentyEight, Rank::Nine => true)]
+    #[test_case(DeckSize::TwentyEight, Rank::Eight => false)]
+    #[test_case(DeckSize::TwentyEight, Rank::Seven => true)]
+    #[test_case(DeckSize::ThirtyTwo, Rank::Eight => true)]
+    #[test_case(DeckSize::ThirtyTwo, Rank::Seven => true)]
+    fn includes(deck_size: DeckSize, rank: Rank) -> bool {
+        deck_size.includes(rank)
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    io,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    game_state::GameState,
+    match_play::{self, GameResult},
+    players::{advanced::AdvancedPlayer, remote::RemotePlayer, wrapper::Wrapper},
+    position::Position,
+    protocol::Request,
+    rules::Rules,
+};
+
+pub(crate) const DEFAULT_PORT: u16 = 7777;
+
+const ACCEPT_POLL: Duration = Duration::from_millis(50);
+
+pub(crate) struct Server {
+    listener: TcpListener,
+    rules: Rules,
+    timeout: Duration,
+}
+
+pub(crate) struct Table {
+    players: [Wrapper; 4],
+    remote_players: Vec<RemotePlayer>,
+    rules: Rules,
+}
+
+impl Server {
+    pub(crate) fn bind(
+        address: impl ToSocketAddrs,
+        rules: Rules,
+        timeout: Duration,
+    ) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(address)?,
+            rules,
+            timeout,
+        })
+    }
+
+    pub(crate) fn local_port(&self) -> io::Result<u16> {
+        Ok(self.listener.local_addr()?.port())
+    }
+
+    pub(crate) fn seat_players(&self, remote_seats: &[Position]) -> io::Result<Table> {
+        let mut remote_players = Vec::new();
+        let mut players = [
+            Position::North,
+            Position::East,
+            Position::South,
+            Position::West,
+        ]
+        .map(|position| {
+            Wrapper::create_single_player(Box::new(AdvancedPlayer::create_with_rules(
+                position, self.rules,
+            )))
+        });
+        for position in remote_seats {
+            let (stream, peer) = self.accept(position)?;
+            log::info!("{} joined as {:?}", peer, position);
+            let remote_player = RemotePlayer::create(stream, *position, self.timeout)?;
+            players[position.index()] =
+                Wrapper::create_single_player(Box::new(remote_player.clone()));
+            remote_players.push(remote_player);
+        }
+        Ok(Table {
+            players,
+            remote_players,
+            rules: self.rules,
+        })
+    }
+
+    fn accept(&self, position: &Position) -> io::Result<(TcpStream, SocketAddr)> {
+        let deadline = Instant::now() + self.timeout;
+        self.listener.set_nonblocking(true)?;
+        let accepted = loop {
+            match self.listener.accept() {
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        break Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("No one joined as {:?} in time", position),
+                        ));
+                    }
+                    thread::sleep(ACCEPT_POLL);
+                }
+                result => break result,
+            }
+        };
+        self.listener.set_nonblocking(false)?;
+        let (stream, peer) = accepted?;
+        stream.set_nonblocking(false)?;
+        Ok((stream, peer))
+    }
+}
+
+impl Table {
+    pub(crate) fn play_game(&self, dealer: Position) -> GameResult {
+        let game_result =
+            match_play::play_game(GameState::create(self.players.clone(), self.rules, dealer));
+        for remote_player in &self.remote_players {
+            remote_player.tell(Request::GameOver {
+                scores: game_result.scores,
+            });
+        }
+        game_result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::DeckSize;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpStream,
+        thread::{self, JoinHandle},
+    };
+
+    const RULES: Rules = Rules {
+        joker: false,
+        deck_size: DeckSize::TwentyFour,
+    };
+
+    fn message_field<'a>(message: &'a str, field: &str) -> Option<&'a str> {
+        let start = message.find(&format!("\"{}\":", field))? + field.len() + 3;
+        let rest = &message[start..];
+        let end = rest.find([',', '}', ']']).unwrap_or(rest.len());
+        Some(rest[..end].trim_matches(|c| c == '"' || c == '['))
+    }
+
+    fn connect_client(port: u16, disconnect_early: bool) -> JoinHandle<Vec<String>> {
+        thread::spawn(move || {
+            let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut message_types = Vec::new();
+            for line in BufReader::new(stream).lines() {
+                let line = line.unwrap();
+                let message_type = message_field(&line, "type").unwrap().to_string();
+                let reply = match message_type.as_str() {
+                    "seat" | "trick_end" | "game_over" => None,
+                    "quit" => {
+                        message_types.push(message_type);
+                        break;
+                    }
+                    "call_trump" => Some(r#"{"suit": null}"#.to_string()),
+                    "joker_suit" => Some(r#"{"suit": "S"}"#.to_string()),
+                    "discard" | "play" => Some(format!(
+                        r#"{{"card": "{}"}}"#,
+                        message_field(&line, "hand").unwrap()
+                    )),
+                    _ => Some(r#"{"yes": false}"#.to_string()),
+                };
+                message_types.push(message_type);
+                if disconnect_early && message_types.len() > 3 {
+                    break;
+                }
+                if let Some(reply) = reply {
+                    writeln!(writer, "{}", reply).unwrap();
+                }
+            }
+            message_types
+        })
+    }
+
+    #[test]
+    fn plays_a_game_with_loopback_clients() {
+        let server = Server::bind("127.0.0.1:0", RULES, Duration::from_secs(5)).unwrap();
+        let port = server.local_port().unwrap();
+        let clients = [connect_client(port, false), connect_client(port, false)];
+        let table = server
+            .seat_players(&[Position::South, Position::North])
+            .unwrap();
+        let game_result = table.play_game(Position::East);
+        assert!(game_result.scores.iter().any(|score| *score >= 10));
+        drop(table);
+        for client in clients {
+            let message_types = client.join().unwrap();
+            assert_eq!("seat", message_types[0]);
+            assert!(message_types.contains(&"play".to_string()));
+            assert!(message_types.contains(&"trick_end".to_string()));
+            assert_eq!(
+                ["game_over", "quit"],
+                message_types[message_types.len() - 2..]
+            );
+        }
+    }
+
+    #[test]
+    fn seating_times_out() {
+        let server = Server::bind("127.0.0.1:0", RULES, Duration::from_millis(200)).unwrap();
+        let error = server.seat_players(&[Position::South]).err().unwrap();
+        assert_eq!(io::ErrorKind::TimedOut, error.kind());
+    }
+
+    #[test]
+    fn early_reply_gets_default_decisions() {
+        let server = Server::bind("127.0.0.1:0", RULES, Duration::from_secs(5)).unwrap();
+        let port = server.local_port().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+            writeln!(stream, r#"{{"yes": true}}"#).unwrap();
+            BufReader::new(stream).lines().count()
+        });
+        let table = server.seat_players(&[Position::West]).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let game_result = table.play_game(Position::South);
+        assert!(game_result.scores.iter().any(|score| *score >= 10));
+        assert!(table.remote_players[0].failed());
+        drop(table);
+        assert_eq!(1, client.join().unwrap());
+    }
+
+    #[test]
+    fn disconnected_client_gets_default_decisions() {
+        let server = Server::bind("127.0.0.1:0", RULES, Duration::from_secs(5)).unwrap();
+        let port = server.local_port().unwrap();
+        let client = connect_client(port, true);
+        let table = server.seat_players(&[Position::West]).unwrap();
+        let game_result = table.play_game(Position::South);
+        assert!(game_result.scores.iter().any(|score| *score >= 10));
+        assert_eq!(4, client.join().unwrap().len());
+    }
+}
@@ -0,0 +1,61 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::Path,
+    sync::{Mutex, OnceLock},
+};
+
+// Keeps what was loaded from each path so that every seat and every game
+// naming the same file shares one copy and reads it once.
+pub(crate) struct SharedFiles<T> {
+    files: OnceLock<Mutex<HashMap<String, T>>>,
+}
+
+impl<T: Clone> SharedFiles<T> {
+    pub(crate) const fn new() -> SharedFiles<T> {
+        SharedFiles {
+            files: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn load(
+        &self,
+        path: &str,
+        load: impl FnOnce(&Path) -> io::Result<T>,
+    ) -> io::Result<T> {
+        let mut files = self.files.get_or_init(Default::default).lock().unwrap();
+        if let Some(file) = files.get(path) {
+            return Ok(file.clone());
+        }
+        let file = load(Path::new(path))?;
+        let _previous = files.insert(path.to_string(), file.clone());
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_each_path_once() {
+        let shared = SharedFiles::new();
+        let mut loads = 0;
+        for _ in 0..2 {
+            assert_eq!(
+                "a",
+                shared
+                    .load("a", |path| {
+                        loads += 1;
+                        Ok(path.display().to_string())
+                    })
+                    .unwrap()
+            );
+        }
+        assert_eq!(1, loads);
+        assert!(shared
+            .load("b", |_| Err::<String, _>(io::ErrorKind::NotFound.into()))
+            .is_err());
+        assert_eq!("b", shared.load("b", |_| Ok("b".to_string())).unwrap());
+    }
+}
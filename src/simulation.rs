@@ -0,0 +1,778 @@
+use std::{collections::HashMap, str::FromStr};
+
+use rayon::prelude::ParallelIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bid_result::BidResultCalled,
+    card::CardBeforeBidding,
+    deck::Deck,
+    hand::HandBeforeBidding,
+    hand_state::{HandPhase, HandState},
+    hands_iterator::{CardLocation, Constraints},
+    player::Player,
+    players::{preprogrammed_bidder::PreprogrammedBidder, registry::Strategy, wrapper::Wrapper},
+    position::Position,
+    rules::Rules,
+    suit::Suit,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Bid {
+    OrderUp,
+    Call(Suit),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Scenario {
+    pub(crate) rules: Rules,
+    pub(crate) dealer: Position,
+    pub(crate) trump_candidate: CardBeforeBidding,
+    pub(crate) hand: HandBeforeBidding,
+    pub(crate) bid: Bid,
+    pub(crate) go_alone: bool,
+    pub(crate) ignore_other_bids: bool,
+    pub(crate) strategies: [Strategy; 4],
+    pub(crate) constraints: Constraints,
+    pub(crate) passes: Vec<Pass>,
+    pub(crate) discard: Option<CardBeforeBidding>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BidRound {
+    First,
+    Second,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Pass {
+    pub(crate) position: Position,
+    pub(crate) round: BidRound,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) enum HandResult {
+    InconsistentBidding,
+    DifferentBidResult,
+    ExpectedBidResult { score: i8 },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SimulationResults {
+    pub(crate) total_count: u64,
+    pub(crate) result_counts: HashMap<HandResult, u64>,
+}
+
+impl Scenario {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        validate_strategies(&self.strategies)?;
+        let all_cards = Deck::create_all_cards(&self.rules);
+        for (index, card) in self
+            .hand
+            .cards
+            .iter()
+            .chain(std::iter::once(&self.trump_candidate))
+            .enumerate()
+        {
+            if !all_cards.contains(card) {
+                return Err(format!("{} is not in the deck for these rules", card));
+            }
+            if self.hand.cards[..index.min(self.hand.cards.len())].contains(card) {
+                return Err(format!("{} appears more than once", card));
+            }
+        }
+        if self.hand.cards.len() != 5 {
+            return Err(format!(
+                "The hand must have 5 cards, not {}",
+                self.hand.cards.len()
+            ));
+        }
+        self.validate_constraints(&all_cards)?;
+        self.validate_passes()?;
+        self.validate_discard()
+    }
+
+    fn validate_discard(&self) -> Result<(), String> {
+        match self.discard {
+            Some(_) if self.dealer != Position::South || self.bid != Bid::OrderUp => {
+                Err("You only discard when you're dealing and order up".to_string())
+            }
+            Some(card) if card != self.trump_candidate && !self.hand.cards.contains(&card) => {
+                Err(format!("You can't discard {}, it isn't in your hand", card))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn discard_options(&self) -> Vec<Scenario> {
+        self.hand
+            .cards
+            .iter()
+            .chain(std::iter::once(&self.trump_candidate))
+            .map(|&card| Scenario {
+                dealer: Position::South,
+                bid: Bid::OrderUp,
+                discard: Some(card),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    fn validate_passes(&self) -> Result<(), String> {
+        for (index, pass) in self.passes.iter().enumerate() {
+            if pass.position == Position::South {
+                return Err("Passes are only observed for the other players".to_string());
+            }
+            if pass.round == BidRound::Second && self.bid == Bid::OrderUp {
+                return Err(format!(
+                    "{:?} can't have passed in the second round if you order up",
+                    pass.position
+                ));
+            }
+            let round = match self.bid {
+                Bid::OrderUp => BidRound::First,
+                Bid::Call(_) => BidRound::Second,
+            };
+            if pass.round == round
+                && self.bidding_turn(pass.position) > self.bidding_turn(Position::South)
+            {
+                return Err(format!(
+                    "{:?} bids after you and can't have passed in the {:?} round",
+                    pass.position, pass.round
+                ));
+            }
+            if self.passes[..index].contains(pass) {
+                return Err(format!(
+                    "{:?} passed more than once in the {:?} round",
+                    pass.position, pass.round
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn bidding_turn(&self, position: Position) -> usize {
+        let mut bidder = self.dealer.next_position_bidding();
+        let mut turn = 0;
+        while bidder != position {
+            bidder = bidder.next_position_bidding();
+            turn += 1;
+        }
+        turn
+    }
+
+    fn matches_passes(&self, hand_state: &HandState) -> bool {
+        let hands = match &hand_state.phase {
+            HandPhase::Bidding { hands, .. } => hands,
+            _ => return false,
+        };
+        self.passes.iter().all(|pass| {
+            let hand = &hands[pass.position.index()];
+            let mut player =
+                self.strategies[pass.position.index()].create(pass.position, self.rules);
+            match pass.round {
+                BidRound::First => {
+                    !player.should_order_up(hand, &self.dealer, &self.trump_candidate)
+                }
+                BidRound::Second => player
+                    .call_trump(hand, &self.dealer, &self.trump_candidate)
+                    .is_none(),
+            }
+        })
+    }
+
+    fn validate_constraints(&self, all_cards: &[CardBeforeBidding]) -> Result<(), String> {
+        let kitty_cards = all_cards.len() - 15 - self.hand.cards.len() - 1;
+        let mut slots = [5, 5, 5, kitty_cards];
+        for constraint in self
+            .constraints
+            .pinned
+            .iter()
+            .chain(self.constraints.excluded.iter())
+        {
+            if !all_cards.contains(&constraint.card) {
+                return Err(format!(
+                    "{} is not in the deck for these rules",
+                    constraint.card
+                ));
+            }
+            if constraint.card == self.trump_candidate || self.hand.cards.contains(&constraint.card)
+            {
+                return Err(format!(
+                    "{} is already known to be in your hand or the up card",
+                    constraint.card
+                ));
+            }
+        }
+        for (index, constraint) in self.constraints.pinned.iter().enumerate() {
+            if self.constraints.pinned[..index]
+                .iter()
+                .any(|pinned| pinned.card == constraint.card)
+            {
+                return Err(format!("{} is pinned more than once", constraint.card));
+            }
+            let slot = &mut slots[constraint.location.index()];
+            if *slot == 0 {
+                return Err(format!(
+                    "Too many cards are pinned to {:?}",
+                    constraint.location
+                ));
+            }
+            *slot -= 1;
+        }
+        for constraint in &self.constraints.excluded {
+            if self.constraints.pinned.contains(constraint) {
+                return Err(format!(
+                    "{} is both pinned to and excluded from {:?}",
+                    constraint.card, constraint.location
+                ));
+            }
+            if CardLocation::ALL.iter().all(|&location| {
+                self.constraints.excluded.iter().any(|excluded| {
+                    excluded.card == constraint.card && excluded.location == location
+                })
+            }) {
+                return Err(format!(
+                    "{} is excluded from every location",
+                    constraint.card
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn bidding_info(&self) -> (PreprogrammedBidder, BidResultCalled) {
+        match (self.bid, self.go_alone) {
+            (Bid::OrderUp, true) => (
+                PreprogrammedBidder::orders_up_alone(),
+                BidResultCalled::CalledAlone {
+                    trump: self.trump_candidate.suit,
+                    caller: Position::South,
+                },
+            ),
+            (Bid::OrderUp, false) => (
+                PreprogrammedBidder::orders_up(),
+                BidResultCalled::Called {
+                    trump: self.trump_candidate.suit,
+                    caller: Position::South,
+                },
+            ),
+            (Bid::Call(trump), true) => (
+                PreprogrammedBidder::calls_alone(trump),
+                BidResultCalled::CalledAlone {
+                    trump,
+                    caller: Position::South,
+                },
+            ),
+            (Bid::Call(trump), false) => (
+                PreprogrammedBidder::calls(trump),
+                BidResultCalled::Called {
+                    trump,
+                    caller: Position::South,
+                },
+            ),
+        }
+    }
+
+    fn run_permutation(
+        &self,
+        bidder: &mut PreprogrammedBidder,
+        expected_bid_result: &BidResultCalled,
+        hand_state: &mut HandState,
+    ) -> HandResult {
+        if !self.matches_passes(hand_state) {
+            return HandResult::InconsistentBidding;
+        }
+        let south =
+            self.strategies[Position::South.index()].create_player(Position::South, self.rules);
+        let mut players = [
+            self.create_player(Position::North),
+            self.create_player(Position::East),
+            match self.discard {
+                Some(card) => Wrapper::create_with_discarder(
+                    Box::new(bidder.clone()),
+                    Box::new(PreprogrammedBidder::discards(card)),
+                    south,
+                ),
+                None => Wrapper::create_separate_bidder(Box::new(bidder.clone()), south),
+            },
+            self.create_player(Position::West),
+        ];
+        match hand_state.finish_bidding(&mut players) {
+            Some(bid_result) if expected_bid_result.is_equivalent(&bid_result) => (),
+            _ => return HandResult::DifferentBidResult,
+        };
+        loop {
+            if let Some((winner, score)) = hand_state.step(&mut players) {
+                return if winner == Position::South || winner == Position::South.partner() {
+                    HandResult::ExpectedBidResult { score: score as i8 }
+                } else {
+                    HandResult::ExpectedBidResult {
+                        score: -(score as i8),
+                    }
+                };
+            }
+        }
+    }
+
+    fn create_player(&self, position: Position) -> Wrapper {
+        let strategy = &self.strategies[position.index()];
+        if self.ignore_other_bids {
+            Wrapper::create_separate_bidder(
+                Box::new(PreprogrammedBidder::does_nothing()),
+                strategy.create_player(position, self.rules),
+            )
+        } else {
+            strategy.create(position, self.rules)
+        }
+    }
+}
+
+impl SimulationResults {
+    pub(crate) fn add(mut self, hand_result: HandResult) -> SimulationResults {
+        add_to_results(&mut self.result_counts, hand_result, 1);
+        self.total_count += 1;
+        self
+    }
+
+    pub(crate) fn combine(mut self, other: SimulationResults) -> SimulationResults {
+        for (result, count) in other.result_counts.into_iter() {
+            add_to_results(&mut self.result_counts, result, count);
+        }
+        self.total_count += other.total_count;
+        self
+    }
+
+    pub(crate) fn bid_count(&self) -> u64 {
+        self.result_counts
+            .iter()
+            .filter(|(result, _)| matches!(result, HandResult::ExpectedBidResult { .. }))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    pub(crate) fn expected_value(&self) -> Option<f64> {
+        if self.bid_count() == 0 {
+            return None;
+        }
+        let total_score: i64 = self
+            .result_counts
+            .iter()
+            .map(|(result, count)| match result {
+                HandResult::InconsistentBidding | HandResult::DifferentBidResult => 0,
+                HandResult::ExpectedBidResult { score } => *score as i64 * *count as i64,
+            })
+            .sum();
+        Some(total_score as f64 / self.bid_count() as f64)
+    }
+}
+
+impl FromStr for BidRound {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "1" => Ok(Self::First),
+            "2" => Ok(Self::Second),
+            _ => Err(format!("Invalid bidding round: {}", name)),
+        }
+    }
+}
+
+impl FromStr for Pass {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.split_once(':') {
+            Some((position, round)) => Ok(Pass {
+                position: Position::from_str(position)?,
+                round: BidRound::from_str(round)?,
+            }),
+            None => Err(format!("Expected position:round, such as W:1: {}", name)),
+        }
+    }
+}
+
+pub(crate) fn validate_strategies(strategies: &[Strategy; 4]) -> Result<(), String> {
+    match strategies
+        .iter()
+        .find(|strategy| matches!(strategy, Strategy::Human | Strategy::Process(_)))
+    {
+        Some(strategy) => Err(format!(
+            "The {} strategy can't be used in simulations",
+            strategy
+        )),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn simulate(scenario: &Scenario) -> SimulationResults {
+    tally(
+        scenario,
+        HandState::create_with_scenario(
+            &scenario.rules,
+            scenario.dealer,
+            scenario.trump_candidate,
+            scenario.hand.clone(),
+            &scenario.constraints,
+        ),
+    )
+}
+
+pub(crate) fn simulate_sampled(scenario: &Scenario, samples: u64, seed: u64) -> SimulationResults {
+    tally(
+        scenario,
+        HandState::create_with_sampled_scenario(
+            &scenario.rules,
+            scenario.dealer,
+            scenario.trump_candidate,
+            scenario.hand.clone(),
+            &scenario.constraints,
+            samples,
+            seed,
+        ),
+    )
+}
+
+fn tally(
+    scenario: &Scenario,
+    hand_states: impl ParallelIterator<Item = HandState>,
+) -> SimulationResults {
+    hand_states
+        .map_with(
+            scenario.bidding_info(),
+            |(bidder, expected_bid_result), mut hand_state| {
+                scenario.run_permutation(bidder, expected_bid_result, &mut hand_state)
+            },
+        )
+        .fold(SimulationResults::default, SimulationResults::add)
+        .reduce(SimulationResults::default, SimulationResults::combine)
+}
+
+fn add_to_results(
+    result_counts: &mut HashMap<HandResult, u64>,
+    hand_result: HandResult,
+    count: u64,
+) {
+    match result_counts.get_mut(&hand_result) {
+        Some(result_count) => {
+            *result_count += count;
+        }
+        None => {
+            if let Some(old_value) = result_counts.insert(hand_result, count) {
+                panic!(
+                    "Got an old value after get_mut returned None: {}",
+                    old_value
+                )
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hands_iterator::CardConstraint;
+    use test_case::test_case;
+
+    fn scenario(hand: [&str; 5], go_alone: bool) -> Scenario {
+        Scenario {
+            rules: Rules::default(),
+            dealer: Position::South,
+            trump_candidate: CardBeforeBidding::from_str("9H").unwrap(),
+            hand: HandBeforeBidding {
+                cards: hand
+                    .iter()
+                    .map(|name| CardBeforeBidding::from_str(name).unwrap())
+                    .collect(),
+            },
+            bid: Bid::OrderUp,
+            go_alone,
+            ignore_other_bids: true,
+            strategies: [
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+                Strategy::Advanced,
+            ],
+            constraints: Constraints::default(),
+            passes: Vec::new(),
+            discard: None,
+        }
+    }
+
+    #[test]
+    fn sampled_results_are_reproducible() {
+        let scenario = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        let first = simulate_sampled(&scenario, 200, 5);
+        assert_eq!(200, first.total_count);
+        assert_eq!(200, first.bid_count());
+        assert_eq!(first, simulate_sampled(&scenario, 200, 5));
+        assert!(first.expected_value().unwrap() > 1.0);
+    }
+
+    #[test]
+    fn no_consistent_deals_has_no_expected_value() {
+        let mut results = SimulationResults {
+            total_count: 3,
+            ..Default::default()
+        };
+        let _previous = results
+            .result_counts
+            .insert(HandResult::InconsistentBidding, 3);
+        assert_eq!(0, results.bid_count());
+        assert_eq!(None, results.expected_value());
+    }
+
+    #[test]
+    fn weak_hand_loses_points_alone() {
+        let scenario = scenario(["10C", "9S", "10S", "QD", "KD"], true);
+        assert!(
+            simulate_sampled(&scenario, 200, 5)
+                .expected_value()
+                .unwrap()
+                < 0.0
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(
+            Ok(()),
+            scenario(["JH", "JD", "AH", "KH", "AS"], false).validate()
+        );
+        assert_eq!(
+            Err(format!(
+                "{} appears more than once",
+                CardBeforeBidding::from_str("JH").unwrap()
+            )),
+            scenario(["JH", "JD", "AH", "KH", "JH"], false).validate()
+        );
+        assert_eq!(
+            Err(format!(
+                "{} appears more than once",
+                CardBeforeBidding::from_str("9H").unwrap()
+            )),
+            scenario(["JH", "JD", "AH", "KH", "9H"], false).validate()
+        );
+        assert_eq!(
+            Err(format!(
+                "{} is not in the deck for these rules",
+                CardBeforeBidding::from_str("JK").unwrap()
+            )),
+            scenario(["JH", "JD", "AH", "KH", "JK"], false).validate()
+        );
+        let mut human = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        human.strategies[Position::West.index()] = Strategy::Human;
+        assert_eq!(
+            Err("The human strategy can't be used in simulations".to_string()),
+            human.validate()
+        );
+        let mut process = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        process.strategies[Position::North.index()] = Strategy::Process("bot".to_string());
+        assert_eq!(
+            Err("The process:bot strategy can't be used in simulations".to_string()),
+            process.validate()
+        );
+    }
+
+    fn constraint(name: &str) -> CardConstraint {
+        CardConstraint::from_str(name).unwrap()
+    }
+
+    #[test]
+    fn validate_constraints() {
+        let mut pinned = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        pinned.constraints.pinned = vec![constraint("JC:N"), constraint("JS:K")];
+        pinned.constraints.excluded = vec![constraint("AD:W")];
+        assert_eq!(Ok(()), pinned.validate());
+        pinned.constraints.pinned.push(constraint("JC:E"));
+        assert_eq!(
+            Err(format!(
+                "{} is pinned more than once",
+                constraint("JC:E").card
+            )),
+            pinned.validate()
+        );
+        let mut known = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        known.constraints.excluded = vec![constraint("9H:W")];
+        assert_eq!(
+            Err(format!(
+                "{} is already known to be in your hand or the up card",
+                constraint("9H:W").card
+            )),
+            known.validate()
+        );
+        let mut full_kitty = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        full_kitty.constraints.pinned = ["JC:K", "JS:K", "AC:K", "AD:K"].map(constraint).to_vec();
+        assert_eq!(
+            Err("Too many cards are pinned to Kitty".to_string()),
+            full_kitty.validate()
+        );
+        let mut conflicting = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        conflicting.constraints.pinned = vec![constraint("JC:N")];
+        conflicting.constraints.excluded = vec![constraint("JC:N")];
+        assert_eq!(
+            Err(format!(
+                "{} is both pinned to and excluded from North",
+                constraint("JC:N").card
+            )),
+            conflicting.validate()
+        );
+        let mut nowhere = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        nowhere.constraints.excluded = ["JC:W", "JC:N", "JC:E", "JC:K"].map(constraint).to_vec();
+        assert_eq!(
+            Err(format!(
+                "{} is excluded from every location",
+                constraint("JC:W").card
+            )),
+            nowhere.validate()
+        );
+    }
+
+    fn dealt_hands(hand_state: HandState) -> [HandBeforeBidding; 4] {
+        match hand_state.phase {
+            HandPhase::Bidding { hands, .. } => hands,
+            _ => panic!("Expected a freshly dealt hand"),
+        }
+    }
+
+    #[test]
+    fn constrained_deals_respect_constraints() {
+        let mut scenario = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        scenario.constraints.pinned = vec![constraint("JC:N"), constraint("JS:K")];
+        scenario.constraints.excluded = vec![constraint("AD:W"), constraint("AD:E")];
+        let jack_of_clubs = constraint("JC:N").card;
+        let jack_of_spades = constraint("JS:K").card;
+        let ace_of_diamonds = constraint("AD:W").card;
+        let hands: Vec<[HandBeforeBidding; 4]> = HandState::create_with_sampled_scenario(
+            &scenario.rules,
+            scenario.dealer,
+            scenario.trump_candidate,
+            scenario.hand.clone(),
+            &scenario.constraints,
+            100,
+            3,
+        )
+        .map(dealt_hands)
+        .collect();
+        assert_eq!(100, hands.len());
+        for [north, east, _, west] in hands {
+            assert!(north.cards.contains(&jack_of_clubs));
+            assert_eq!(5, north.cards.len());
+            for hand in [&north, &east, &west] {
+                assert!(!hand.cards.contains(&jack_of_spades));
+            }
+            assert!(!west.cards.contains(&ace_of_diamonds));
+            assert!(!east.cards.contains(&ace_of_diamonds));
+        }
+    }
+
+    #[test]
+    fn validate_discard() {
+        let mut scenario = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        scenario.discard = Some(CardBeforeBidding::from_str("9H").unwrap());
+        assert_eq!(Ok(()), scenario.validate());
+        scenario.discard = Some(CardBeforeBidding::from_str("AC").unwrap());
+        assert_eq!(
+            Err(format!(
+                "You can't discard {}, it isn't in your hand",
+                CardBeforeBidding::from_str("AC").unwrap()
+            )),
+            scenario.validate()
+        );
+        scenario.discard = Some(CardBeforeBidding::from_str("AS").unwrap());
+        scenario.dealer = Position::West;
+        assert_eq!(
+            Err("You only discard when you're dealing and order up".to_string()),
+            scenario.validate()
+        );
+    }
+
+    #[test]
+    fn discarding_trump_costs_points() {
+        let scenario = scenario(["JH", "JD", "AH", "KH", "9C"], false);
+        let options = scenario.discard_options();
+        assert_eq!(6, options.len());
+        let expected_value = |name: &str| {
+            let card = CardBeforeBidding::from_str(name).unwrap();
+            let option = options
+                .iter()
+                .find(|option| option.discard == Some(card))
+                .unwrap();
+            simulate_sampled(option, 300, 11).expected_value().unwrap()
+        };
+        assert!(expected_value("9C") > expected_value("JH"));
+    }
+
+    #[test_case("W:1" => Ok(Pass { position: Position::West, round: BidRound::First }))]
+    #[test_case("N:2" => Ok(Pass { position: Position::North, round: BidRound::Second }))]
+    #[test_case("N:3" => Err("Invalid bidding round: 3".to_string()))]
+    #[test_case("N" => Err("Expected position:round, such as W:1: N".to_string()))]
+    fn parse_pass(name: &str) -> Result<Pass, String> {
+        Pass::from_str(name)
+    }
+
+    #[test]
+    fn validate_passes() {
+        let mut scenario = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        scenario.passes = ["W:1", "N:1"]
+            .map(|name| Pass::from_str(name).unwrap())
+            .to_vec();
+        assert_eq!(Ok(()), scenario.validate());
+        scenario.passes.push(Pass::from_str("E:2").unwrap());
+        assert_eq!(
+            Err("East can't have passed in the second round if you order up".to_string()),
+            scenario.validate()
+        );
+        scenario.bid = Bid::Call(Suit::Spades);
+        assert_eq!(Ok(()), scenario.validate());
+        scenario.passes.push(Pass::from_str("W:1").unwrap());
+        assert_eq!(
+            Err("West passed more than once in the First round".to_string()),
+            scenario.validate()
+        );
+        scenario.passes = vec![Pass::from_str("S:1").unwrap()];
+        assert_eq!(
+            Err("Passes are only observed for the other players".to_string()),
+            scenario.validate()
+        );
+    }
+
+    #[test]
+    fn validate_pass_order() {
+        let mut scenario = scenario(["JH", "JD", "AH", "KH", "AS"], false);
+        scenario.dealer = Position::West;
+        scenario.passes = vec![Pass::from_str("W:1").unwrap()];
+        assert_eq!(
+            Err("West bids after you and can't have passed in the First round".to_string()),
+            scenario.validate()
+        );
+        scenario.passes = vec![Pass::from_str("N:1").unwrap()];
+        assert_eq!(Ok(()), scenario.validate());
+        scenario.bid = Bid::Call(Suit::Spades);
+        scenario.passes = ["W:1", "N:2"]
+            .map(|name| Pass::from_str(name).unwrap())
+            .to_vec();
+        assert_eq!(Ok(()), scenario.validate());
+        scenario.passes.push(Pass::from_str("W:2").unwrap());
+        assert_eq!(
+            Err("West bids after you and can't have passed in the Second round".to_string()),
+            scenario.validate()
+        );
+    }
+
+    #[test]
+    fn observed_passes_filter_deals() {
+        let mut scenario = scenario(["9C", "10C", "9S", "10S", "QD"], false);
+        let unconditioned = simulate_sampled(&scenario, 300, 7);
+        scenario.passes = ["W:1", "N:1", "E:1"]
+            .map(|name| Pass::from_str(name).unwrap())
+            .to_vec();
+        let conditioned = simulate_sampled(&scenario, 300, 7);
+        let inconsistent = conditioned.result_counts[&HandResult::InconsistentBidding];
+        assert!(inconsistent > 0);
+        assert_eq!(300, conditioned.bid_count() + inconsistent);
+        assert_eq!(300, unconditioned.bid_count());
+    }
+}
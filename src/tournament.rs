@@ -0,0 +1,292 @@
+use rand::{thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    game_state::GameState,
+    match_play::{play_game, FirstDealer, GameResult},
+    players::registry::Strategy,
+    position::Position,
+    rules::Rules,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PairingResult {
+    pub(crate) strategies: [Strategy; 2],
+    pub(crate) boards: u32,
+    pub(crate) wins: [u32; 2],
+    pub(crate) points: [u64; 2],
+    pub(crate) hands_played: u64,
+    board_difference_sum: i64,
+    board_difference_squares: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Standing {
+    pub(crate) strategy: Strategy,
+    pub(crate) games: u32,
+    pub(crate) wins: u32,
+    pub(crate) points: u64,
+    pub(crate) hands_played: u64,
+}
+
+impl PairingResult {
+    pub(crate) fn create(strategies: [Strategy; 2]) -> PairingResult {
+        PairingResult {
+            strategies,
+            boards: 0,
+            wins: [0; 2],
+            points: [0; 2],
+            hands_played: 0,
+            board_difference_sum: 0,
+            board_difference_squares: 0,
+        }
+    }
+
+    pub(crate) fn add(mut self, (first, second): (GameResult, GameResult)) -> PairingResult {
+        self.boards += 1;
+        let first_wins = [first.winner() == 0, second.winner() == 1].map(u32::from);
+        for (team, game_result) in [(0, first), (1, second)] {
+            self.wins[0] += first_wins[team];
+            self.wins[1] += 1 - first_wins[team];
+            self.points[0] += game_result.scores[team] as u64;
+            self.points[1] += game_result.scores[1 - team] as u64;
+            self.hands_played += game_result.hands_played as u64;
+        }
+        let difference = (first_wins[0] + first_wins[1]) as i64 - 1;
+        self.board_difference_sum += difference;
+        self.board_difference_squares += difference * difference;
+        self
+    }
+
+    pub(crate) fn combine(mut self, other: PairingResult) -> PairingResult {
+        self.boards += other.boards;
+        self.hands_played += other.hands_played;
+        self.board_difference_sum += other.board_difference_sum;
+        self.board_difference_squares += other.board_difference_squares;
+        for index in 0..2 {
+            self.wins[index] += other.wins[index];
+            self.points[index] += other.points[index];
+        }
+        self
+    }
+
+    pub(crate) fn win_rate(&self, index: usize) -> f64 {
+        if self.boards == 0 {
+            return 0f64;
+        }
+        self.wins[index] as f64 / (2 * self.boards) as f64
+    }
+
+    pub(crate) fn z_score(&self) -> f64 {
+        if self.boards < 2 {
+            return 0f64;
+        }
+        let boards = self.boards as f64;
+        let mean = self.board_difference_sum as f64 / boards;
+        let variance =
+            (self.board_difference_squares as f64 - boards * mean * mean) / (boards - 1f64);
+        if variance <= 0f64 {
+            return if mean == 0f64 {
+                0f64
+            } else {
+                mean.signum() * f64::INFINITY
+            };
+        }
+        mean / (variance / boards).sqrt()
+    }
+
+    pub(crate) fn p_value(&self) -> f64 {
+        p_value(self.z_score())
+    }
+}
+
+impl Standing {
+    fn create(strategy: Strategy) -> Standing {
+        Standing {
+            strategy,
+            games: 0,
+            wins: 0,
+            points: 0,
+            hands_played: 0,
+        }
+    }
+
+    pub(crate) fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0f64;
+        }
+        self.wins as f64 / self.games as f64
+    }
+
+    pub(crate) fn points_per_hand(&self) -> f64 {
+        if self.hands_played == 0 {
+            return 0f64;
+        }
+        self.points as f64 / self.hands_played as f64
+    }
+}
+
+pub(crate) fn play_pairing(strategies: &[Strategy; 2], rules: Rules, boards: u32) -> PairingResult {
+    (0..boards)
+        .into_par_iter()
+        .map(|_| {
+            let seed = thread_rng().gen();
+            let dealer = FirstDealer::Random.dealer(0);
+            (
+                play_duplicate_game([&strategies[0], &strategies[1]], rules, dealer, seed),
+                play_duplicate_game([&strategies[1], &strategies[0]], rules, dealer, seed),
+            )
+        })
+        .fold(
+            || PairingResult::create(strategies.clone()),
+            PairingResult::add,
+        )
+        .reduce(
+            || PairingResult::create(strategies.clone()),
+            PairingResult::combine,
+        )
+}
+
+fn play_duplicate_game(
+    [north_south, east_west]: [&Strategy; 2],
+    rules: Rules,
+    dealer: Position,
+    seed: u64,
+) -> GameResult {
+    let players = [
+        north_south.create(Position::North, rules),
+        east_west.create(Position::East, rules),
+        north_south.create(Position::South, rules),
+        east_west.create(Position::West, rules),
+    ];
+    play_game(GameState::create_seeded(players, rules, dealer, seed))
+}
+
+pub(crate) fn play_tournament(
+    strategies: &[Strategy],
+    rules: Rules,
+    boards: u32,
+) -> (Vec<Standing>, Vec<PairingResult>) {
+    let mut pairing_results = Vec::new();
+    for (index, first) in strategies.iter().enumerate() {
+        for second in &strategies[index + 1..] {
+            pairing_results.push(play_pairing(
+                &[first.clone(), second.clone()],
+                rules,
+                boards,
+            ));
+        }
+    }
+    (get_standings(strategies, &pairing_results), pairing_results)
+}
+
+fn get_standings(strategies: &[Strategy], pairing_results: &[PairingResult]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|strategy| Standing::create(strategy.clone()))
+        .collect();
+    for pairing_result in pairing_results {
+        for index in 0..2 {
+            let standing = standings
+                .iter_mut()
+                .find(|standing| standing.strategy == pairing_result.strategies[index])
+                .unwrap();
+            standing.games += 2 * pairing_result.boards;
+            standing.wins += pairing_result.wins[index];
+            standing.points += pairing_result.points[index];
+            standing.hands_played += pairing_result.hands_played;
+        }
+    }
+    standings.sort_by(|a, b| b.win_rate().total_cmp(&a.win_rate()));
+    standings
+}
+
+pub(crate) fn p_value(z_score: f64) -> f64 {
+    erfc(z_score.abs() / std::f64::consts::SQRT_2)
+}
+
+fn erfc(x: f64) -> f64 {
+    let t = 1f64 / (1f64 + 0.3275911 * x);
+    let polynomial = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    polynomial * (-x * x).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn game_result(scores: [u8; 2]) -> GameResult {
+        GameResult {
+            scores,
+            hands_played: 10,
+            loners: [0; 2],
+            euchres: [0; 2],
+        }
+    }
+
+    #[test]
+    fn add_board() {
+        let pairing_result = PairingResult::create([Strategy::Advanced, Strategy::Basic])
+            .add((game_result([10, 6]), game_result([9, 10])))
+            .add((game_result([10, 6]), game_result([10, 8])));
+        assert_eq!(2, pairing_result.boards);
+        assert_eq!([3, 1], pairing_result.wins);
+        assert_eq!([38, 31], pairing_result.points);
+        assert_eq!(40, pairing_result.hands_played);
+        assert_eq!(0.75, pairing_result.win_rate(0));
+    }
+
+    #[test_case([1, 1, 1, 1] => f64::INFINITY ; "always wins")]
+    #[test_case([0, 0, 0, 0] => 0f64 ; "always splits")]
+    #[test_case([1, -1, 1, -1] => 0f64 ; "evenly matched")]
+    #[test_case([1, 1, 0, 0] => 1.7320508075688774 ; "slightly better")]
+    fn z_score(differences: [i64; 4]) -> f64 {
+        PairingResult {
+            boards: 4,
+            board_difference_sum: differences.iter().sum(),
+            board_difference_squares: differences.iter().map(|d| d * d).sum(),
+            ..PairingResult::create([Strategy::Advanced, Strategy::Basic])
+        }
+        .z_score()
+    }
+
+    #[test]
+    fn single_board_z_score() {
+        let pairing_result = PairingResult::create([Strategy::Advanced, Strategy::Basic])
+            .add((game_result([10, 6]), game_result([9, 10])));
+        assert_eq!(0f64, pairing_result.z_score());
+        assert!((pairing_result.p_value() - 1f64).abs() < 1e-6);
+    }
+
+    #[test_case(0f64 => 1f64)]
+    #[test_case(1.96 => 0.05)]
+    #[test_case(-2.576 => 0.01)]
+    fn p_value_from_z_score(z: f64) -> f64 {
+        (p_value(z) * 1000f64).round() / 1000f64
+    }
+
+    #[test]
+    fn plays_tournament() {
+        let (standings, pairing_results) =
+            play_tournament(&[Strategy::Basic, Strategy::Advanced], Rules::default(), 2);
+        assert_eq!(1, pairing_results.len());
+        assert_eq!(2, standings.len());
+        for standing in standings {
+            assert_eq!(4, standing.games);
+        }
+    }
+
+    #[test]
+    fn no_boards_has_no_rates() {
+        let (standings, pairing_results) =
+            play_tournament(&[Strategy::Basic, Strategy::Advanced], Rules::default(), 0);
+        assert_eq!(0f64, pairing_results[0].win_rate(0));
+        for standing in standings {
+            assert_eq!(0f64, standing.win_rate());
+            assert_eq!(0f64, standing.points_per_hand());
+        }
+    }
+}
@@ -13,10 +13,19 @@ pub(crate) struct TrickState {
 #[derive(Debug)]
 pub(crate) enum TrickPhase {
     BeforeFirstCard,
-    BeforeSecondCard { cards_played: [PlayedCard; 1] },
-    BeforeThirdCard { cards_played: [PlayedCard; 2] },
-    BeforeFourthCard { cards_played: [PlayedCard; 3] },
-    Done { trick_winner: Position },
+    BeforeSecondCard {
+        cards_played: [PlayedCard; 1],
+    },
+    BeforeThirdCard {
+        cards_played: [PlayedCard; 2],
+    },
+    BeforeFourthCard {
+        cards_played: [PlayedCard; 3],
+    },
+    Done {
+        trick_winner: Position,
+        cards_played: Vec<PlayedCard>,
+    },
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -74,6 +83,7 @@ impl TrickState {
                             &self.bid_result.trump(),
                             cards_played.as_slice(),
                         ),
+                        cards_played: cards_played.to_vec(),
                     };
                     for player in players {
                         player.trick_end(&self.bid_result, &cards_played);
@@ -108,6 +118,7 @@ impl TrickState {
                             &self.bid_result.trump(),
                             cards_played.as_slice(),
                         ),
+                        cards_played: cards_played.to_vec(),
                     };
                     for player in players {
                         player.trick_end(&self.bid_result, &cards_played);
@@ -131,6 +142,7 @@ impl TrickState {
                             &self.bid_result.trump(),
                             new_cards_played.as_slice(),
                         ),
+                        cards_played: new_cards_played.to_vec(),
                     };
                     for player in players {
                         player.trick_end(&self.bid_result, &new_cards_played);
@@ -138,7 +150,7 @@ impl TrickState {
                 }
                 None
             }
-            TrickPhase::Done { trick_winner } => Some(trick_winner),
+            TrickPhase::Done { trick_winner, .. } => Some(trick_winner),
         }
     }
 
@@ -150,7 +162,23 @@ impl TrickState {
         cards_played: &[PlayedCard],
     ) -> Card {
         let hand = &mut hands[player.index()];
-        let mut card = players[player.index()].play_card(hand, bid_result, cards_played);
+        let card = players[player.index()].play_card(hand, bid_result, cards_played);
+        let card = TrickState::legal_card(hand, card, cards_played);
+        hand.cards.retain(|c| c != &card);
+        card
+    }
+
+    pub(crate) fn cards_played(&self) -> &[PlayedCard] {
+        match &self.phase {
+            TrickPhase::BeforeFirstCard => &[],
+            TrickPhase::BeforeSecondCard { cards_played } => cards_played,
+            TrickPhase::BeforeThirdCard { cards_played } => cards_played,
+            TrickPhase::BeforeFourthCard { cards_played } => cards_played,
+            TrickPhase::Done { cards_played, .. } => cards_played,
+        }
+    }
+
+    pub(crate) fn legal_card(hand: &Hand, mut card: Card, cards_played: &[PlayedCard]) -> Card {
         if !hand.cards.contains(&card) {
             card = hand.cards[0]
         }
@@ -165,11 +193,27 @@ impl TrickState {
                 }
             }
         }
-        hand.cards.retain(|c| c != &card);
         card
     }
 
-    fn get_winning_position(&trump: &Suit, cards_played: &[PlayedCard]) -> Position {
+    pub(crate) fn legal_cards(hand: &Hand, cards_played: &[PlayedCard]) -> Vec<Card> {
+        let following_suit: Vec<Card> = match cards_played.first() {
+            Some(led_card) => hand
+                .cards
+                .iter()
+                .filter(|card| card.suit == led_card.card.suit)
+                .copied()
+                .collect(),
+            None => Vec::new(),
+        };
+        if following_suit.is_empty() {
+            hand.cards.clone()
+        } else {
+            following_suit
+        }
+    }
+
+    pub(crate) fn get_winning_position(&trump: &Suit, cards_played: &[PlayedCard]) -> Position {
         cards_played
             .iter()
             .reduce(|first_played_card, second_played_card| {
@@ -0,0 +1,669 @@
+use std::{
+    collections::VecDeque,
+    io,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use log::{Level, Metadata, Record};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    bid_result::BidResultCalled,
+    bid_state::BidPhase,
+    game_record::Recorder,
+    game_state::{GamePhase, GameState},
+    hand::Hand,
+    hand_state::HandPhase,
+    hint::Hint,
+    player::Player,
+    players::{recording::RecordingPlayer, registry::Strategy, tui::TuiPlayer, wrapper::Wrapper},
+    position::Position,
+    rules::Rules,
+    trick_state::{PlayedCard, TrickPhase},
+};
+
+const HIDDEN_CARD: &str = "\u{1F0A0}";
+const MAX_MESSAGES: usize = 100;
+const INITIAL_DELAY: Duration = Duration::from_millis(800);
+const MIN_DELAY: Duration = Duration::from_millis(50);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+const POSITIONS: [Position; 4] = [
+    Position::North,
+    Position::East,
+    Position::South,
+    Position::West,
+];
+
+static MESSAGES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+static MESSAGE_LOGGER: MessageLogger = MessageLogger;
+
+struct MessageLogger;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TableView {
+    pub(crate) dealer: Option<Position>,
+    pub(crate) scores: [u8; 2],
+    pub(crate) hands: [Vec<String>; 4],
+    pub(crate) up_card: Option<String>,
+    pub(crate) contract: Option<String>,
+    pub(crate) cards_played: Vec<(Position, String)>,
+    pub(crate) tricks_taken: [u8; 4],
+    pub(crate) result: Option<String>,
+    pub(crate) hint: Vec<String>,
+}
+
+pub(crate) struct Screen {
+    terminal: DefaultTerminal,
+    view: TableView,
+    quit: bool,
+}
+
+enum Command {
+    Step,
+    Quit,
+}
+
+impl log::Log for MessageLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut messages = MESSAGES.lock().unwrap();
+        messages.push_back(record.args().to_string());
+        if messages.len() > MAX_MESSAGES {
+            let _oldest = messages.pop_front();
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+impl TableView {
+    pub(crate) fn from_game<T: Player>(game_state: &GameState<T>, revealed: &[bool; 4]) -> Self {
+        let mut view = TableView {
+            scores: [game_state.north_south_score, game_state.east_west_score],
+            ..Default::default()
+        };
+        let hand_state = match &game_state.phase {
+            GamePhase::Playing { hand_state } => hand_state,
+            GamePhase::Done => return view,
+        };
+        view.dealer = Some(hand_state.dealer);
+        match &hand_state.phase {
+            HandPhase::Bidding { bid_state, hands } => {
+                view.hands = hands
+                    .clone()
+                    .map(|hand| hand.cards.iter().map(|card| card.to_string()).collect());
+                view.up_card = match &bid_state.phase {
+                    BidPhase::DealerNamesJokerSuit { trump_candidate }
+                    | BidPhase::FirstRoundFirstPlayer { trump_candidate }
+                    | BidPhase::FirstRoundSecondPlayer { trump_candidate }
+                    | BidPhase::FirstRoundThirdPlayer { trump_candidate }
+                    | BidPhase::FirstRoundFourthPlayer { trump_candidate } => {
+                        Some(format!("Up card: {}", trump_candidate))
+                    }
+                    BidPhase::OrderedUp { card_ordered, .. }
+                    | BidPhase::OrderedUpAlone { card_ordered, .. }
+                    | BidPhase::OrderedUpDefendedAlone { card_ordered, .. } => {
+                        Some(format!("Ordered up: {}", card_ordered))
+                    }
+                    BidPhase::SecondRoundFirstPlayer { turned_down }
+                    | BidPhase::SecondRoundSecondPlayer { turned_down }
+                    | BidPhase::SecondRoundThirdPlayer { turned_down }
+                    | BidPhase::SecondRoundFourthPlayer { turned_down } => {
+                        Some(format!("Turned down: {}", turned_down))
+                    }
+                    BidPhase::Done { .. } => None,
+                };
+            }
+            HandPhase::FirstTrick {
+                bid_result,
+                hands,
+                trick_state,
+            } => {
+                view.show_trick(bid_result, hands, trick_state.cards_played());
+            }
+            HandPhase::SecondTrick {
+                bid_result,
+                hands,
+                trick_state,
+                tricks_taken,
+            }
+            | HandPhase::ThirdTrick {
+                bid_result,
+                hands,
+                trick_state,
+                tricks_taken,
+            }
+            | HandPhase::FourthTrick {
+                bid_result,
+                hands,
+                trick_state,
+                tricks_taken,
+            }
+            | HandPhase::FifthTrick {
+                bid_result,
+                hands,
+                trick_state,
+                tricks_taken,
+            } => {
+                view.show_trick(bid_result, hands, trick_state.cards_played());
+                view.tricks_taken = *tricks_taken;
+            }
+            HandPhase::Scoring {
+                bid_result,
+                tricks_taken,
+            } => {
+                view.contract = Some(match bid_result.clone().try_into() {
+                    Ok(bid_result) => contract_text(&bid_result),
+                    Err(_) => "No one called trump".to_string(),
+                });
+                view.tricks_taken = *tricks_taken;
+            }
+        }
+        for (hand, revealed) in view.hands.iter_mut().zip(revealed) {
+            if !revealed {
+                *hand = vec![HIDDEN_CARD.to_string(); hand.len()];
+            }
+        }
+        view
+    }
+
+    fn show_trick(
+        &mut self,
+        bid_result: &BidResultCalled,
+        hands: &[Hand; 4],
+        cards_played: &[PlayedCard],
+    ) {
+        self.contract = Some(contract_text(bid_result));
+        self.hands = hands
+            .clone()
+            .map(|hand| hand.cards.iter().map(|card| card.to_string()).collect());
+        self.cards_played = cards_played
+            .iter()
+            .map(|played_card| (played_card.player, played_card.card.to_string()))
+            .collect();
+    }
+}
+
+impl Screen {
+    pub(crate) fn draw(&mut self, status: Line<'_>) -> io::Result<()> {
+        let view = &self.view;
+        let _frame = self.terminal.draw(|frame| render(frame, view, &status))?;
+        Ok(())
+    }
+
+    pub(crate) fn ask_yes_no(
+        &mut self,
+        question: &str,
+        hint: impl FnOnce() -> Hint,
+    ) -> Option<bool> {
+        if self.quit {
+            return None;
+        }
+        let mut hint = Some(hint);
+        let status = Line::from(vec![
+            Span::raw(question).bold(),
+            Span::raw("  y yes  n no  h hint  q quit"),
+        ]);
+        let answer = loop {
+            self.draw(status.clone()).ok()?;
+            match read_key().ok()? {
+                KeyCode::Char('y') => break Some(true),
+                KeyCode::Char('n') => break Some(false),
+                KeyCode::Char('h') => self.show_hint(&mut hint),
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    self.quit = true;
+                    break None;
+                }
+                _ => (),
+            }
+        };
+        self.view.hint.clear();
+        answer
+    }
+
+    pub(crate) fn ask_choice(
+        &mut self,
+        question: &str,
+        options: &[String],
+        hint: impl FnOnce() -> Hint,
+    ) -> Option<usize> {
+        if self.quit {
+            return None;
+        }
+        let mut hint = Some(hint);
+        let mut selected = 0;
+        let answer = loop {
+            let mut spans = vec![Span::raw(question).bold(), Span::raw(" ")];
+            for (index, option) in options.iter().enumerate() {
+                let style = if index == selected {
+                    Style::new().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::new()
+                };
+                spans.push(Span::styled(format!(" {} ", option), style));
+            }
+            spans.push(Span::raw(
+                "  \u{2190}/\u{2192} choose  Enter confirm  h hint  q quit",
+            ));
+            self.draw(Line::from(spans)).ok()?;
+            match read_key().ok()? {
+                KeyCode::Left => selected = (selected + options.len() - 1) % options.len(),
+                KeyCode::Right => selected = (selected + 1) % options.len(),
+                KeyCode::Enter => break Some(selected),
+                KeyCode::Char('h') => self.show_hint(&mut hint),
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    self.quit = true;
+                    break None;
+                }
+                _ => (),
+            }
+        };
+        self.view.hint.clear();
+        answer
+    }
+
+    fn show_hint(&mut self, hint: &mut Option<impl FnOnce() -> Hint>) {
+        if let Some(hint) = hint.take() {
+            self.view.hint = hint().lines();
+        }
+    }
+}
+
+pub(crate) fn run(
+    strategies: [Strategy; 4],
+    rules: Rules,
+    record: Option<&Path>,
+) -> io::Result<()> {
+    log::set_logger(&MESSAGE_LOGGER)
+        .map(|()| log::set_max_level(log::LevelFilter::Info))
+        .unwrap_or_else(|_| println!("Logging initialization failed!"));
+    let screen = Arc::new(Mutex::new(Screen {
+        terminal: ratatui::init(),
+        view: TableView::default(),
+        quit: false,
+    }));
+    let recorder = Recorder::create(rules);
+    let result = play(&screen, strategies, rules, &recorder);
+    ratatui::restore();
+    match record {
+        Some(record) => result.and(recorder.save(record)),
+        None => result,
+    }
+}
+
+fn play(
+    screen: &Arc<Mutex<Screen>>,
+    strategies: [Strategy; 4],
+    rules: Rules,
+    recorder: &Recorder,
+) -> io::Result<()> {
+    let has_human = strategies.contains(&Strategy::Human);
+    let revealed = strategies
+        .clone()
+        .map(|strategy| !has_human || strategy == Strategy::Human);
+    let players = POSITIONS.map(|position| {
+        let player: Box<dyn Player> = match &strategies[position.index()] {
+            Strategy::Human => Box::new(TuiPlayer::create(position, rules, screen.clone())),
+            strategy => strategy.create_player(position, rules),
+        };
+        Wrapper::create_single_player(Box::new(RecordingPlayer::create(
+            position,
+            player,
+            recorder.clone(),
+        )))
+    });
+    let mut game_state = GameState::create(players, rules, Position::South);
+    let mut paused = false;
+    let mut delay = INITIAL_DELAY;
+    loop {
+        screen.lock().unwrap().view = TableView::from_game(&game_state, &revealed);
+        if !has_human {
+            match wait(screen, &mut paused, &mut delay)? {
+                Command::Step => (),
+                Command::Quit => return Ok(()),
+            }
+        } else if let Some(message) = pause_message(&game_state) {
+            screen
+                .lock()
+                .unwrap()
+                .draw(Line::from(format!("{}  any key continue  q quit", message)))?;
+            if let KeyCode::Char('q') | KeyCode::Esc = read_key()? {
+                return Ok(());
+            }
+        }
+        recorder.observe(&game_state);
+        let result = game_state.step();
+        if screen.lock().unwrap().quit {
+            return Ok(());
+        }
+        if let Some(result) = result {
+            let mut screen = screen.lock().unwrap();
+            screen.view = TableView::from_game(&game_state, &[true; 4]);
+            screen.view.result = Some(result);
+            screen.draw(Line::from("Press any key to exit"))?;
+            let _key = read_key()?;
+            return Ok(());
+        }
+    }
+}
+
+fn wait(
+    screen: &Arc<Mutex<Screen>>,
+    paused: &mut bool,
+    delay: &mut Duration,
+) -> io::Result<Command> {
+    let deadline = Instant::now() + *delay;
+    loop {
+        screen
+            .lock()
+            .unwrap()
+            .draw(controls_help(*paused, *delay))?;
+        let timeout = if *paused {
+            MAX_DELAY
+        } else {
+            deadline.saturating_duration_since(Instant::now())
+        };
+        if !event::poll(timeout)? {
+            if *paused {
+                continue;
+            }
+            return Ok(Command::Step);
+        }
+        match read_key()? {
+            KeyCode::Char(' ') => *paused = !*paused,
+            KeyCode::Char('n') | KeyCode::Right => return Ok(Command::Step),
+            KeyCode::Char('+') => *delay = (*delay / 2).max(MIN_DELAY),
+            KeyCode::Char('-') => *delay = (*delay * 2).min(MAX_DELAY),
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Command::Quit),
+            _ => (),
+        }
+    }
+}
+
+fn read_key() -> io::Result<KeyCode> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(key.code);
+            }
+        }
+    }
+}
+
+fn pause_message<T: Player>(game_state: &GameState<T>) -> Option<&'static str> {
+    let hand_state = match &game_state.phase {
+        GamePhase::Playing { hand_state } => hand_state,
+        GamePhase::Done => return None,
+    };
+    match &hand_state.phase {
+        HandPhase::FirstTrick { trick_state, .. }
+        | HandPhase::SecondTrick { trick_state, .. }
+        | HandPhase::ThirdTrick { trick_state, .. }
+        | HandPhase::FourthTrick { trick_state, .. }
+        | HandPhase::FifthTrick { trick_state, .. } => match trick_state.phase {
+            TrickPhase::Done { .. } => Some("Trick over"),
+            _ => None,
+        },
+        HandPhase::Scoring { .. } => Some("Hand over"),
+        HandPhase::Bidding { .. } => None,
+    }
+}
+
+fn controls_help(paused: bool, delay: Duration) -> Line<'static> {
+    Line::from(format!(
+        "{}  space {}  n step  +/- speed ({} ms)  q quit",
+        if paused { "Paused" } else { "Playing" },
+        if paused { "resume" } else { "pause" },
+        delay.as_millis()
+    ))
+}
+
+fn contract_text(bid_result: &BidResultCalled) -> String {
+    match bid_result {
+        BidResultCalled::Called { trump, caller } => format!("{:?} called {}", caller, trump),
+        BidResultCalled::CalledAlone { trump, caller } => {
+            format!("{:?} called {} alone", caller, trump)
+        }
+        BidResultCalled::DefendedAlone {
+            trump,
+            caller,
+            defender,
+        } => format!(
+            "{:?} called {} alone, {:?} defending alone",
+            caller, trump, defender
+        ),
+    }
+}
+
+fn render(frame: &mut Frame<'_>, view: &TableView, status: &Line<'_>) {
+    let [score_area, table_area, messages_area, status_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(15),
+        Constraint::Length(8),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    frame.render_widget(
+        Paragraph::new(format!(
+            "Euchre   North/South {}   East/West {}",
+            view.scores[0], view.scores[1]
+        ))
+        .bold(),
+        score_area,
+    );
+    let [north_area, middle_area, south_area] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Min(7),
+        Constraint::Length(4),
+    ])
+    .areas(table_area);
+    let [west_area, center_area, east_area] = Layout::horizontal([
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+    ])
+    .areas(middle_area);
+    for (position, area) in [
+        (Position::North, north_area),
+        (Position::East, east_area),
+        (Position::South, south_area),
+        (Position::West, west_area),
+    ] {
+        render_seat(frame, view, position, area);
+    }
+    render_center(frame, view, center_area);
+    if view.hint.is_empty() {
+        let messages = MESSAGES.lock().unwrap();
+        let visible = messages_area.height.saturating_sub(2) as usize;
+        frame.render_widget(
+            Paragraph::new(
+                messages
+                    .iter()
+                    .skip(messages.len().saturating_sub(visible))
+                    .map(|message| Line::from(message.as_str()))
+                    .collect::<Vec<Line<'_>>>(),
+            )
+            .block(Block::bordered().title("Messages")),
+            messages_area,
+        );
+    } else {
+        frame.render_widget(
+            Paragraph::new(
+                view.hint
+                    .iter()
+                    .map(|line| Line::from(line.as_str()))
+                    .collect::<Vec<Line<'_>>>(),
+            )
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title("Hint")),
+            messages_area,
+        );
+    }
+    frame.render_widget(Paragraph::new(status.clone()), status_area);
+}
+
+fn render_seat(frame: &mut Frame<'_>, view: &TableView, position: Position, area: Rect) {
+    let title = if view.dealer == Some(position) {
+        format!("{:?} (dealer)", position)
+    } else {
+        format!("{:?}", position)
+    };
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(view.hands[position.index()].join(" ")),
+            Line::from(format!("Tricks: {}", view.tricks_taken[position.index()])),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::bordered().title(title)),
+        area,
+    );
+}
+
+fn render_center(frame: &mut Frame<'_>, view: &TableView, area: Rect) {
+    let mut lines = Vec::new();
+    if let Some(result) = &view.result {
+        lines.push(Line::from(result.as_str()).bold());
+    }
+    if let Some(up_card) = &view.up_card {
+        lines.push(Line::from(up_card.as_str()));
+    }
+    if let Some(contract) = &view.contract {
+        lines.push(Line::from(contract.as_str()));
+    }
+    lines.push(Line::from(""));
+    for (position, card) in &view.cards_played {
+        lines.push(Line::from(format!("{:?}: {}", position, card)));
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::bordered().title("Table")),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::advanced::AdvancedPlayer;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn game_state() -> GameState<AdvancedPlayer> {
+        GameState::create_seeded(
+            POSITIONS.map(AdvancedPlayer::create),
+            Rules::default(),
+            Position::East,
+            11,
+        )
+    }
+
+    #[test]
+    fn view_hides_unrevealed_hands() {
+        let view = TableView::from_game(&game_state(), &[false, false, true, false]);
+        assert_eq!(Some(Position::East), view.dealer);
+        assert_eq!(vec![HIDDEN_CARD.to_string(); 5], view.hands[0]);
+        assert_ne!(view.hands[0], view.hands[2]);
+        assert!(view.up_card.unwrap().starts_with("Up card: "));
+    }
+
+    #[test]
+    fn pauses_after_each_trick_and_hand() {
+        let mut game_state = game_state();
+        let mut pauses = Vec::new();
+        while let GamePhase::Playing { hand_state } = &game_state.phase {
+            let dealer = hand_state.dealer;
+            pauses.extend(pause_message(&game_state));
+            let _result = game_state.step();
+            if let GamePhase::Playing { hand_state } = &game_state.phase {
+                if hand_state.dealer != dealer {
+                    break;
+                }
+            }
+        }
+        assert_eq!(Some(&"Hand over"), pauses.last());
+        assert!(pauses[..pauses.len() - 1]
+            .iter()
+            .all(|message| *message == "Trick over"));
+    }
+
+    #[test]
+    fn view_shows_current_trick() {
+        let mut game_state = game_state();
+        let mut saw_trick = false;
+        while game_state.step().is_none() {
+            let view = TableView::from_game(&game_state, &[true; 4]);
+            if !view.cards_played.is_empty() {
+                saw_trick = true;
+                assert!(view.contract.is_some());
+                assert!(view.cards_played.len() <= 4);
+            }
+        }
+        assert!(saw_trick);
+        assert!(TableView::from_game(&game_state, &[true; 4])
+            .scores
+            .iter()
+            .any(|score| *score >= 10));
+    }
+
+    #[test]
+    fn renders_all_seats() {
+        let view = TableView::from_game(&game_state(), &[true; 4]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        let _frame = terminal
+            .draw(|frame| render(frame, &view, &Line::from("status")))
+            .unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        for label in [
+            "North",
+            "East (dealer)",
+            "South",
+            "West",
+            "Up card",
+            "status",
+        ] {
+            assert!(text.contains(label), "{} is missing", label);
+        }
+    }
+
+    #[test]
+    fn renders_hint_in_place_of_messages() {
+        let mut view = TableView::from_game(&game_state(), &[true; 4]);
+        view.hint = vec!["Advanced would pass".to_string(), "  1 trump".to_string()];
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        let _frame = terminal
+            .draw(|frame| render(frame, &view, &Line::from("status")))
+            .unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(text.contains("Hint"));
+        assert!(text.contains("Advanced would pass"));
+        assert!(!text.contains("Messages"));
+    }
+}
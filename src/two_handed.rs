@@ -0,0 +1,321 @@
+use crate::{
+    bid_result::BidResultCalled,
+    bid_state::BidState,
+    card::CardBeforeBidding,
+    deck::Deck,
+    hand::{Hand, HandBeforeBidding},
+    player::Player,
+    position::Position,
+    rules::Rules,
+    trick_state::{PlayedCard, TrickState},
+};
+
+pub(crate) const SEATS: [Position; 2] = [Position::South, Position::West];
+
+pub(crate) struct TwoHandedGameState<T: Player> {
+    pub(crate) players: [T; 2],
+    pub(crate) rules: Rules,
+    pub(crate) phase: TwoHandedGamePhase,
+    pub(crate) scores: [u8; 2],
+}
+
+pub(crate) enum TwoHandedGamePhase {
+    Playing { hand_state: TwoHandedHandState },
+    Done,
+}
+
+#[derive(Debug)]
+pub(crate) struct TwoHandedHandState {
+    pub(crate) dealer: usize,
+    pub(crate) phase: TwoHandedHandPhase,
+}
+
+#[derive(Debug)]
+pub(crate) enum TwoHandedHandPhase {
+    FirstRound {
+        bidder: usize,
+        trump_candidate: CardBeforeBidding,
+        hands: [HandBeforeBidding; 2],
+    },
+    SecondRound {
+        bidder: usize,
+        turned_down: CardBeforeBidding,
+        hands: [HandBeforeBidding; 2],
+    },
+    Playing {
+        bid_result: BidResultCalled,
+        hands: [Hand; 2],
+        leader: usize,
+        tricks_taken: [u8; 2],
+    },
+    Scoring {
+        bid_result: Option<BidResultCalled>,
+        tricks_taken: [u8; 2],
+    },
+}
+
+impl<T: Player> TwoHandedGameState<T> {
+    pub(crate) fn create(players: [T; 2], rules: Rules) -> TwoHandedGameState<T> {
+        let (hands, trump_candidate) = Deck::create_shuffled_deck(&rules).deal_two_handed();
+        TwoHandedGameState {
+            players,
+            rules,
+            phase: TwoHandedGamePhase::Playing {
+                hand_state: TwoHandedHandState::create(0, trump_candidate, hands),
+            },
+            scores: [0; 2],
+        }
+    }
+
+    pub(crate) fn step(&mut self) -> Option<String> {
+        match &mut self.phase {
+            TwoHandedGamePhase::Playing { ref mut hand_state } => {
+                if let Some((seat, score)) = hand_state.step(&mut self.players) {
+                    self.finish_hand(seat, score)
+                }
+                None
+            }
+            TwoHandedGamePhase::Done => {
+                let winner = if self.scores[0] >= 10 { 0 } else { 1 };
+                Some(format!(
+                    "{:?} wins! Final score: {}-{}",
+                    SEATS[winner],
+                    self.scores[winner],
+                    self.scores[1 - winner],
+                ))
+            }
+        }
+    }
+
+    fn finish_hand(&mut self, seat: usize, score: u8) {
+        if score > 0 {
+            self.scores[seat] += score;
+            log::info!(
+                "{:?} scored {} points: {}-{}",
+                SEATS[seat],
+                score,
+                self.scores[seat],
+                self.scores[1 - seat]
+            );
+            if self.scores[seat] >= 10 {
+                self.phase = TwoHandedGamePhase::Done;
+            }
+        }
+        match &self.phase {
+            TwoHandedGamePhase::Playing { hand_state } => {
+                let (hands, trump_candidate) =
+                    Deck::create_shuffled_deck(&self.rules).deal_two_handed();
+                self.phase = TwoHandedGamePhase::Playing {
+                    hand_state: TwoHandedHandState::create(
+                        1 - hand_state.dealer,
+                        trump_candidate,
+                        hands,
+                    ),
+                }
+            }
+            TwoHandedGamePhase::Done => (),
+        }
+    }
+}
+
+impl TwoHandedHandState {
+    pub(crate) fn create(
+        dealer: usize,
+        trump_candidate: CardBeforeBidding,
+        hands: [HandBeforeBidding; 2],
+    ) -> TwoHandedHandState {
+        TwoHandedHandState {
+            dealer,
+            phase: TwoHandedHandPhase::FirstRound {
+                bidder: 1 - dealer,
+                trump_candidate,
+                hands,
+            },
+        }
+    }
+
+    pub(crate) fn step(&mut self, players: &mut [impl Player; 2]) -> Option<(usize, u8)> {
+        let dealer = SEATS[self.dealer];
+        match &mut self.phase {
+            TwoHandedHandPhase::FirstRound {
+                bidder,
+                trump_candidate,
+                hands,
+            } => {
+                if players[*bidder].should_order_up(&hands[*bidder], &dealer, trump_candidate) {
+                    log::info!("{:?} ordered up {}", SEATS[*bidder], trump_candidate);
+                    BidState::discard(
+                        &mut players[self.dealer],
+                        &mut hands[self.dealer],
+                        *trump_candidate,
+                    );
+                    self.phase = TwoHandedHandState::start_playing(
+                        BidResultCalled::Called {
+                            trump: trump_candidate.suit,
+                            caller: SEATS[*bidder],
+                        },
+                        hands.clone(),
+                        1 - self.dealer,
+                    );
+                } else if *bidder == self.dealer {
+                    self.phase = TwoHandedHandPhase::SecondRound {
+                        bidder: 1 - self.dealer,
+                        turned_down: *trump_candidate,
+                        hands: hands.clone(),
+                    };
+                } else {
+                    *bidder = self.dealer;
+                }
+                None
+            }
+            TwoHandedHandPhase::SecondRound {
+                bidder,
+                turned_down,
+                hands,
+            } => {
+                match players[*bidder].call_trump(&hands[*bidder], &dealer, turned_down) {
+                    Some(trump) if trump != turned_down.suit => {
+                        log::info!("{:?} called {}", SEATS[*bidder], trump);
+                        self.phase = TwoHandedHandState::start_playing(
+                            BidResultCalled::Called {
+                                trump,
+                                caller: SEATS[*bidder],
+                            },
+                            hands.clone(),
+                            1 - self.dealer,
+                        );
+                    }
+                    _ if *bidder == self.dealer => {
+                        log::info!("{}", "No one called a trump suit");
+                        self.phase = TwoHandedHandPhase::Scoring {
+                            bid_result: None,
+                            tricks_taken: [0; 2],
+                        };
+                    }
+                    _ => *bidder = self.dealer,
+                }
+                None
+            }
+            TwoHandedHandPhase::Playing {
+                bid_result,
+                hands,
+                leader,
+                tricks_taken,
+            } => {
+                let mut cards_played = Vec::with_capacity(2);
+                for seat in [*leader, 1 - *leader] {
+                    let hand = &mut hands[seat];
+                    let card = players[seat].play_card(hand, bid_result, &cards_played);
+                    let card = TrickState::legal_card(hand, card, &cards_played);
+                    hand.cards.retain(|c| c != &card);
+                    cards_played.push(PlayedCard {
+                        player: SEATS[seat],
+                        card,
+                    });
+                }
+                for player in players.iter_mut() {
+                    player.trick_end(bid_result, &cards_played);
+                }
+                let trick_winner = TwoHandedHandState::seat_index(
+                    TrickState::get_winning_position(&bid_result.trump(), &cards_played),
+                );
+                tricks_taken[trick_winner] += 1;
+                if hands[trick_winner].cards.is_empty() {
+                    self.phase = TwoHandedHandPhase::Scoring {
+                        bid_result: Some(bid_result.clone()),
+                        tricks_taken: *tricks_taken,
+                    };
+                } else {
+                    *leader = trick_winner;
+                }
+                None
+            }
+            TwoHandedHandPhase::Scoring {
+                bid_result: Some(bid_result),
+                tricks_taken,
+            } => Some(TwoHandedHandState::get_score(bid_result, tricks_taken)),
+            TwoHandedHandPhase::Scoring {
+                bid_result: None, ..
+            } => Some((self.dealer, 0)),
+        }
+    }
+
+    fn start_playing(
+        bid_result: BidResultCalled,
+        hands: [HandBeforeBidding; 2],
+        leader: usize,
+    ) -> TwoHandedHandPhase {
+        let trump = bid_result.trump();
+        TwoHandedHandPhase::Playing {
+            bid_result,
+            hands: hands.map(|hand| HandBeforeBidding::update_bowers(hand, &trump)),
+            leader,
+            tricks_taken: [0; 2],
+        }
+    }
+
+    fn seat_index(position: Position) -> usize {
+        SEATS.iter().position(|&seat| seat == position).unwrap()
+    }
+
+    fn get_score(bid_result: &BidResultCalled, tricks_taken: &[u8; 2]) -> (usize, u8) {
+        let caller = TwoHandedHandState::seat_index(bid_result.caller());
+        match tricks_taken[caller] {
+            5 => (caller, 2),
+            3 | 4 => (caller, 1),
+            _ => (1 - caller, 2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{players::advanced::AdvancedPlayer, suit::Suit};
+    use test_case::test_case;
+
+    #[test_case(Position::South, [5, 0] => (0, 2))]
+    #[test_case(Position::South, [4, 1] => (0, 1))]
+    #[test_case(Position::South, [3, 2] => (0, 1))]
+    #[test_case(Position::South, [2, 3] => (1, 2))]
+    #[test_case(Position::West, [0, 5] => (1, 2))]
+    #[test_case(Position::West, [5, 0] => (0, 2))]
+    fn get_score(caller: Position, tricks_taken: [u8; 2]) -> (usize, u8) {
+        TwoHandedHandState::get_score(
+            &BidResultCalled::Called {
+                trump: Suit::Spades,
+                caller,
+            },
+            &tricks_taken,
+        )
+    }
+
+    #[test]
+    fn deal_two_handed() {
+        let mut deck = Deck::create_shuffled_deck(&Rules::default());
+        let (hands, trump_candidate) = deck.deal_two_handed();
+        assert_eq!(5, hands[0].cards.len());
+        assert_eq!(5, hands[1].cards.len());
+        assert_eq!(13, deck.cards.len());
+        assert!(!hands[0].cards.contains(&trump_candidate));
+        assert!(!hands[1].cards.contains(&trump_candidate));
+    }
+
+    #[test]
+    fn plays_full_game() {
+        let mut game_state = TwoHandedGameState::create(
+            [
+                AdvancedPlayer::create(SEATS[0]),
+                AdvancedPlayer::create(SEATS[1]),
+            ],
+            Rules::default(),
+        );
+        let result = loop {
+            if let Some(result) = game_state.step() {
+                break result;
+            }
+        };
+        assert!(game_state.scores.iter().any(|&score| score >= 10));
+        assert!(result.contains("wins!"));
+    }
+}